@@ -0,0 +1,398 @@
+use std::any::{Any, TypeId, type_name};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use futures::future::BoxFuture;
+use futures::lock::Mutex as AsyncMutex;
+use crate::error::ContainerError;
+use crate::lifetime::Lifetime;
+
+type Instance = Arc<dyn Any + Send + Sync>;
+type Factory = Arc<dyn Fn(Resolver) -> BoxFuture<'static, Result<Instance, ContainerError>> + Send + Sync>;
+type Cell = Arc<AsyncMutex<Option<Instance>>>;
+
+struct Provider {
+    type_name: &'static str,
+    lifetime: Lifetime,
+    factory: Factory,
+    singleton: Cell,
+}
+
+struct Registry {
+    providers: HashMap<TypeId, Provider>,
+}
+
+#[derive(Default)]
+struct ScopeState {
+    cells: Mutex<HashMap<TypeId, Cell>>,
+}
+
+impl ScopeState {
+    fn cell(&self, type_id: TypeId) -> Cell {
+        let mut cells = self.cells.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        cells.entry(type_id).or_default().clone()
+    }
+}
+
+/// 프로바이더 등록을 모아 [`Container`] 를 만듭니다.
+///
+/// 같은 타입을 여러 번 등록하면 마지막 등록이 사용됩니다. (테스트에서 구현을 바꿔 끼울 때 유용합니다.)
+///
+/// # 예시
+///
+/// ```ignore
+/// let container = ContainerBuilder::new()
+///     .instance(pool)
+///     .singleton(|resolver: Resolver| async move {
+///         let pool = resolver.get::<PgPool>().await?;
+///         Ok(UserRepository::new(pool.as_ref().clone()))
+///     })
+///     .build();
+/// ```
+#[derive(Default)]
+pub struct ContainerBuilder {
+    providers: HashMap<TypeId, Provider>,
+}
+
+impl ContainerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn singleton<T, F, Fut>(self, factory: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Resolver) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, ContainerError>> + Send + 'static,
+    {
+        self.provide(Lifetime::Singleton, factory)
+    }
+
+    pub fn scoped<T, F, Fut>(self, factory: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Resolver) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, ContainerError>> + Send + 'static,
+    {
+        self.provide(Lifetime::Scoped, factory)
+    }
+
+    pub fn transient<T, F, Fut>(self, factory: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Resolver) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, ContainerError>> + Send + 'static,
+    {
+        self.provide(Lifetime::Transient, factory)
+    }
+
+    /// 이미 만들어진 값을 싱글톤으로 등록합니다.
+    pub fn instance<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        let instance: Instance = Arc::new(value);
+        let factory_instance = instance.clone();
+
+        self.providers.insert(
+            TypeId::of::<T>(),
+            Provider {
+                type_name: type_name::<T>(),
+                lifetime: Lifetime::Singleton,
+                factory: Arc::new(move |_| {
+                    let instance = factory_instance.clone();
+                    Box::pin(async move { Ok(instance) })
+                }),
+                singleton: Arc::new(AsyncMutex::new(Some(instance))),
+            },
+        );
+        self
+    }
+
+    pub fn provide<T, F, Fut>(mut self, lifetime: Lifetime, factory: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(Resolver) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, ContainerError>> + Send + 'static,
+    {
+        let factory: Factory = Arc::new(move |resolver| {
+            let future = factory(resolver);
+            Box::pin(async move { future.await.map(|value| Arc::new(value) as Instance) })
+        });
+
+        self.providers.insert(
+            TypeId::of::<T>(),
+            Provider {
+                type_name: type_name::<T>(),
+                lifetime,
+                factory,
+                singleton: Arc::new(AsyncMutex::new(None)),
+            },
+        );
+        self
+    }
+
+    pub fn build(self) -> Container {
+        Container {
+            registry: Arc::new(Registry {
+                providers: self.providers,
+            }),
+        }
+    }
+}
+
+/// 타입별 프로바이더 레지스트리
+///
+/// `Clone` 은 같은 레지스트리와 싱글톤 인스턴스를 공유합니다.
+#[derive(Clone)]
+pub struct Container {
+    registry: Arc<Registry>,
+}
+
+impl Container {
+    pub fn builder() -> ContainerBuilder {
+        ContainerBuilder::new()
+    }
+
+    /// 스코프 없이 인스턴스를 가져옵니다. scoped 프로바이더는 [`Container::scope`] 안에서만 사용할 수 있습니다.
+    pub async fn get<T: Send + Sync + 'static>(&self) -> Result<Arc<T>, ContainerError> {
+        self.resolver(None).get::<T>().await
+    }
+
+    /// 새 스코프를 엽니다. scoped 인스턴스는 스코프가 살아있는 동안 공유됩니다.
+    pub fn scope(&self) -> Scope {
+        Scope {
+            container: self.clone(),
+            state: Arc::new(ScopeState::default()),
+        }
+    }
+
+    /// 등록된 모든 싱글톤을 미리 생성합니다.
+    ///
+    /// 애플리케이션 시작 시 호출하면 누락된 프로바이더나 순환 의존성을
+    /// 첫 요청이 아닌 부팅 단계에서 발견할 수 있습니다.
+    pub async fn init_singletons(&self) -> Result<(), ContainerError> {
+        let resolver = self.resolver(None);
+
+        for (type_id, provider) in &self.registry.providers {
+            if provider.lifetime == Lifetime::Singleton {
+                resolver.resolve(*type_id, provider.type_name).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn resolver(&self, scope: Option<Arc<ScopeState>>) -> Resolver {
+        Resolver {
+            registry: self.registry.clone(),
+            scope,
+            path: Vec::new(),
+        }
+    }
+}
+
+/// 요청 단위 등 짧은 수명의 인스턴스를 보관하는 스코프
+#[derive(Clone)]
+pub struct Scope {
+    container: Container,
+    state: Arc<ScopeState>,
+}
+
+impl Scope {
+    pub async fn get<T: Send + Sync + 'static>(&self) -> Result<Arc<T>, ContainerError> {
+        self.container
+            .resolver(Some(self.state.clone()))
+            .get::<T>()
+            .await
+    }
+}
+
+#[derive(Clone)]
+struct Frame {
+    type_id: TypeId,
+    type_name: &'static str,
+    lifetime: Lifetime,
+}
+
+/// 팩토리에 전달되어 의존성을 꺼낼 때 사용합니다.
+///
+/// 현재까지의 해석 경로를 들고 다니며, 같은 타입이 다시 나타나면 순환 의존성으로 판단합니다.
+#[derive(Clone)]
+pub struct Resolver {
+    registry: Arc<Registry>,
+    scope: Option<Arc<ScopeState>>,
+    path: Vec<Frame>,
+}
+
+impl Resolver {
+    pub async fn get<T: Send + Sync + 'static>(&self) -> Result<Arc<T>, ContainerError> {
+        let name = type_name::<T>();
+
+        self.resolve(TypeId::of::<T>(), name)
+            .await?
+            .downcast::<T>()
+            .map_err(|_| ContainerError::TypeMismatch(name))
+    }
+
+    fn resolve(&self, type_id: TypeId, name: &'static str) -> BoxFuture<'_, Result<Instance, ContainerError>> {
+        Box::pin(async move {
+            if let Some(position) = self.path.iter().position(|frame| frame.type_id == type_id) {
+                let cycle = self.path[position..]
+                    .iter()
+                    .map(|frame| frame.type_name)
+                    .chain(std::iter::once(name))
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                return Err(ContainerError::Cycle(cycle));
+            }
+
+            let provider = self
+                .registry
+                .providers
+                .get(&type_id)
+                .ok_or(ContainerError::NotRegistered(name))?;
+
+            if provider.lifetime == Lifetime::Scoped
+                && let Some(singleton) = self.path.iter().find(|frame| frame.lifetime == Lifetime::Singleton)
+            {
+                return Err(ContainerError::CaptiveDependency {
+                    singleton: singleton.type_name,
+                    scoped: name,
+                });
+            }
+
+            let mut child = self.clone();
+            child.path.push(Frame {
+                type_id,
+                type_name: provider.type_name,
+                lifetime: provider.lifetime,
+            });
+
+            let cell = match provider.lifetime {
+                Lifetime::Transient => return (provider.factory)(child).await,
+                Lifetime::Singleton => provider.singleton.clone(),
+                Lifetime::Scoped => self
+                    .scope
+                    .as_ref()
+                    .ok_or(ContainerError::ScopeRequired(name))?
+                    .cell(type_id),
+            };
+
+            let mut cell = cell.lock().await;
+
+            if let Some(instance) = cell.as_ref() {
+                return Ok(instance.clone());
+            }
+
+            let instance = (provider.factory)(child).await?;
+            *cell = Some(instance.clone());
+
+            Ok(instance)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Config {
+        url: String,
+    }
+
+    struct Repository {
+        url: String,
+    }
+
+    struct RequestId(usize);
+
+    struct A;
+    struct B;
+
+    #[ntex::test]
+    async fn test_resolve_async_factory_dependencies() {
+        let container = ContainerBuilder::new()
+            .instance(Config { url: "postgres://localhost".to_string() })
+            .singleton(|resolver: Resolver| async move {
+                let config = resolver.get::<Config>().await?;
+                Ok(Repository { url: config.url.clone() })
+            })
+            .build();
+
+        let first = container.get::<Repository>().await.unwrap();
+        let second = container.get::<Repository>().await.unwrap();
+
+        assert_eq!(first.url, "postgres://localhost");
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[ntex::test]
+    async fn test_scoped_and_transient_lifetimes() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let scoped_counter = counter.clone();
+
+        let container = ContainerBuilder::new()
+            .scoped(move |_| {
+                let id = scoped_counter.fetch_add(1, Ordering::SeqCst);
+                async move { Ok(RequestId(id)) }
+            })
+            .transient(|_| async { Ok(String::from("transient")) })
+            .build();
+
+        let scope = container.scope();
+        let first = scope.get::<RequestId>().await.unwrap();
+        let same = scope.get::<RequestId>().await.unwrap();
+        let other = container.scope().get::<RequestId>().await.unwrap();
+
+        assert_eq!(first.0, same.0);
+        assert_ne!(first.0, other.0);
+
+        let a = container.get::<String>().await.unwrap();
+        let b = container.get::<String>().await.unwrap();
+        assert!(!Arc::ptr_eq(&a, &b));
+
+        assert!(matches!(
+            container.get::<RequestId>().await,
+            Err(ContainerError::ScopeRequired(_))
+        ));
+    }
+
+    #[ntex::test]
+    async fn test_cycle_is_reported_with_path() {
+        let container = ContainerBuilder::new()
+            .singleton(|resolver: Resolver| async move {
+                resolver.get::<B>().await?;
+                Ok(A)
+            })
+            .singleton(|resolver: Resolver| async move {
+                resolver.get::<A>().await?;
+                Ok(B)
+            })
+            .build();
+
+        let error = container.init_singletons().await.err().unwrap();
+        let message = error.to_string();
+
+        assert!(message.starts_with("circular dependency detected: "));
+        assert_eq!(message.matches(" -> ").count(), 2);
+    }
+
+    #[ntex::test]
+    async fn test_singleton_cannot_capture_scoped() {
+        let container = ContainerBuilder::new()
+            .scoped(|_| async { Ok(RequestId(0)) })
+            .singleton(|resolver: Resolver| async move {
+                resolver.get::<RequestId>().await?;
+                Ok(A)
+            })
+            .build();
+
+        assert!(matches!(
+            container.scope().get::<A>().await,
+            Err(ContainerError::CaptiveDependency { .. })
+        ));
+        assert!(matches!(
+            container.get::<B>().await,
+            Err(ContainerError::NotRegistered(_))
+        ));
+    }
+}
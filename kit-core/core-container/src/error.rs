@@ -0,0 +1,60 @@
+use ntex::web::{DefaultError, WebResponseError};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ContainerError {
+    #[error("no provider registered for `{0}`")]
+    NotRegistered(&'static str),
+
+    #[error("circular dependency detected: {0}")]
+    Cycle(String),
+
+    #[error("`{0}` is scoped and can only be resolved inside a scope")]
+    ScopeRequired(&'static str),
+
+    #[error("singleton `{singleton}` cannot depend on scoped `{scoped}`")]
+    CaptiveDependency {
+        singleton: &'static str,
+        scoped: &'static str,
+    },
+
+    #[error("`{type_name}` is not exported to module `{module}`")]
+    NotExported {
+        type_name: &'static str,
+        module: &'static str,
+    },
+
+    #[error("provider for `{0}` returned a value of a different type")]
+    TypeMismatch(&'static str),
+
+    #[error("container is not registered as application state")]
+    NotConfigured,
+
+    #[error("failed to construct `{type_name}`: {message}")]
+    Factory {
+        type_name: &'static str,
+        message: String,
+    },
+}
+
+impl ContainerError {
+    /// 팩토리 내부에서 발생한 에러를 감쌉니다.
+    ///
+    /// # 예시
+    ///
+    /// ```ignore
+    /// PgPoolOptions::new()
+    ///     .connect(&url)
+    ///     .await
+    ///     .map_err(ContainerError::factory::<PgPool>)
+    /// ```
+    pub fn factory<T>(error: impl std::fmt::Display) -> Self {
+        ContainerError::Factory {
+            type_name: std::any::type_name::<T>(),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// 핸들러 인자 주입에 실패하면 500 으로 응답합니다.
+impl WebResponseError<DefaultError> for ContainerError {}
@@ -0,0 +1,109 @@
+use std::ops::Deref;
+use std::sync::Arc;
+use ntex::http::Payload;
+use ntex::web::{FromRequest, HttpRequest};
+use crate::container::{Container, Scope};
+use crate::error::ContainerError;
+
+/// 컨테이너에서 `T` 를 꺼내 핸들러 인자로 주입합니다.
+///
+/// 애플리케이션에 `.state(container)` 로 [`Container`] 가 등록되어 있어야 하며,
+/// scoped 프로바이더는 요청마다 하나의 인스턴스를 공유합니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// #[post("/user")]
+/// async fn create_user(handler: Inject<UserRegisterCommandHandler>) -> impl Responder { ... }
+/// ```
+pub struct Inject<T>(pub Arc<T>);
+
+impl<T> Inject<T> {
+    pub fn into_inner(self) -> Arc<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for Inject<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T, Err> FromRequest<Err> for Inject<T>
+where
+    T: Send + Sync + 'static,
+{
+    type Error = ContainerError;
+
+    async fn from_request(req: &HttpRequest, _: &mut Payload) -> Result<Self, Self::Error> {
+        let scope = request_scope(req)?;
+        scope.get::<T>().await.map(Inject)
+    }
+}
+
+/// 요청 스코프 자체를 꺼냅니다. 핸들러 타입을 실행 시점에 고르는 버스 등에서 사용합니다.
+impl<Err> FromRequest<Err> for Scope {
+    type Error = ContainerError;
+
+    async fn from_request(req: &HttpRequest, _: &mut Payload) -> Result<Self, Self::Error> {
+        request_scope(req)
+    }
+}
+
+// 같은 요청 안의 주입은 하나의 스코프를 공유한다.
+fn request_scope(req: &HttpRequest) -> Result<Scope, ContainerError> {
+    if let Some(scope) = req.extensions().get::<Scope>() {
+        return Ok(scope.clone());
+    }
+
+    let container = req
+        .app_state::<Container>()
+        .ok_or(ContainerError::NotConfigured)?;
+    let scope = container.scope();
+    req.extensions_mut().insert(scope.clone());
+
+    Ok(scope)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::web::{self, App, HttpResponse, test};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Counter(usize);
+
+    #[ntex::test]
+    async fn test_inject_shares_scope_within_request() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let factory_created = created.clone();
+
+        let container = Container::builder()
+            .scoped(move |_| {
+                let id = factory_created.fetch_add(1, Ordering::SeqCst);
+                async move { Ok(Counter(id)) }
+            })
+            .build();
+
+        let app = test::init_service(
+            App::new().state(container).route(
+                "/",
+                web::get().to(|a: Inject<Counter>, b: Inject<Counter>| async move {
+                    assert_eq!(a.0.0, b.0.0);
+                    HttpResponse::Ok().body(a.0.0.to_string())
+                }),
+            ),
+        )
+        .await;
+
+        let first = test::call_service(&app, test::TestRequest::get().uri("/").to_request()).await;
+        let second = test::call_service(&app, test::TestRequest::get().uri("/").to_request()).await;
+
+        assert!(first.status().is_success());
+        assert_eq!(test::read_body(second).await, "1");
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1,14 +1,9 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+mod container;
+mod error;
+mod inject;
+mod lifetime;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use container::{Container, ContainerBuilder, Resolver, Scope};
+pub use error::ContainerError;
+pub use inject::Inject;
+pub use lifetime::Lifetime;
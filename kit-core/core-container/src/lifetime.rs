@@ -0,0 +1,10 @@
+/// 프로바이더가 만든 인스턴스의 수명
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lifetime {
+    /// 컨테이너 전체에서 하나의 인스턴스를 공유합니다.
+    Singleton,
+    /// 스코프(일반적으로 HTTP 요청 하나)마다 하나의 인스턴스를 만듭니다.
+    Scoped,
+    /// 요청할 때마다 새 인스턴스를 만듭니다.
+    Transient,
+}
@@ -0,0 +1,98 @@
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use ntex::http::HeaderMap;
+use ntex::web::HttpRequest;
+
+/// 요청을 서비스 간에 추적하기 위한 헤더
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+/// 게이트웨이나 로드밸런서가 붙이는 요청 ID 헤더. 상관 ID 가 없으면 이 값을 사용합니다.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// 요청 하나를 식별하는 상관 ID
+///
+/// [`crate::ExceptionBoundary`] 가 요청 확장(extensions)에 넣어두며, 모든 에러 응답 본문과
+/// `x-correlation-id` 응답 헤더에 포함됩니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrelationId(String);
+
+impl CorrelationId {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// 32자리 16진수 ID 를 생성합니다.
+    pub fn generate() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos())
+            .unwrap_or_default();
+
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+        hasher.write_u128(nanos);
+        let high = hasher.finish();
+        hasher.write_u64(high);
+        let low = hasher.finish();
+
+        Self(format!("{:016x}{:016x}", high, low))
+    }
+
+    /// 상위 서비스가 전달한 상관 ID(또는 요청 ID)를 읽습니다.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        [CORRELATION_ID_HEADER, REQUEST_ID_HEADER]
+            .iter()
+            .filter_map(|name| headers.get(*name))
+            .filter_map(|value| value.to_str().ok())
+            .map(str::trim)
+            .find(|value| !value.is_empty() && value.len() <= 128)
+            .map(Self::new)
+    }
+
+    /// 요청 확장에 저장된 상관 ID, 없으면 요청 헤더의 값
+    pub fn of(req: &HttpRequest) -> Option<Self> {
+        req.extensions()
+            .get::<CorrelationId>()
+            .cloned()
+            .or_else(|| Self::from_headers(req.headers()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::http::header::{HeaderName, HeaderValue};
+
+    #[test]
+    fn test_generate_unique_ids() {
+        let first = CorrelationId::generate();
+        let second = CorrelationId::generate();
+
+        assert_eq!(first.as_str().len(), 32);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_correlation_header_takes_precedence() {
+        let mut headers = HeaderMap::new();
+        headers.insert(HeaderName::from_static(REQUEST_ID_HEADER), HeaderValue::from_static("request"));
+        assert_eq!(CorrelationId::from_headers(&headers).unwrap().as_str(), "request");
+
+        headers.insert(HeaderName::from_static(CORRELATION_ID_HEADER), HeaderValue::from_static("correlation"));
+        assert_eq!(CorrelationId::from_headers(&headers).unwrap().as_str(), "correlation");
+    }
+}
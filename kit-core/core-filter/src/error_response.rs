@@ -0,0 +1,143 @@
+use ntex::http::StatusCode;
+use std::borrow::Cow;
+use ntex::web::HttpResponse;
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::Serialize;
+use serde_json::Value;
+use crate::correlation::CorrelationId;
+
+/// 모든 에러 응답이 공유하는 본문
+///
+/// ```json
+/// { "status": 409, "code": "conflict", "message": "...", "details": {...}, "correlation_id": "..." }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ErrorResponse {
+    status: StatusCode,
+    code: String,
+    message: String,
+    details: Option<Value>,
+}
+
+#[derive(Serialize)]
+struct Body<'a> {
+    status: u16,
+    code: &'a str,
+    message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<&'a Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correlation_id: Option<&'a str>,
+}
+
+impl ErrorResponse {
+    pub fn new(status: StatusCode, code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code: code.into(),
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// 상태 코드의 표준 문구로 응답을 만듭니다. (`404` → `not_found`, `Not Found`)
+    pub fn from_status(status: StatusCode) -> Self {
+        let reason = status.canonical_reason().unwrap_or("Unknown Error");
+        let code = reason
+            .to_ascii_lowercase()
+            .replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+
+        Self::new(status, code, reason)
+    }
+
+    /// 내부 정보를 노출하지 않는 500 응답
+    pub fn internal() -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "internal_error", "Internal Server Error")
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn to_json(&self, correlation_id: Option<&CorrelationId>) -> String {
+        let body = Body {
+            status: self.status.as_u16(),
+            code: &self.code,
+            message: &self.message,
+            details: self.details.as_ref(),
+            correlation_id: correlation_id.map(CorrelationId::as_str),
+        };
+
+        serde_json::to_string(&body).unwrap_or_default()
+    }
+
+    pub fn render(&self, correlation_id: Option<&CorrelationId>) -> HttpResponse {
+        HttpResponse::build(self.status)
+            .content_type("application/json")
+            .body(self.to_json(correlation_id))
+    }
+}
+
+/// OpenAPI 문서에서 에러 응답 본문을 설명합니다.
+impl JsonSchema for ErrorResponse {
+    fn schema_name() -> Cow<'static, str> {
+        "ErrorResponse".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "모든 에러 응답이 공유하는 본문",
+            "type": "object",
+            "properties": {
+                "status": { "type": "integer", "format": "uint16" },
+                "code": { "type": "string" },
+                "message": { "type": "string" },
+                "details": { "description": "에러별 추가 정보 (검증 실패 시 `errors` 목록 등)" },
+                "correlation_id": { "type": "string" }
+            },
+            "required": ["status", "code", "message"]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_body_includes_correlation_id() {
+        let body = ErrorResponse::from_status(StatusCode::NOT_FOUND)
+            .with_details(json!({ "id": 1 }))
+            .to_json(Some(&CorrelationId::new("abc")));
+
+        assert_eq!(
+            serde_json::from_str::<Value>(&body).unwrap(),
+            json!({
+                "status": 404,
+                "code": "not_found",
+                "message": "Not Found",
+                "details": { "id": 1 },
+                "correlation_id": "abc",
+            })
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use std::error::Error as StdError;
+use std::fmt;
+use ntex::web::{DefaultError, HttpRequest, HttpResponse, WebResponseError};
+use serde::Serialize;
+use crate::correlation::CorrelationId;
+use crate::filter::ExceptionFilters;
+
+/// 필터가 처리할 수 있도록 원래 타입을 보존하는 핸들러 에러
+///
+/// `?` 로 어떤 에러든 담을 수 있고, 응답은 요청에 등록된 [`ExceptionFilters`] 가 만듭니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// #[post("/user")]
+/// async fn create_user(...) -> Result<impl Responder, Exception> {
+///     let user = repository.insert(&command).await?; // sqlx::Error
+///     Ok(HttpResponse::Ok().json(&user))
+/// }
+/// ```
+pub struct Exception {
+    error: Box<dyn StdError + Send + Sync + 'static>,
+}
+
+impl Exception {
+    pub fn new(error: impl StdError + Send + Sync + 'static) -> Self {
+        Self { error: Box::new(error) }
+    }
+
+    pub fn error(&self) -> &(dyn StdError + Send + Sync + 'static) {
+        self.error.as_ref()
+    }
+
+    pub fn downcast_ref<E: StdError + 'static>(&self) -> Option<&E> {
+        self.error.downcast_ref::<E>()
+    }
+}
+
+impl<E: StdError + Send + Sync + 'static> From<E> for Exception {
+    fn from(error: E) -> Self {
+        Self::new(error)
+    }
+}
+
+/// 원래 에러를 꺼냅니다. 에러를 다시 감싸는 쪽(커맨드 버스 등)에서도 필터가 같은 타입을 보게 됩니다.
+impl From<Exception> for Box<dyn StdError + Send + Sync + 'static> {
+    fn from(exception: Exception) -> Self {
+        exception.error
+    }
+}
+
+impl fmt::Display for Exception {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl fmt::Debug for Exception {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.error, f)
+    }
+}
+
+impl WebResponseError<DefaultError> for Exception {
+    fn error_response(&self, req: &HttpRequest) -> HttpResponse {
+        let response = req
+            .app_state::<ExceptionFilters>()
+            .cloned()
+            .unwrap_or_default()
+            .resolve(self.error());
+        let correlation_id = CorrelationId::of(req);
+
+        if response.status().is_server_error() {
+            eprintln!(
+                "[exception] {} {} ({}): {:?}",
+                req.method(),
+                req.path(),
+                correlation_id.as_ref().map(CorrelationId::as_str).unwrap_or("-"),
+                self.error,
+            );
+        }
+
+        response.render(correlation_id.as_ref())
+    }
+}
+
+/// 필드 단위 입력 검증 실패. 기본 필터가 `422` 로 응답합니다.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationError {
+    pub errors: Vec<FieldError>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_field(mut self, field: impl Into<String>, message: impl Into<String>) -> Self {
+        self.errors.push(FieldError {
+            field: field.into(),
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// 실패한 필드가 있으면 `Err(self)` 를 돌려줍니다.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() { Ok(()) } else { Err(self) }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields = self
+            .errors
+            .iter()
+            .map(|error| format!("{}: {}", error.field, error.message))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(f, "validation failed: {}", fields)
+    }
+}
+
+impl StdError for ValidationError {}
+
+/// 핸들러에서 발생한 panic. [`crate::ExceptionBoundary`] 가 잡아 필터로 전달합니다.
+#[derive(Debug, Clone)]
+pub struct Panic {
+    pub message: String,
+}
+
+impl fmt::Display for Panic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "handler panicked: {}", self.message)
+    }
+}
+
+impl StdError for Panic {}
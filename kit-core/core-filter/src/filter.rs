@@ -0,0 +1,166 @@
+use std::error::Error as StdError;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use ntex::http::StatusCode;
+use serde_json::json;
+use crate::error_response::ErrorResponse;
+use crate::exception::ValidationError;
+
+/// 특정 에러를 응답으로 바꾸는 필터
+///
+/// 처리하지 않는 에러면 `None` 을 돌려 다음 필터로 넘깁니다.
+pub trait ExceptionFilter: Send + Sync + 'static {
+    fn catch(&self, error: &(dyn StdError + 'static)) -> Option<ErrorResponse>;
+}
+
+/// 타입 `E` 의 에러만 처리하는 필터
+pub struct Catch<E, F> {
+    render: F,
+    _e: PhantomData<fn(&E)>,
+}
+
+/// 타입 `E` 를 처리하는 필터를 만듭니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// ExceptionFilters::new().with_filter(catch(|e: &sqlx::Error| match e {
+///     sqlx::Error::RowNotFound => ErrorResponse::from_status(StatusCode::NOT_FOUND),
+///     _ => ErrorResponse::internal(),
+/// }))
+/// ```
+pub fn catch<E, F>(render: F) -> Catch<E, F>
+where
+    E: StdError + 'static,
+    F: Fn(&E) -> ErrorResponse + Send + Sync + 'static,
+{
+    Catch {
+        render,
+        _e: PhantomData,
+    }
+}
+
+impl<E, F> ExceptionFilter for Catch<E, F>
+where
+    E: StdError + 'static,
+    F: Fn(&E) -> ErrorResponse + Send + Sync + 'static,
+{
+    fn catch(&self, error: &(dyn StdError + 'static)) -> Option<ErrorResponse> {
+        error.downcast_ref::<E>().map(&self.render)
+    }
+}
+
+/// 에러 필터 목록
+///
+/// 앱 전체에는 `.state(filters)` 로, 특정 스코프에는 `web::scope(..).state(filters.extend())` 로 등록합니다.
+/// 스코프에 등록한 필터가 먼저 적용되고, 처리되지 않으면 상위 필터, 마지막으로 기본 필터가 적용됩니다.
+///
+/// 필터는 에러 자신부터 `source()` 체인을 따라가며 처음 처리된 응답을 사용합니다.
+#[derive(Clone, Default)]
+pub struct ExceptionFilters {
+    filters: Vec<Arc<dyn ExceptionFilter>>,
+    parent: Option<Arc<ExceptionFilters>>,
+}
+
+impl ExceptionFilters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_filter(mut self, filter: impl ExceptionFilter) -> Self {
+        self.filters.push(Arc::new(filter));
+        self
+    }
+
+    /// 현재 필터를 상위로 두는 하위(스코프용) 필터 목록을 만듭니다.
+    pub fn extend(&self) -> Self {
+        Self {
+            filters: Vec::new(),
+            parent: Some(Arc::new(self.clone())),
+        }
+    }
+
+    pub fn resolve(&self, error: &(dyn StdError + 'static)) -> ErrorResponse {
+        self.find(error).unwrap_or_else(|| default_response(error))
+    }
+
+    fn find(&self, error: &(dyn StdError + 'static)) -> Option<ErrorResponse> {
+        let mut current = Some(error);
+
+        while let Some(error) = current {
+            if let Some(response) = self.filters.iter().find_map(|filter| filter.catch(error)) {
+                return Some(response);
+            }
+            current = error.source();
+        }
+
+        self.parent.as_ref().and_then(|parent| parent.find(error))
+    }
+}
+
+fn default_response(error: &(dyn StdError + 'static)) -> ErrorResponse {
+    if let Some(validation) = error.downcast_ref::<ValidationError>() {
+        return ErrorResponse::new(StatusCode::UNPROCESSABLE_ENTITY, "validation_failed", "Validation Failed")
+            .with_details(json!({ "errors": validation.errors }));
+    }
+
+    ErrorResponse::internal()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt;
+    use crate::exception::Panic;
+
+    #[derive(Debug)]
+    struct NotFound;
+
+    impl fmt::Display for NotFound {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("not found")
+        }
+    }
+
+    impl StdError for NotFound {}
+
+    #[derive(Debug)]
+    struct Wrapped(NotFound);
+
+    impl fmt::Display for Wrapped {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("wrapped")
+        }
+    }
+
+    impl StdError for Wrapped {
+        fn source(&self) -> Option<&(dyn StdError + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn test_filter_matches_source_chain() {
+        let filters = ExceptionFilters::new()
+            .with_filter(catch(|_: &NotFound| ErrorResponse::from_status(StatusCode::NOT_FOUND)));
+
+        assert_eq!(filters.resolve(&Wrapped(NotFound)).status(), StatusCode::NOT_FOUND);
+        assert_eq!(filters.resolve(&Panic { message: "boom".into() }).code(), "internal_error");
+    }
+
+    #[test]
+    fn test_scope_filters_override_parent() {
+        let global = ExceptionFilters::new()
+            .with_filter(catch(|_: &NotFound| ErrorResponse::from_status(StatusCode::NOT_FOUND)));
+        let scoped = global
+            .extend()
+            .with_filter(catch(|_: &NotFound| ErrorResponse::from_status(StatusCode::GONE)));
+
+        assert_eq!(scoped.resolve(&NotFound).status(), StatusCode::GONE);
+        assert_eq!(global.extend().resolve(&NotFound).status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            scoped.resolve(&ValidationError::new().with_field("name", "required")).status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+}
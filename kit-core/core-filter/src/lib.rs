@@ -1,14 +1,11 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+mod correlation;
+mod error_response;
+mod exception;
+mod filter;
+mod middleware;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use correlation::{CORRELATION_ID_HEADER, CorrelationId, REQUEST_ID_HEADER};
+pub use error_response::ErrorResponse;
+pub use exception::{Exception, FieldError, Panic, ValidationError};
+pub use filter::{Catch, ExceptionFilter, ExceptionFilters, catch};
+pub use middleware::{ExceptionBoundary, ExceptionBoundaryMiddleware};
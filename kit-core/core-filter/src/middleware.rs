@@ -0,0 +1,257 @@
+use std::any::Any;
+use std::fmt;
+use std::panic::AssertUnwindSafe;
+use futures::FutureExt;
+use ntex::http::StatusCode;
+use ntex::http::body::{Body, ResponseBody};
+use ntex::http::header::{CONTENT_LENGTH, CONTENT_TYPE, HeaderName, HeaderValue};
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web;
+use crate::correlation::{CORRELATION_ID_HEADER, CorrelationId};
+use crate::error_response::ErrorResponse;
+use crate::exception::Panic;
+use crate::filter::ExceptionFilters;
+
+/// 앱 전체의 에러 응답을 일관되게 만드는 미들웨어
+///
+/// - 요청마다 상관 ID 를 정하고(`x-correlation-id`/`x-request-id` 헤더, 없으면 생성) 응답 헤더에 돌려줍니다.
+/// - 핸들러의 panic 을 잡아 [`Panic`] 으로 필터에 전달합니다.
+/// - 추출기 에러처럼 [`crate::Exception`] 을 거치지 않은 텍스트 에러 응답도 같은 JSON 본문으로 바꿉니다.
+///
+/// panic 으로 만든 `500` 도 메트릭과 트레이스에 남도록, 메트릭·트레이싱 미들웨어보다 안쪽(그보다 먼저 `.wrap()`)에 둡니다.
+pub struct ExceptionBoundary;
+
+impl<S> Middleware<S> for ExceptionBoundary {
+    type Service = ExceptionBoundaryMiddleware<S>;
+
+    fn create(&self, service: S) -> Self::Service {
+        ExceptionBoundaryMiddleware { service }
+    }
+}
+
+pub struct ExceptionBoundaryMiddleware<S> {
+    service: S,
+}
+
+impl<S, Err> Service<web::WebRequest<Err>> for ExceptionBoundaryMiddleware<S>
+where
+    S: Service<web::WebRequest<Err>, Response = web::WebResponse, Error = web::Error>,
+    Err: web::ErrorRenderer,
+{
+    type Response = web::WebResponse;
+    type Error = web::Error;
+
+    ntex::forward_ready!(service);
+
+    async fn call(
+        &self,
+        req: web::WebRequest<Err>,
+        ctx: ServiceCtx<'_, Self>,
+    ) -> Result<Self::Response, Self::Error> {
+        let correlation_id = CorrelationId::from_headers(req.headers()).unwrap_or_else(CorrelationId::generate);
+        req.extensions_mut().insert(correlation_id.clone());
+
+        let filters = req.app_state::<ExceptionFilters>().cloned().unwrap_or_default();
+        let method = req.method().clone();
+        let path = req.path().to_string();
+
+        match AssertUnwindSafe(ctx.call(&self.service, req)).catch_unwind().await {
+            Ok(Ok(response)) => Ok(normalize(response, &correlation_id)),
+            Ok(Err(e)) => Err(e),
+            Err(payload) => {
+                let panic = Panic {
+                    message: panic_message(payload.as_ref()),
+                };
+                eprintln!("[exception] {} {} ({}): {}", method, path, correlation_id, panic);
+
+                // 요청 객체는 panic 과 함께 해제되므로 WebResponse 를 만들 수 없다.
+                // 에러로 돌려주면 ntex 가 상태 코드와 본문(JSON)으로 응답을 만든다.
+                let response = filters.resolve(&panic);
+                Err(web::Error::new(RenderedError {
+                    status: response.status(),
+                    body: response.to_json(Some(&correlation_id)),
+                }))
+            }
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+// 이미 JSON 인 에러 응답(Exception 이 만든 응답 등)은 그대로 두고,
+// ntex 기본 렌더러가 만든 텍스트 에러 응답만 공통 본문으로 바꾼다.
+fn normalize(mut response: web::WebResponse, correlation_id: &CorrelationId) -> web::WebResponse {
+    if let Ok(value) = HeaderValue::from_str(correlation_id.as_str()) {
+        response
+            .headers_mut()
+            .insert(HeaderName::from_static(CORRELATION_ID_HEADER), value);
+    }
+
+    let status = response.status();
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("json"));
+
+    if !(status.is_client_error() || status.is_server_error()) || is_json {
+        return response;
+    }
+
+    let mut error = ErrorResponse::from_status(status);
+
+    if status.is_client_error()
+        && let ResponseBody::Body(Body::Bytes(bytes)) = response.response().body()
+        && let Ok(message) = std::str::from_utf8(bytes)
+        && !message.trim().is_empty()
+    {
+        error = error.with_message(message.trim());
+    }
+
+    let mut rendered = error.render(Some(correlation_id));
+    for (name, value) in response.headers() {
+        if name != CONTENT_TYPE && name != CONTENT_LENGTH {
+            rendered.headers_mut().insert(name.clone(), value.clone());
+        }
+    }
+
+    response.into_response(rendered)
+}
+
+/// 요청 없이 렌더링해야 하는 에러(panic)
+#[derive(Debug)]
+struct RenderedError {
+    status: StatusCode,
+    body: String,
+}
+
+impl fmt::Display for RenderedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.body)
+    }
+}
+
+impl web::WebResponseError for RenderedError {
+    fn status_code(&self) -> StatusCode {
+        self.status
+    }
+
+    fn error_response(&self, _: &web::HttpRequest) -> web::HttpResponse {
+        web::HttpResponse::build(self.status)
+            .content_type("application/json")
+            .body(self.body.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::web::types::Json;
+    use ntex::web::{App, HttpResponse, test};
+    use serde_json::Value;
+    use crate::exception::{Exception, ValidationError};
+    use crate::filter::catch;
+
+    #[derive(Debug)]
+    struct Conflict;
+
+    impl fmt::Display for Conflict {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("email already exists")
+        }
+    }
+
+    impl std::error::Error for Conflict {}
+
+    async fn conflict() -> Result<HttpResponse, Exception> {
+        Err(Conflict.into())
+    }
+
+    async fn invalid() -> Result<HttpResponse, Exception> {
+        Err(ValidationError::new().with_field("email", "invalid").into())
+    }
+
+    async fn json(body: Json<Value>) -> HttpResponse {
+        HttpResponse::Ok().json(&body.into_inner())
+    }
+
+    #[ntex::test]
+    async fn test_error_bodies_share_shape_and_correlation_id() {
+        let filters = ExceptionFilters::new()
+            .with_filter(catch(|e: &Conflict| ErrorResponse::new(StatusCode::CONFLICT, "conflict", e.to_string())));
+
+        let app = test::init_service(
+            App::new()
+                .state(filters.clone())
+                .service(
+                    web::scope("/v2")
+                        .state(filters.extend().with_filter(catch(|_: &Conflict| {
+                            ErrorResponse::from_status(StatusCode::GONE)
+                        })))
+                        .route("/conflict", web::get().to(conflict)),
+                )
+                .route("/conflict", web::get().to(conflict))
+                .route("/invalid", web::get().to(invalid))
+                .route("/json", web::post().to(json))
+                .wrap(ExceptionBoundary),
+        )
+        .await;
+
+        let request = test::TestRequest::get()
+            .uri("/conflict")
+            .header(CORRELATION_ID_HEADER, "abc")
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(response.headers().get(CORRELATION_ID_HEADER).unwrap(), "abc");
+        let body: Value = serde_json::from_slice(&test::read_body(response).await).unwrap();
+        assert_eq!(body["message"], "email already exists");
+        assert_eq!(body["correlation_id"], "abc");
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/v2/conflict").to_request()).await;
+        assert_eq!(response.status(), StatusCode::GONE);
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/invalid").to_request()).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body: Value = serde_json::from_slice(&test::read_body(response).await).unwrap();
+        assert_eq!(body["details"]["errors"][0]["field"], "email");
+
+        let request = test::TestRequest::post()
+            .uri("/json")
+            .header(CONTENT_TYPE, "application/json")
+            .set_payload("{")
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = serde_json::from_slice(&test::read_body(response).await).unwrap();
+        assert_eq!(body["code"], "bad_request");
+        assert_eq!(body["correlation_id"].as_str().unwrap().len(), 32);
+    }
+
+    #[ntex::test]
+    async fn test_panic_is_rendered_without_leaking_message() {
+        let app = test::init_service(
+            App::new()
+                .route("/panic", web::get().to(|| async { panic!("secret") as HttpResponse }))
+                .wrap(ExceptionBoundary),
+        )
+        .await;
+
+        let error = app
+            .call(test::TestRequest::get().uri("/panic").to_request())
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(error.as_response_error().status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body: Value = serde_json::from_str(&error.to_string()).unwrap();
+        assert_eq!(body["code"], "internal_error");
+        assert!(!error.to_string().contains("secret"));
+    }
+}
@@ -0,0 +1,134 @@
+use std::marker::PhantomData;
+use std::rc::Rc;
+use futures::future::LocalBoxFuture;
+use ntex::web::{ErrorRenderer, Handler};
+use crate::interceptor::{DynInterceptor, Interceptor, Next};
+
+/// 인터셉터가 적용된 핸들러
+///
+/// 먼저 추가한 인터셉터가 가장 바깥에서 실행됩니다.
+pub struct Intercepted<H, Args, Err>
+where
+    H: Handler<Args, Err>,
+    Err: ErrorRenderer,
+{
+    handler: H,
+    interceptors: Vec<Rc<dyn DynInterceptor<Args, H::Output>>>,
+    _t: PhantomData<Err>,
+}
+
+/// 라우트 하나에 인터셉터를 붙입니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// web::resource("/user").route(
+///     web::post().to(intercept(create_user).with(Timing).with(after(|response| response))),
+/// )
+/// ```
+pub fn intercept<H, Args, Err>(handler: H) -> Intercepted<H, Args, Err>
+where
+    H: Handler<Args, Err>,
+    Err: ErrorRenderer,
+{
+    Intercepted {
+        handler,
+        interceptors: Vec::new(),
+        _t: PhantomData,
+    }
+}
+
+impl<H, Args, Err> Intercepted<H, Args, Err>
+where
+    H: Handler<Args, Err>,
+    Err: ErrorRenderer,
+{
+    pub fn with<T>(mut self, interceptor: T) -> Self
+    where
+        T: Interceptor<Args, H::Output>,
+        Args: 'static,
+        H::Output: 'static,
+    {
+        self.interceptors.push(Rc::new(interceptor));
+        self
+    }
+}
+
+impl<H, Args, Err> Handler<Args, Err> for Intercepted<H, Args, Err>
+where
+    H: Handler<Args, Err> + 'static,
+    Args: 'static,
+    Err: ErrorRenderer,
+{
+    type Output = H::Output;
+
+    async fn call(&self, param: Args) -> Self::Output {
+        let terminal = |args| Box::pin(self.handler.call(args)) as LocalBoxFuture<'_, H::Output>;
+
+        Next::new(&self.interceptors, &terminal).run(param).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use ntex::web::types::Path;
+    use ntex::web::{self, App, test};
+    use crate::interceptor::{after, before};
+
+    thread_local! {
+        static EVENTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    struct Audit(&'static str);
+
+    impl<I: 'static, O: 'static> Interceptor<I, O> for Audit {
+        async fn intercept(&self, input: I, next: Next<'_, I, O>) -> O {
+            EVENTS.with(|events| events.borrow_mut().push(format!("{} before", self.0)));
+            let output = next.run(input).await;
+            EVENTS.with(|events| events.borrow_mut().push(format!("{} after", self.0)));
+            output
+        }
+    }
+
+    struct Cached;
+
+    impl Interceptor<(Path<String>,), String> for Cached {
+        async fn intercept(&self, input: (Path<String>,), next: Next<'_, (Path<String>,), String>) -> String {
+            if input.0.as_str() == "cached" {
+                return "from cache".to_string();
+            }
+            next.run(input).await
+        }
+    }
+
+    async fn greet(name: Path<String>) -> String {
+        format!("hello {}", name.into_inner())
+    }
+
+    #[ntex::test]
+    async fn test_interceptors_wrap_typed_input_and_output() {
+        let app = test::init_service(App::new().route(
+            "/{name}",
+            web::get().to(intercept(greet)
+                .with(Audit("outer"))
+                .with(Audit("inner"))
+                .with(Cached)
+                .with(before(|input: &mut (Path<String>,)| {
+                    EVENTS.with(|events| events.borrow_mut().push(format!("name {}", input.0.as_str())));
+                }))
+                .with(after(|output: String| output.to_uppercase()))),
+        ))
+        .await;
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/kit").to_request()).await;
+        assert_eq!(test::read_body(response).await, "HELLO KIT");
+
+        let events = EVENTS.with(|events| events.take());
+        assert_eq!(events, ["outer before", "inner before", "name kit", "inner after", "outer after"]);
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/cached").to_request()).await;
+        assert_eq!(test::read_body(response).await, "from cache");
+    }
+}
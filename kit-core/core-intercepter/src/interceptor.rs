@@ -0,0 +1,105 @@
+use std::future::Future;
+use std::rc::Rc;
+use futures::future::LocalBoxFuture;
+
+/// 핸들러 실행을 감싸는 인터셉터
+///
+/// `I` 는 핸들러가 받는 입력(추출된 인자 튜플), `O` 는 핸들러의 출력입니다.
+/// `next.run(input)` 을 호출하기 전후로 입력을 바꾸거나 출력을 가공할 수 있고,
+/// 호출하지 않고 직접 `O` 를 돌려주면 핸들러 실행을 건너뜁니다. (캐시 등)
+///
+/// 모든 `I`, `O` 에 대해 구현하면 라우트 단위([`crate::intercept`])와
+/// 전역([`crate::Intercept`]) 모두에 붙일 수 있습니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// struct Timing;
+///
+/// impl<I: 'static, O: 'static> Interceptor<I, O> for Timing {
+///     async fn intercept(&self, input: I, next: Next<'_, I, O>) -> O {
+///         let started_at = Instant::now();
+///         let output = next.run(input).await;
+///         println!("[timing] {:?}", started_at.elapsed());
+///         output
+///     }
+/// }
+/// ```
+pub trait Interceptor<I, O>: 'static {
+    fn intercept(&self, input: I, next: Next<'_, I, O>) -> impl Future<Output = O>;
+}
+
+pub(crate) trait DynInterceptor<I, O> {
+    fn intercept<'a>(&'a self, input: I, next: Next<'a, I, O>) -> LocalBoxFuture<'a, O>;
+}
+
+impl<T, I, O> DynInterceptor<I, O> for T
+where
+    T: Interceptor<I, O>,
+    I: 'static,
+    O: 'static,
+{
+    fn intercept<'a>(&'a self, input: I, next: Next<'a, I, O>) -> LocalBoxFuture<'a, O> {
+        Box::pin(Interceptor::intercept(self, input, next))
+    }
+}
+
+pub(crate) type Chain<I, O> = [Rc<dyn DynInterceptor<I, O>>];
+pub(crate) type Terminal<'a, I, O> = dyn Fn(I) -> LocalBoxFuture<'a, O> + 'a;
+
+/// 남은 인터셉터와 핸들러
+pub struct Next<'a, I, O> {
+    chain: &'a Chain<I, O>,
+    terminal: &'a Terminal<'a, I, O>,
+}
+
+impl<'a, I, O> Next<'a, I, O> {
+    pub(crate) fn new(chain: &'a Chain<I, O>, terminal: &'a Terminal<'a, I, O>) -> Self {
+        Self { chain, terminal }
+    }
+
+    /// 다음 인터셉터(마지막이면 핸들러)를 실행합니다.
+    pub async fn run(self, input: I) -> O {
+        match self.chain.split_first() {
+            Some((interceptor, rest)) => interceptor.intercept(input, Next::new(rest, self.terminal)).await,
+            None => (self.terminal)(input).await,
+        }
+    }
+}
+
+/// 핸들러 실행 전에 입력을 확인하거나 바꿉니다.
+pub struct Before<F>(F);
+
+pub fn before<F>(f: F) -> Before<F> {
+    Before(f)
+}
+
+impl<F, I, O> Interceptor<I, O> for Before<F>
+where
+    F: Fn(&mut I) + 'static,
+    I: 'static,
+    O: 'static,
+{
+    async fn intercept(&self, mut input: I, next: Next<'_, I, O>) -> O {
+        (self.0)(&mut input);
+        next.run(input).await
+    }
+}
+
+/// 핸들러 실행 후 출력을 가공합니다.
+pub struct After<F>(F);
+
+pub fn after<F>(f: F) -> After<F> {
+    After(f)
+}
+
+impl<F, I, O> Interceptor<I, O> for After<F>
+where
+    F: Fn(O) -> O + 'static,
+    I: 'static,
+    O: 'static,
+{
+    async fn intercept(&self, input: I, next: Next<'_, I, O>) -> O {
+        (self.0)(next.run(input).await)
+    }
+}
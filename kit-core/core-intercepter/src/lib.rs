@@ -1,14 +1,7 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+mod handler;
+mod interceptor;
+mod middleware;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use handler::{Intercepted, intercept};
+pub use interceptor::{After, Before, Interceptor, Next, after, before};
+pub use middleware::{Intercept, InterceptMiddleware, WebOutput};
@@ -0,0 +1,96 @@
+use std::rc::Rc;
+use futures::future::LocalBoxFuture;
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web;
+use crate::interceptor::{Interceptor, Next};
+
+/// 전역 인터셉터의 출력 타입
+pub type WebOutput = Result<web::WebResponse, web::Error>;
+
+/// 인터셉터를 앱 또는 스코프 전체에 적용하는 미들웨어
+///
+/// `Interceptor<web::WebRequest<Err>, WebOutput>` 를 구현한 인터셉터(모든 `I`, `O` 에 대해
+/// 구현한 인터셉터 포함)를 `.wrap(Intercept::new(...))` 로 붙일 수 있습니다.
+pub struct Intercept<T> {
+    interceptor: Rc<T>,
+}
+
+impl<T> Intercept<T> {
+    pub fn new(interceptor: T) -> Self {
+        Self {
+            interceptor: Rc::new(interceptor),
+        }
+    }
+}
+
+impl<S, T> Middleware<S> for Intercept<T> {
+    type Service = InterceptMiddleware<S, T>;
+
+    fn create(&self, service: S) -> Self::Service {
+        InterceptMiddleware {
+            service,
+            interceptor: self.interceptor.clone(),
+        }
+    }
+}
+
+pub struct InterceptMiddleware<S, T> {
+    service: S,
+    interceptor: Rc<T>,
+}
+
+impl<S, T, Err> Service<web::WebRequest<Err>> for InterceptMiddleware<S, T>
+where
+    S: Service<web::WebRequest<Err>, Response = web::WebResponse, Error = web::Error>,
+    T: Interceptor<web::WebRequest<Err>, WebOutput>,
+    Err: web::ErrorRenderer + 'static,
+{
+    type Response = web::WebResponse;
+    type Error = web::Error;
+
+    ntex::forward_ready!(service);
+
+    async fn call(
+        &self,
+        req: web::WebRequest<Err>,
+        ctx: ServiceCtx<'_, Self>,
+    ) -> Result<Self::Response, Self::Error> {
+        let terminal = |req| Box::pin(ctx.call(&self.service, req)) as LocalBoxFuture<'_, WebOutput>;
+
+        self.interceptor.intercept(req, Next::new(&[], &terminal)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::http::header::{HeaderName, HeaderValue};
+    use ntex::web::{App, HttpResponse, test};
+
+    struct PoweredBy;
+
+    impl<Err: 'static> Interceptor<web::WebRequest<Err>, WebOutput> for PoweredBy {
+        async fn intercept(&self, input: web::WebRequest<Err>, next: Next<'_, web::WebRequest<Err>, WebOutput>) -> WebOutput {
+            let mut response = next.run(input).await?;
+            response.headers_mut().insert(
+                HeaderName::from_static("x-powered-by"),
+                HeaderValue::from_static("kit"),
+            );
+            Ok(response)
+        }
+    }
+
+    #[ntex::test]
+    async fn test_global_interceptor_transforms_response() {
+        let app = test::init_service(
+            App::new()
+                .wrap(Intercept::new(PoweredBy))
+                .route("/", web::get().to(|| async { HttpResponse::Ok().finish() })),
+        )
+        .await;
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/").to_request()).await;
+
+        assert_eq!(response.headers().get("x-powered-by").unwrap(), "kit");
+    }
+}
@@ -0,0 +1,493 @@
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use core_container::{Container, ContainerBuilder};
+use kit_router::{RouteTable, Router, Versioning};
+use ntex::web::ServiceConfig;
+use crate::consumer::Consumer;
+use crate::error::ModuleError;
+use crate::health::HealthCheck;
+use crate::migration::Migration;
+use crate::module::{Module, ModuleDefinition, ModuleResolver, Routes, Source};
+use crate::projection::Projection;
+
+const ROOT: &str = "root";
+
+/// 모듈 목록으로 애플리케이션을 조립합니다.
+///
+/// import 관계에 따라 모듈을 정렬하고, export 규칙을 검증한 뒤 하나의 [`Container`] 로 묶습니다.
+/// 싱글톤은 이 단계에서 모두 생성되므로 누락된 의존성은 부팅 시점에 드러납니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// let application = Bootstrap::new()
+///     .instance(pool.clone())
+///     .instance(nats_client.clone())
+///     .module(UserModule)
+///     .build()
+///     .await?;
+///
+/// App::new().configure(|cfg| application.configure(cfg))
+/// ```
+#[derive(Default)]
+pub struct Bootstrap {
+    builder: ContainerBuilder,
+    globals: HashSet<TypeId>,
+    modules: Vec<Box<dyn Module>>,
+    versioning: Versioning,
+}
+
+impl Bootstrap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 모든 모듈에서 사용할 수 있는 인스턴스(커넥션 풀, NATS 클라이언트 등)를 등록합니다.
+    pub fn instance<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.builder = self.builder.instance(value);
+        self.globals.insert(TypeId::of::<T>());
+        self
+    }
+
+    /// 모듈 라우트 그룹의 API 버전 구분 방식. 기본값은 경로(`/v1/...`)입니다.
+    pub fn versioning(mut self, versioning: Versioning) -> Self {
+        self.versioning = versioning;
+        self
+    }
+
+    pub fn module(mut self, module: impl Module) -> Self {
+        self.modules.push(Box::new(module));
+        self
+    }
+
+    pub fn modules(mut self, modules: impl IntoIterator<Item = Box<dyn Module>>) -> Self {
+        self.modules.extend(modules);
+        self
+    }
+
+    /// 컨테이너를 만들지 않고 모듈이 선언한 라우트 목록만 모읍니다. (OpenAPI 문서 생성 등)
+    pub fn route_table(&self) -> RouteTable {
+        let groups = self.modules.iter().flat_map(|module| {
+            let mut definition = ModuleDefinition::new(module.name());
+            module.configure(&mut definition);
+            definition.groups
+        });
+
+        Router::new().versioning(self.versioning).groups(groups).table()
+    }
+
+    pub async fn build(self) -> Result<Application, ModuleError> {
+        let mut definitions: HashMap<&'static str, ModuleDefinition> = HashMap::new();
+        let mut declared = Vec::with_capacity(self.modules.len());
+
+        for module in &self.modules {
+            let mut definition = ModuleDefinition::new(module.name());
+            module.configure(&mut definition);
+
+            if definitions.insert(module.name(), definition).is_some() {
+                return Err(ModuleError::DuplicateModule(module.name()));
+            }
+            declared.push(module.name());
+        }
+
+        let order = sort_by_imports(&declared, &definitions)?;
+
+        let mut owners: HashMap<TypeId, &'static str> =
+            self.globals.iter().map(|type_id| (*type_id, ROOT)).collect();
+        let mut exports: HashMap<&'static str, HashSet<TypeId>> = HashMap::new();
+
+        for name in &order {
+            let definition = &definitions[name];
+
+            for provider in &definition.providers {
+                if let Some(first) = owners.insert(provider.type_id, definition.name) {
+                    return Err(ModuleError::DuplicateProvider {
+                        type_name: provider.type_name,
+                        first,
+                        second: definition.name,
+                    });
+                }
+            }
+
+            let own: HashSet<TypeId> = definition.providers.iter().map(|provider| provider.type_id).collect();
+            let imported: HashSet<TypeId> = definition
+                .imports
+                .iter()
+                .flat_map(|import| exports[import].iter().copied())
+                .collect();
+
+            for (type_id, type_name) in &definition.exports {
+                if !own.contains(type_id) && !imported.contains(type_id) {
+                    return Err(ModuleError::InvalidExport {
+                        module: definition.name,
+                        type_name,
+                    });
+                }
+            }
+
+            let visible = self.globals.iter().chain(&own).chain(&imported).copied().collect();
+            let _ = definition.visibility.set(visible);
+
+            exports.insert(
+                definition.name,
+                definition.exports.iter().map(|(type_id, _)| *type_id).collect(),
+            );
+        }
+
+        let mut builder = self.builder;
+        let mut definitions: Vec<ModuleDefinition> = order
+            .iter()
+            .filter_map(|name| definitions.remove(name))
+            .collect();
+
+        for definition in &mut definitions {
+            for provider in definition.providers.drain(..) {
+                builder = (provider.register)(builder);
+            }
+        }
+
+        let container = builder.build();
+        container.init_singletons().await?;
+
+        let mut application = Application {
+            container: container.clone(),
+            modules: order,
+            routes: Vec::new(),
+            router: Router::new().versioning(self.versioning),
+            consumers: Vec::new(),
+            migrations: Vec::new(),
+            health_checks: Vec::new(),
+            projections: Vec::new(),
+        };
+
+        for definition in definitions {
+            println!(
+                "[bootstrap] module `{}` loaded (route groups: {}, routes: {}, consumers: {}, migrations: {})",
+                definition.name,
+                definition.groups.len(),
+                definition.routes.len(),
+                definition.consumers.len(),
+                definition.migrations.len(),
+            );
+
+            for factory in definition.health_checks {
+                let resolver = ModuleResolver::new(
+                    definition.name,
+                    Source::Scope(container.scope()),
+                    definition.visibility.clone(),
+                );
+                application.health_checks.push(factory(resolver).await?);
+            }
+
+            for factory in definition.projections {
+                let resolver = ModuleResolver::new(
+                    definition.name,
+                    Source::Scope(container.scope()),
+                    definition.visibility.clone(),
+                );
+                application.projections.push(factory(resolver).await?);
+            }
+
+            application.consumers.extend(definition.consumers.into_iter().map(|consumer| Consumer {
+                module: definition.name,
+                subject: consumer.subject,
+                queue_group: consumer.queue_group,
+                handler: consumer.handler,
+                container: container.clone(),
+                visibility: definition.visibility.clone(),
+            }));
+
+            let mut migrations = definition.migrations;
+            migrations.sort_by_key(|migration| migration.version);
+
+            application.routes.extend(definition.routes);
+            application.router = application.router.groups(definition.groups);
+            application.migrations.extend(migrations);
+        }
+
+        Ok(application)
+    }
+}
+
+// import 한 모듈이 항상 먼저 오도록 정렬한다. 선언 순서는 가능한 한 유지한다.
+fn sort_by_imports(
+    declared: &[&'static str],
+    definitions: &HashMap<&'static str, ModuleDefinition>,
+) -> Result<Vec<&'static str>, ModuleError> {
+    fn visit(
+        name: &'static str,
+        definitions: &HashMap<&'static str, ModuleDefinition>,
+        path: &mut Vec<&'static str>,
+        order: &mut Vec<&'static str>,
+    ) -> Result<(), ModuleError> {
+        if order.contains(&name) {
+            return Ok(());
+        }
+
+        if let Some(position) = path.iter().position(|visiting| *visiting == name) {
+            let mut cycle = path[position..].to_vec();
+            cycle.push(name);
+            return Err(ModuleError::ImportCycle(cycle.join(" -> ")));
+        }
+
+        path.push(name);
+        for import in &definitions[name].imports {
+            if !definitions.contains_key(import) {
+                return Err(ModuleError::UnknownImport { module: name, import });
+            }
+            visit(import, definitions, path, order)?;
+        }
+        path.pop();
+
+        order.push(name);
+        Ok(())
+    }
+
+    let mut order = Vec::with_capacity(declared.len());
+    for name in declared {
+        visit(name, definitions, &mut Vec::new(), &mut order)?;
+    }
+
+    Ok(order)
+}
+
+/// 조립이 끝난 모듈들의 구성 요소
+///
+/// HTTP 서버 워커마다 [`Application::configure`] 로 라우트를 연결하고,
+/// 구독·마이그레이션·헬스 체크는 애플리케이션의 인프라 계층에서 실행합니다.
+#[derive(Clone)]
+pub struct Application {
+    container: Container,
+    modules: Vec<&'static str>,
+    routes: Vec<Routes>,
+    router: Router,
+    consumers: Vec<Consumer>,
+    migrations: Vec<Migration>,
+    health_checks: Vec<Arc<dyn HealthCheck>>,
+    projections: Vec<Arc<dyn Projection>>,
+}
+
+impl Application {
+    pub fn container(&self) -> &Container {
+        &self.container
+    }
+
+    /// 초기화 순서대로 정렬된 모듈 이름
+    pub fn modules(&self) -> &[&'static str] {
+        &self.modules
+    }
+
+    /// 컨테이너를 애플리케이션 상태로 등록하고 모든 모듈의 라우트를 연결합니다.
+    pub fn configure(&self, cfg: &mut ServiceConfig) {
+        cfg.state(self.container.clone());
+
+        for routes in &self.routes {
+            routes(cfg);
+        }
+        self.router.configure(cfg);
+    }
+
+    /// 모듈이 [`ModuleDefinition::group`] 으로 등록한 라우트 목록
+    pub fn route_table(&self) -> RouteTable {
+        self.router.table()
+    }
+
+    pub fn consumers(&self) -> &[Consumer] {
+        &self.consumers
+    }
+
+    /// 모듈 초기화 순서, 모듈 안에서는 버전 순서로 정렬된 마이그레이션
+    pub fn migrations(&self) -> &[Migration] {
+        &self.migrations
+    }
+
+    pub fn health_checks(&self) -> &[Arc<dyn HealthCheck>] {
+        &self.health_checks
+    }
+
+    pub fn projections(&self) -> &[Arc<dyn Projection>] {
+        &self.projections
+    }
+}
+
+impl std::fmt::Debug for Application {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Application")
+            .field("modules", &self.modules)
+            .field("routes", &self.routes.len())
+            .field("route_table", &self.router.table().len())
+            .field("consumers", &self.consumers.len())
+            .field("migrations", &self.migrations.len())
+            .field("health_checks", &self.health_checks.len())
+            .field("projections", &self.projections.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_container::{ContainerError, Inject};
+    use futures::future::BoxFuture;
+    use kit_router::RouteGroup;
+    use ntex::web::{App, HttpResponse, test};
+    use serde_json::Value;
+
+    struct Config {
+        greeting: String,
+    }
+
+    struct Secret;
+
+    struct Greeter {
+        greeting: String,
+    }
+
+    struct ConfigModule;
+
+    impl Module for ConfigModule {
+        fn name(&self) -> &'static str {
+            "config"
+        }
+
+        fn configure(&self, module: &mut ModuleDefinition) {
+            module
+                .singleton(|_| async { Ok(Config { greeting: "hello".to_string() }) })
+                .singleton(|_| async { Ok(Secret) })
+                .export::<Config>();
+        }
+    }
+
+    struct GreeterModule;
+
+    impl Module for GreeterModule {
+        fn name(&self) -> &'static str {
+            "greeter"
+        }
+
+        fn configure(&self, module: &mut ModuleDefinition) {
+            module
+                .import("config")
+                .singleton(|resolver: ModuleResolver| async move {
+                    let config = resolver.get::<Config>().await?;
+                    Ok(Greeter { greeting: config.greeting.clone() })
+                })
+                .migration(2, "second", "SELECT 2")
+                .migration(1, "first", "SELECT 1")
+                .health_check(|_| async { Ok(AlwaysUp) })
+                .projection(|_| async { Ok(Greetings) })
+                .group(RouteGroup::new("/greet").version(1).get("", greet));
+        }
+    }
+
+    struct SneakyModule;
+
+    impl Module for SneakyModule {
+        fn name(&self) -> &'static str {
+            "sneaky"
+        }
+
+        fn configure(&self, module: &mut ModuleDefinition) {
+            module.import("config").singleton(|resolver: ModuleResolver| async move {
+                resolver.get::<Secret>().await?;
+                Ok(0_u8)
+            });
+        }
+    }
+
+    async fn greet(greeter: Inject<Greeter>) -> HttpResponse {
+        HttpResponse::Ok().body(greeter.greeting.clone())
+    }
+
+    struct CyclicModule(&'static str, &'static str);
+
+    impl Module for CyclicModule {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn configure(&self, module: &mut ModuleDefinition) {
+            module.import(self.1);
+        }
+    }
+
+    struct AlwaysUp;
+
+    impl HealthCheck for AlwaysUp {
+        fn name(&self) -> &str {
+            "always"
+        }
+
+        fn check(&self) -> BoxFuture<'_, Result<Option<Value>, String>> {
+            Box::pin(async { Ok(None) })
+        }
+    }
+
+    struct Greetings;
+
+    impl Projection for Greetings {
+        fn name(&self) -> &str {
+            "greetings"
+        }
+
+        fn rebuild(&self) -> BoxFuture<'_, Result<u64, String>> {
+            Box::pin(async { Ok(1) })
+        }
+    }
+
+    #[ntex::test]
+    async fn test_bootstrap_orders_modules_and_mounts_routes() {
+        let application = Bootstrap::new()
+            .module(GreeterModule)
+            .module(ConfigModule)
+            .build()
+            .await
+            .unwrap();
+
+        assert_eq!(application.modules(), &["config", "greeter"]);
+        assert_eq!(application.migrations()[0].name, "first");
+        assert_eq!(application.health_checks().len(), 1);
+        assert_eq!(application.projections()[0].rebuild().await, Ok(1));
+        assert_eq!(application.route_table().routes()[0].path, "/v1/greet");
+        assert_eq!(
+            Bootstrap::new().module(GreeterModule).route_table().routes()[0].path,
+            "/v1/greet"
+        );
+
+        let app = test::init_service(App::new().configure(|cfg| application.configure(cfg))).await;
+        let response = test::call_service(&app, test::TestRequest::get().uri("/v1/greet").to_request()).await;
+
+        assert_eq!(test::read_body(response).await, "hello");
+    }
+
+    #[ntex::test]
+    async fn test_private_provider_is_not_visible_to_importer() {
+        let error = Bootstrap::new()
+            .module(ConfigModule)
+            .module(SneakyModule)
+            .build()
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            ModuleError::Container(ContainerError::NotExported { module: "sneaky", .. })
+        ));
+    }
+
+    #[ntex::test]
+    async fn test_import_errors() {
+        let cycle = Bootstrap::new()
+            .module(CyclicModule("a", "b"))
+            .module(CyclicModule("b", "a"))
+            .build()
+            .await
+            .unwrap_err();
+
+        assert_eq!(cycle.to_string(), "circular module import detected: a -> b -> a");
+
+        let unknown = Bootstrap::new().module(GreeterModule).build().await.unwrap_err();
+        assert!(matches!(unknown, ModuleError::UnknownImport { import: "config", .. }));
+    }
+}
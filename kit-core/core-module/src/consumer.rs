@@ -0,0 +1,48 @@
+use std::any::TypeId;
+use std::collections::HashSet;
+use std::sync::{Arc, OnceLock};
+use async_nats::Message;
+use core_container::Container;
+use futures::future::BoxFuture;
+use crate::module::{ModuleResolver, Source};
+
+pub(crate) type ConsumerHandler =
+    Arc<dyn Fn(ModuleResolver, Message) -> BoxFuture<'static, Result<(), String>> + Send + Sync>;
+
+/// 모듈이 선언한 NATS 구독
+///
+/// 구독과 메시지 루프는 애플리케이션이 담당하고, 메시지마다 [`Consumer::handle`] 을 호출합니다.
+/// 핸들러는 메시지마다 새 스코프에서 의존성을 꺼냅니다.
+#[derive(Clone)]
+pub struct Consumer {
+    pub(crate) module: &'static str,
+    pub(crate) subject: String,
+    pub(crate) queue_group: Option<String>,
+    pub(crate) handler: ConsumerHandler,
+    pub(crate) container: Container,
+    pub(crate) visibility: Arc<OnceLock<HashSet<TypeId>>>,
+}
+
+impl Consumer {
+    pub fn module(&self) -> &'static str {
+        self.module
+    }
+
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn queue_group(&self) -> Option<&str> {
+        self.queue_group.as_deref()
+    }
+
+    pub fn handle(&self, message: Message) -> BoxFuture<'static, Result<(), String>> {
+        let resolver = ModuleResolver::new(
+            self.module,
+            Source::Scope(self.container.scope()),
+            self.visibility.clone(),
+        );
+
+        (self.handler)(resolver, message)
+    }
+}
@@ -0,0 +1,33 @@
+use core_container::ContainerError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ModuleError {
+    #[error("module `{0}` is registered more than once")]
+    DuplicateModule(&'static str),
+
+    #[error("module `{module}` imports unknown module `{import}`")]
+    UnknownImport {
+        module: &'static str,
+        import: &'static str,
+    },
+
+    #[error("circular module import detected: {0}")]
+    ImportCycle(String),
+
+    #[error("`{type_name}` is provided by both `{first}` and `{second}`")]
+    DuplicateProvider {
+        type_name: &'static str,
+        first: &'static str,
+        second: &'static str,
+    },
+
+    #[error("module `{module}` exports `{type_name}` which it neither provides nor imports")]
+    InvalidExport {
+        module: &'static str,
+        type_name: &'static str,
+    },
+
+    #[error(transparent)]
+    Container(#[from] ContainerError),
+}
@@ -0,0 +1,12 @@
+use futures::future::BoxFuture;
+use serde_json::Value;
+
+/// 개별 의존성의 상태를 확인하는 검사
+///
+/// 성공 시 상세 정보(선택)를, 실패 시 원인을 돌려줍니다.
+/// 각 모듈은 [`crate::ModuleDefinition::health_check`] 로 자신의 검사를 등록할 수 있습니다.
+pub trait HealthCheck: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn check(&self) -> BoxFuture<'_, Result<Option<Value>, String>>;
+}
@@ -1,14 +1,15 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+mod bootstrap;
+mod consumer;
+mod error;
+mod health;
+mod migration;
+mod module;
+mod projection;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use bootstrap::{Application, Bootstrap};
+pub use consumer::Consumer;
+pub use error::ModuleError;
+pub use health::HealthCheck;
+pub use migration::Migration;
+pub use module::{Module, ModuleDefinition, ModuleResolver};
+pub use projection::Projection;
@@ -0,0 +1,10 @@
+/// 모듈이 소유한 스키마 변경
+///
+/// 같은 모듈 안에서 `version` 오름차순으로 적용되며, 적용 여부는 `(module, version)` 으로 구분합니다.
+#[derive(Debug, Clone)]
+pub struct Migration {
+    pub module: &'static str,
+    pub version: i64,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
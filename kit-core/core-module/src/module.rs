@@ -0,0 +1,299 @@
+use std::any::{TypeId, type_name};
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use async_nats::Message;
+use core_container::{ContainerBuilder, ContainerError, Lifetime, Resolver, Scope};
+use futures::future::BoxFuture;
+use kit_router::RouteGroup;
+use ntex::web::ServiceConfig;
+use crate::consumer::ConsumerHandler;
+use crate::health::HealthCheck;
+use crate::migration::Migration;
+use crate::projection::Projection;
+
+pub(crate) type Routes = Arc<dyn Fn(&mut ServiceConfig) + Send + Sync>;
+pub(crate) type HealthCheckFactory =
+    Box<dyn FnOnce(ModuleResolver) -> BoxFuture<'static, Result<Arc<dyn HealthCheck>, ContainerError>> + Send>;
+pub(crate) type ProjectionFactory =
+    Box<dyn FnOnce(ModuleResolver) -> BoxFuture<'static, Result<Arc<dyn Projection>, ContainerError>> + Send>;
+
+/// 하나의 기능 단위(user, order 등)를 애플리케이션에 연결합니다.
+///
+/// 모듈은 [`Module::configure`] 에서 자신이 제공하는 프로바이더, 라우트, NATS 구독,
+/// 마이그레이션, 헬스 체크, 프로젝션과 다른 모듈과의 import/export 관계를 선언합니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// pub struct UserModule;
+///
+/// impl Module for UserModule {
+///     fn name(&self) -> &'static str {
+///         "user"
+///     }
+///
+///     fn configure(&self, module: &mut ModuleDefinition) {
+///         module
+///             .singleton(|resolver: ModuleResolver| async move {
+///                 let pool = resolver.get::<PgPool>().await?;
+///                 Ok(UserRepository::new(pool.as_ref().clone()))
+///             })
+///             .export::<UserRepository>()
+///             .group(RouteGroup::new("/user").version(1).post("", create_user));
+///     }
+/// }
+/// ```
+pub trait Module: Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+
+    fn configure(&self, module: &mut ModuleDefinition);
+}
+
+pub(crate) struct ProviderRegistration {
+    pub(crate) type_id: TypeId,
+    pub(crate) type_name: &'static str,
+    pub(crate) register: Box<dyn FnOnce(ContainerBuilder) -> ContainerBuilder + Send>,
+}
+
+pub(crate) struct ConsumerRegistration {
+    pub(crate) subject: String,
+    pub(crate) queue_group: Option<String>,
+    pub(crate) handler: ConsumerHandler,
+}
+
+/// [`Module::configure`] 에서 모듈의 구성 요소를 모읍니다.
+pub struct ModuleDefinition {
+    pub(crate) name: &'static str,
+    pub(crate) imports: Vec<&'static str>,
+    pub(crate) exports: Vec<(TypeId, &'static str)>,
+    pub(crate) providers: Vec<ProviderRegistration>,
+    pub(crate) routes: Vec<Routes>,
+    pub(crate) groups: Vec<RouteGroup>,
+    pub(crate) consumers: Vec<ConsumerRegistration>,
+    pub(crate) migrations: Vec<Migration>,
+    pub(crate) health_checks: Vec<HealthCheckFactory>,
+    pub(crate) projections: Vec<ProjectionFactory>,
+    pub(crate) visibility: Arc<OnceLock<HashSet<TypeId>>>,
+}
+
+impl ModuleDefinition {
+    pub(crate) fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            imports: Vec::new(),
+            exports: Vec::new(),
+            providers: Vec::new(),
+            routes: Vec::new(),
+            groups: Vec::new(),
+            consumers: Vec::new(),
+            migrations: Vec::new(),
+            health_checks: Vec::new(),
+            projections: Vec::new(),
+            visibility: Arc::new(OnceLock::new()),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// 다른 모듈이 export 한 프로바이더를 사용합니다. import 한 모듈이 먼저 초기화됩니다.
+    pub fn import(&mut self, module: &'static str) -> &mut Self {
+        self.imports.push(module);
+        self
+    }
+
+    /// 이 모듈을 import 한 모듈에게 `T` 를 공개합니다.
+    pub fn export<T: 'static>(&mut self) -> &mut Self {
+        self.exports.push((TypeId::of::<T>(), type_name::<T>()));
+        self
+    }
+
+    pub fn singleton<T, F, Fut>(&mut self, factory: F) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(ModuleResolver) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, ContainerError>> + Send + 'static,
+    {
+        self.provide(Lifetime::Singleton, factory)
+    }
+
+    pub fn scoped<T, F, Fut>(&mut self, factory: F) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(ModuleResolver) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, ContainerError>> + Send + 'static,
+    {
+        self.provide(Lifetime::Scoped, factory)
+    }
+
+    pub fn transient<T, F, Fut>(&mut self, factory: F) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(ModuleResolver) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, ContainerError>> + Send + 'static,
+    {
+        self.provide(Lifetime::Transient, factory)
+    }
+
+    pub fn provide<T, F, Fut>(&mut self, lifetime: Lifetime, factory: F) -> &mut Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(ModuleResolver) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, ContainerError>> + Send + 'static,
+    {
+        let module = self.name;
+        let visibility = self.visibility.clone();
+
+        self.providers.push(ProviderRegistration {
+            type_id: TypeId::of::<T>(),
+            type_name: type_name::<T>(),
+            register: Box::new(move |builder| {
+                builder.provide(lifetime, move |resolver: Resolver| {
+                    factory(ModuleResolver::new(module, Source::Resolver(resolver), visibility.clone()))
+                })
+            }),
+        });
+        self
+    }
+
+    /// 모듈의 라우트 그룹을 등록합니다. 그룹의 라우트는 [`crate::Application::route_table`] 에 포함됩니다.
+    pub fn group(&mut self, group: RouteGroup) -> &mut Self {
+        self.groups.push(group);
+        self
+    }
+
+    /// `ServiceConfig` 로 직접 라우트를 등록합니다. 이 라우트는 라우트 테이블에 나타나지 않습니다.
+    pub fn routes<F>(&mut self, routes: F) -> &mut Self
+    where
+        F: Fn(&mut ServiceConfig) + Send + Sync + 'static,
+    {
+        self.routes.push(Arc::new(routes));
+        self
+    }
+
+    pub fn consumer<F, Fut>(&mut self, subject: impl Into<String>, handler: F) -> &mut Self
+    where
+        F: Fn(ModuleResolver, Message) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.push_consumer(subject.into(), None, handler)
+    }
+
+    /// 큐 그룹으로 구독합니다. 같은 그룹의 인스턴스 중 하나만 메시지를 받습니다.
+    pub fn queue_consumer<F, Fut>(
+        &mut self,
+        subject: impl Into<String>,
+        queue_group: impl Into<String>,
+        handler: F,
+    ) -> &mut Self
+    where
+        F: Fn(ModuleResolver, Message) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.push_consumer(subject.into(), Some(queue_group.into()), handler)
+    }
+
+    fn push_consumer<F, Fut>(&mut self, subject: String, queue_group: Option<String>, handler: F) -> &mut Self
+    where
+        F: Fn(ModuleResolver, Message) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.consumers.push(ConsumerRegistration {
+            subject,
+            queue_group,
+            handler: Arc::new(move |resolver, message| Box::pin(handler(resolver, message))),
+        });
+        self
+    }
+
+    pub fn migration(&mut self, version: i64, name: &'static str, sql: &'static str) -> &mut Self {
+        self.migrations.push(Migration {
+            module: self.name,
+            version,
+            name,
+            sql,
+        });
+        self
+    }
+
+    pub fn health_check<H, F, Fut>(&mut self, factory: F) -> &mut Self
+    where
+        H: HealthCheck + 'static,
+        F: FnOnce(ModuleResolver) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<H, ContainerError>> + Send + 'static,
+    {
+        self.health_checks.push(Box::new(move |resolver| {
+            Box::pin(async move {
+                let check = factory(resolver).await?;
+                Ok(Arc::new(check) as Arc<dyn HealthCheck>)
+            })
+        }));
+        self
+    }
+
+    pub fn projection<P, F, Fut>(&mut self, factory: F) -> &mut Self
+    where
+        P: Projection + 'static,
+        F: FnOnce(ModuleResolver) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<P, ContainerError>> + Send + 'static,
+    {
+        self.projections.push(Box::new(move |resolver| {
+            Box::pin(async move {
+                let projection = factory(resolver).await?;
+                Ok(Arc::new(projection) as Arc<dyn Projection>)
+            })
+        }));
+        self
+    }
+}
+
+#[derive(Clone)]
+pub(crate) enum Source {
+    Resolver(Resolver),
+    Scope(Scope),
+}
+
+/// 모듈 안에서 의존성을 꺼낼 때 사용합니다.
+///
+/// 부트스트랩에 등록된 전역 인스턴스, 모듈 자신의 프로바이더, import 한 모듈이 export 한 프로바이더만 꺼낼 수 있습니다.
+#[derive(Clone)]
+pub struct ModuleResolver {
+    module: &'static str,
+    source: Source,
+    visibility: Arc<OnceLock<HashSet<TypeId>>>,
+}
+
+impl ModuleResolver {
+    pub(crate) fn new(module: &'static str, source: Source, visibility: Arc<OnceLock<HashSet<TypeId>>>) -> Self {
+        Self {
+            module,
+            source,
+            visibility,
+        }
+    }
+
+    pub fn module(&self) -> &'static str {
+        self.module
+    }
+
+    pub async fn get<T: Send + Sync + 'static>(&self) -> Result<Arc<T>, ContainerError> {
+        let visible = self
+            .visibility
+            .get()
+            .is_some_and(|types| types.contains(&TypeId::of::<T>()));
+
+        if !visible {
+            return Err(ContainerError::NotExported {
+                type_name: type_name::<T>(),
+                module: self.module,
+            });
+        }
+
+        match &self.source {
+            Source::Resolver(resolver) => resolver.get::<T>().await,
+            Source::Scope(scope) => scope.get::<T>().await,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use futures::future::BoxFuture;
+
+/// 도메인 이벤트로 갱신되는 읽기 모델
+///
+/// 평소에는 구독한 이벤트로 갱신하고, 스키마가 바뀌거나 데이터가 어긋나면
+/// [`Projection::rebuild`] 로 이벤트 로그를 처음부터 다시 적용합니다.
+/// 각 모듈은 [`crate::ModuleDefinition::projection`] 으로 자신의 프로젝션을 등록할 수 있습니다.
+pub trait Projection: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// 읽기 모델을 비우고 저장된 이벤트를 모두 다시 적용합니다. 적용한 이벤트 수를 돌려줍니다.
+    fn rebuild(&self) -> BoxFuture<'_, Result<u64, String>>;
+}
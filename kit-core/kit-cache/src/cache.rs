@@ -0,0 +1,35 @@
+use std::time::Duration;
+use futures::future::BoxFuture;
+use crate::error::CacheError;
+
+/// 바이트 값을 저장하는 캐시 백엔드
+///
+/// 값의 직렬화와 키 접두사는 [`crate::Namespace`] 가 담당하므로, 백엔드는 받은 키와 바이트를 그대로 저장합니다.
+/// `ttl` 이 `None` 이면 만료되지 않습니다. (메모리 백엔드는 용량이 차면 밀려날 수 있습니다)
+pub trait Cache: Send + Sync + 'static {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Vec<u8>>, CacheError>>;
+
+    fn set<'a>(&'a self, key: &'a str, value: Vec<u8>, ttl: Option<Duration>) -> BoxFuture<'a, Result<(), CacheError>>;
+
+    /// 키가 있었으면 `true`
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<bool, CacheError>>;
+
+    /// 남은 만료 시간. 키가 없거나 만료 시간이 없으면 `None` 입니다.
+    fn ttl<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Duration>, CacheError>>;
+
+    /// 현재 값이 `expected` 와 같을 때만 `value` 로 바꾸고 `true` 를 돌려줍니다.
+    ///
+    /// `expected` 가 `None` 이면 키가 없을 때만 저장합니다. (set-if-absent)
+    fn compare_and_swap<'a>(
+        &'a self,
+        key: &'a str,
+        expected: Option<&'a [u8]>,
+        value: Vec<u8>,
+        ttl: Option<Duration>,
+    ) -> BoxFuture<'a, Result<bool, CacheError>>;
+
+    /// 정수 값을 1 올리고 올린 값을 돌려줍니다. 키가 없으면 `initial` 로 저장하고 돌려줍니다.
+    ///
+    /// 값은 십진수 문자열로 저장되므로 [`crate::Namespace::get`] 으로 `u64` 를 읽을 수 있습니다.
+    fn increment<'a>(&'a self, key: &'a str, initial: u64) -> BoxFuture<'a, Result<u64, CacheError>>;
+}
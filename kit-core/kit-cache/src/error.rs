@@ -0,0 +1,20 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CacheError {
+    #[error("cache backend error: {0}")]
+    Backend(#[from] redis::RedisError),
+
+    #[error("failed to encode cache value: {0}")]
+    Encode(#[source] serde_json::Error),
+
+    #[error("failed to decode cache value for `{key}`: {source}")]
+    Decode {
+        key: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("cache value for `{0}` is not an integer")]
+    NotAnInteger(String),
+}
@@ -0,0 +1,13 @@
+mod cache;
+mod error;
+mod memory;
+mod namespace;
+mod read_through;
+mod redis;
+
+pub use cache::Cache;
+pub use error::CacheError;
+pub use memory::MemoryCache;
+pub use namespace::Namespace;
+pub use read_through::ReadThrough;
+pub use redis::RedisCache;
@@ -0,0 +1,190 @@
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use futures::future::{BoxFuture, ready};
+use lru::LruCache;
+use crate::cache::Cache;
+use crate::error::CacheError;
+
+struct Entry {
+    value: Vec<u8>,
+    expires_at: Option<Instant>,
+}
+
+impl Entry {
+    fn new(value: Vec<u8>, ttl: Option<Duration>) -> Self {
+        Self {
+            value,
+            expires_at: ttl.map(|ttl| Instant::now() + ttl),
+        }
+    }
+
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// 프로세스 안의 LRU 캐시
+///
+/// `capacity` 개를 넘으면 가장 오래 쓰지 않은 항목부터 밀려납니다.
+/// 만료된 항목은 조회될 때 지워집니다.
+pub struct MemoryCache {
+    entries: Mutex<LruCache<String, Entry>>,
+}
+
+impl MemoryCache {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).expect("cache capacity must be greater than 0");
+
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    // 만료된 항목은 없는 것으로 보고 지운다.
+    fn live<'a>(entries: &'a mut LruCache<String, Entry>, key: &str) -> Option<&'a Entry> {
+        if entries.peek(key).is_some_and(|entry| entry.is_expired(Instant::now())) {
+            entries.pop(key);
+        }
+
+        entries.get(key)
+    }
+}
+
+impl Default for MemoryCache {
+    fn default() -> Self {
+        Self::new(10_000)
+    }
+}
+
+impl Cache for MemoryCache {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Vec<u8>>, CacheError>> {
+        let mut entries = self.entries.lock().unwrap();
+        let value = Self::live(&mut entries, key).map(|entry| entry.value.clone());
+
+        Box::pin(ready(Ok(value)))
+    }
+
+    fn set<'a>(&'a self, key: &'a str, value: Vec<u8>, ttl: Option<Duration>) -> BoxFuture<'a, Result<(), CacheError>> {
+        self.entries.lock().unwrap().put(key.to_string(), Entry::new(value, ttl));
+
+        Box::pin(ready(Ok(())))
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<bool, CacheError>> {
+        let mut entries = self.entries.lock().unwrap();
+        let deleted = Self::live(&mut entries, key).is_some() && entries.pop(key).is_some();
+
+        Box::pin(ready(Ok(deleted)))
+    }
+
+    fn ttl<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Duration>, CacheError>> {
+        let mut entries = self.entries.lock().unwrap();
+        let ttl = Self::live(&mut entries, key)
+            .and_then(|entry| entry.expires_at)
+            .map(|expires_at| expires_at.saturating_duration_since(Instant::now()));
+
+        Box::pin(ready(Ok(ttl)))
+    }
+
+    fn compare_and_swap<'a>(
+        &'a self,
+        key: &'a str,
+        expected: Option<&'a [u8]>,
+        value: Vec<u8>,
+        ttl: Option<Duration>,
+    ) -> BoxFuture<'a, Result<bool, CacheError>> {
+        let mut entries = self.entries.lock().unwrap();
+        let swapped = Self::live(&mut entries, key).map(|entry| entry.value.as_slice()) == expected;
+
+        if swapped {
+            entries.put(key.to_string(), Entry::new(value, ttl));
+        }
+
+        Box::pin(ready(Ok(swapped)))
+    }
+
+    fn increment<'a>(&'a self, key: &'a str, initial: u64) -> BoxFuture<'a, Result<u64, CacheError>> {
+        let mut entries = self.entries.lock().unwrap();
+        let current = Self::live(&mut entries, key).map(|entry| (String::from_utf8_lossy(&entry.value).parse::<u64>(), entry.expires_at));
+
+        let result = match current {
+            None => {
+                entries.put(key.to_string(), Entry::new(initial.to_string().into_bytes(), None));
+                Ok(initial)
+            }
+            Some((Ok(value), expires_at)) => {
+                let value = value + 1;
+                entries.put(key.to_string(), Entry {
+                    value: value.to_string().into_bytes(),
+                    expires_at,
+                });
+                Ok(value)
+            }
+            Some((Err(_), _)) => Err(CacheError::NotAnInteger(key.to_string())),
+        };
+
+        Box::pin(ready(result))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// 백엔드 공통 동작. Redis 테스트도 같은 검사를 사용합니다.
+    pub(crate) async fn check_backend(cache: &dyn Cache, prefix: &str) {
+        let key = format!("{}:check", prefix);
+        cache.delete(&key).await.unwrap();
+
+        assert_eq!(cache.get(&key).await.unwrap(), None);
+        assert!(cache.compare_and_swap(&key, None, b"a".to_vec(), None).await.unwrap());
+        assert!(!cache.compare_and_swap(&key, None, b"b".to_vec(), None).await.unwrap());
+        assert_eq!(cache.get(&key).await.unwrap(), Some(b"a".to_vec()));
+        assert_eq!(cache.ttl(&key).await.unwrap(), None);
+
+        assert!(!cache.compare_and_swap(&key, Some(b"x"), b"b".to_vec(), None).await.unwrap());
+        assert!(
+            cache
+                .compare_and_swap(&key, Some(b"a"), b"b".to_vec(), Some(Duration::from_secs(60)))
+                .await
+                .unwrap()
+        );
+        assert_eq!(cache.get(&key).await.unwrap(), Some(b"b".to_vec()));
+        assert!(cache.ttl(&key).await.unwrap().is_some_and(|ttl| ttl <= Duration::from_secs(60)));
+
+        cache.set(&key, b"c".to_vec(), Some(Duration::from_millis(50))).await.unwrap();
+        ntex::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(cache.get(&key).await.unwrap(), None);
+        assert!(cache.compare_and_swap(&key, None, b"d".to_vec(), None).await.unwrap());
+
+        assert!(cache.delete(&key).await.unwrap());
+        assert!(!cache.delete(&key).await.unwrap());
+
+        assert_eq!(cache.increment(&key, 7).await.unwrap(), 7);
+        assert_eq!(cache.increment(&key, 7).await.unwrap(), 8);
+        assert_eq!(cache.get(&key).await.unwrap(), Some(b"8".to_vec()));
+        cache.set(&key, b"\"x\"".to_vec(), None).await.unwrap();
+        assert!(cache.increment(&key, 7).await.is_err());
+        cache.delete(&key).await.unwrap();
+    }
+
+    #[ntex::test]
+    async fn test_memory_backend() {
+        check_backend(&MemoryCache::new(16), "memory").await;
+    }
+
+    #[ntex::test]
+    async fn test_memory_evicts_least_recently_used() {
+        let cache = MemoryCache::new(2);
+
+        cache.set("a", b"1".to_vec(), None).await.unwrap();
+        cache.set("b", b"2".to_vec(), None).await.unwrap();
+        cache.get("a").await.unwrap();
+        cache.set("c", b"3".to_vec(), None).await.unwrap();
+
+        assert!(cache.get("a").await.unwrap().is_some());
+        assert!(cache.get("b").await.unwrap().is_none());
+        assert!(cache.get("c").await.unwrap().is_some());
+    }
+}
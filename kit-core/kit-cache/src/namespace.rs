@@ -0,0 +1,143 @@
+use std::sync::Arc;
+use std::time::Duration;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use crate::cache::Cache;
+use crate::error::CacheError;
+
+/// 키 앞에 `{name}:` 을 붙이고 값을 JSON 으로 주고받는 캐시 핸들
+///
+/// 같은 백엔드를 여러 모듈이 나눠 쓸 때 키가 겹치지 않도록 모듈마다 네임스페이스를 하나씩 둡니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// let users = Namespace::new(cache, "user");
+/// users.set("1", &user, Some(Duration::from_secs(60))).await?;
+/// let user: Option<UserView> = users.get("1").await?;
+/// ```
+#[derive(Clone)]
+pub struct Namespace {
+    cache: Arc<dyn Cache>,
+    prefix: String,
+}
+
+impl Namespace {
+    pub fn new(cache: Arc<dyn Cache>, name: impl Into<String>) -> Self {
+        Self {
+            cache,
+            prefix: name.into(),
+        }
+    }
+
+    /// `{name}:{sub}:` 아래의 네임스페이스
+    pub fn namespace(&self, sub: &str) -> Self {
+        Self {
+            cache: self.cache.clone(),
+            prefix: format!("{}:{}", self.prefix, sub),
+        }
+    }
+
+    pub fn cache(&self) -> &Arc<dyn Cache> {
+        &self.cache
+    }
+
+    /// 백엔드에 저장되는 실제 키
+    pub fn key(&self, key: &str) -> String {
+        format!("{}:{}", self.prefix, key)
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, CacheError> {
+        let key = self.key(key);
+
+        match self.cache.get(&key).await? {
+            Some(value) => serde_json::from_slice(&value)
+                .map(Some)
+                .map_err(|source| CacheError::Decode { key, source }),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn set<T: Serialize + ?Sized>(&self, key: &str, value: &T, ttl: Option<Duration>) -> Result<(), CacheError> {
+        let value = serde_json::to_vec(value).map_err(CacheError::Encode)?;
+        self.cache.set(&self.key(key), value, ttl).await
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<bool, CacheError> {
+        self.cache.delete(&self.key(key)).await
+    }
+
+    pub async fn ttl(&self, key: &str) -> Result<Option<Duration>, CacheError> {
+        self.cache.ttl(&self.key(key)).await
+    }
+
+    /// 정수 값을 1 올립니다. 키가 없으면 `initial` 로 시작합니다.
+    pub async fn increment(&self, key: &str, initial: u64) -> Result<u64, CacheError> {
+        self.cache.increment(&self.key(key), initial).await
+    }
+
+    /// 현재 값이 `expected` 와 같을 때만 `value` 로 바꿉니다. `expected` 가 `None` 이면 키가 없을 때만 저장합니다.
+    ///
+    /// 비교는 직렬화된 JSON 으로 하므로, 같은 타입으로 저장한 값끼리 비교해야 합니다.
+    pub async fn compare_and_swap<T: Serialize + ?Sized>(
+        &self,
+        key: &str,
+        expected: Option<&T>,
+        value: &T,
+        ttl: Option<Duration>,
+    ) -> Result<bool, CacheError> {
+        let expected = expected
+            .map(serde_json::to_vec)
+            .transpose()
+            .map_err(CacheError::Encode)?;
+        let value = serde_json::to_vec(value).map_err(CacheError::Encode)?;
+
+        self.cache
+            .compare_and_swap(&self.key(key), expected.as_deref(), value, ttl)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use crate::memory::MemoryCache;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Counter {
+        value: u32,
+    }
+
+    #[ntex::test]
+    async fn test_namespace_prefixes_keys_and_round_trips_values() {
+        let cache: Arc<dyn Cache> = Arc::new(MemoryCache::new(16));
+        let users = Namespace::new(cache.clone(), "user");
+        let sessions = users.namespace("session");
+
+        users.set("1", &Counter { value: 1 }, None).await.unwrap();
+        sessions.set("1", &Counter { value: 2 }, None).await.unwrap();
+
+        assert_eq!(sessions.key("1"), "user:session:1");
+        assert_eq!(users.get::<Counter>("1").await.unwrap(), Some(Counter { value: 1 }));
+        assert_eq!(sessions.get::<Counter>("1").await.unwrap(), Some(Counter { value: 2 }));
+        assert!(cache.get("1").await.unwrap().is_none());
+
+        assert!(
+            users
+                .compare_and_swap("1", Some(&Counter { value: 1 }), &Counter { value: 3 }, None)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !users
+                .compare_and_swap("1", Some(&Counter { value: 1 }), &Counter { value: 4 }, None)
+                .await
+                .unwrap()
+        );
+        assert_eq!(users.get::<Counter>("1").await.unwrap(), Some(Counter { value: 3 }));
+
+        cache.set("user:2", b"not json".to_vec(), None).await.unwrap();
+        assert!(matches!(users.get::<Counter>("2").await, Err(CacheError::Decode { .. })));
+    }
+}
@@ -0,0 +1,432 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use futures::FutureExt;
+use futures::channel::oneshot;
+use futures::future::Shared;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use crate::namespace::Namespace;
+
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    value: T,
+    /// 이 시각(유닉스 밀리초)이 지나면 stale 로 보고 백그라운드에서 다시 읽는다.
+    fresh_until: u64,
+    /// 저장할 때의 태그 버전. 하나라도 바뀌었으면 무효이다.
+    tags: Vec<(String, u64)>,
+}
+
+enum Lookup<T> {
+    Fresh(T),
+    Stale(T),
+    Miss,
+}
+
+// 로더의 결과 `Result<T, Arc<E>>`
+type Outcome = Arc<dyn Any + Send + Sync>;
+type Landing = Shared<oneshot::Receiver<Outcome>>;
+
+enum Flight {
+    Leader(FlightGuard),
+    Waiter(Landing),
+}
+
+// 키별로 실행 중인 로더
+#[derive(Default)]
+struct Flights {
+    keys: Mutex<HashMap<String, Landing>>,
+}
+
+impl Flights {
+    // 실행 중인 로더가 없으면 이 호출이 로더를 맡는다.
+    fn join(self: &Arc<Self>, key: &str) -> Flight {
+        let mut keys = self.keys.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(landing) = keys.get(key) {
+            return Flight::Waiter(landing.clone());
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        let landing = receiver.shared();
+        keys.insert(key.to_string(), landing.clone());
+
+        Flight::Leader(FlightGuard {
+            flights: self.clone(),
+            key: key.to_string(),
+            landing,
+            sender: Some(sender),
+        })
+    }
+}
+
+// 로더를 맡은 호출이 쥐고 있다가 끝나거나 취소되면 키를 비운다.
+// 결과를 보내지 못하고 버려지면 기다리던 호출은 취소를 받고 다시 시도한다.
+struct FlightGuard {
+    flights: Arc<Flights>,
+    key: String,
+    landing: Landing,
+    sender: Option<oneshot::Sender<Outcome>>,
+}
+
+impl FlightGuard {
+    fn land(mut self, outcome: Outcome) {
+        if let Some(sender) = self.sender.take() {
+            let _ = sender.send(outcome);
+        }
+    }
+}
+
+impl Drop for FlightGuard {
+    fn drop(&mut self) {
+        let mut keys = self.flights.keys.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if keys.get(&self.key).is_some_and(|landing| landing.ptr_eq(&self.landing)) {
+            keys.remove(&self.key);
+        }
+    }
+}
+
+/// 캐시에 없을 때만 원본을 읽어 채우는 캐시 어사이드 헬퍼
+///
+/// - 같은 키의 동시 미스는 로더를 한 번만 실행하고 나머지는 그 결과나 에러를 함께 받습니다. (single-flight)
+///   에러는 여러 호출이 나눠 가지므로 `Arc<E>` 로 돌려줍니다.
+/// - `ttl` 이 지난 값은 `stale_while_revalidate` 동안 그대로 돌려주면서 백그라운드에서 다시 읽습니다.
+/// - 값마다 태그를 달 수 있고, [`ReadThrough::invalidate_tag`] 로 그 태그가 달린 값을 한꺼번에 무효화합니다.
+///
+/// 캐시 백엔드 오류는 로그만 남기고 원본을 읽으므로, 캐시가 죽어도 조회는 실패하지 않습니다.
+/// 로더가 실패한 결과는 저장하지 않습니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// let repository = self.user_view_repository.clone();
+/// let user = self.cache
+///     .get_or_load(&id.to_string(), &[&format!("user:{}", id)], move || async move { repository.find(id).await })
+///     .await?;
+/// ```
+#[derive(Clone)]
+pub struct ReadThrough {
+    entries: Namespace,
+    tags: Namespace,
+    ttl: Duration,
+    stale_while_revalidate: Duration,
+    flights: Arc<Flights>,
+}
+
+impl ReadThrough {
+    /// 값은 `{namespace}:{key}`, 태그 버전은 `{namespace}:#tag:{tag}` 에 저장됩니다.
+    pub fn new(namespace: Namespace) -> Self {
+        Self {
+            tags: namespace.namespace("#tag"),
+            entries: namespace,
+            ttl: Duration::from_secs(60),
+            stale_while_revalidate: Duration::ZERO,
+            flights: Arc::new(Flights::default()),
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn with_stale_while_revalidate(mut self, window: Duration) -> Self {
+        self.stale_while_revalidate = window;
+        self
+    }
+
+    pub async fn get_or_load<T, E, F, Fut>(&self, key: &str, tags: &[&str], load: F) -> Result<T, Arc<E>>
+    where
+        T: Serialize + DeserializeOwned + Clone + Send + Sync + 'static,
+        E: Display + Send + Sync + 'static,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+    {
+        match self.lookup(key).await {
+            Lookup::Fresh(value) => return Ok(value),
+            Lookup::Stale(value) => {
+                self.revalidate(key, tags, load);
+                return Ok(value);
+            }
+            Lookup::Miss => {}
+        }
+
+        let guard = loop {
+            match self.flights.join(key) {
+                Flight::Leader(guard) => break guard,
+                Flight::Waiter(landing) => {
+                    if let Ok(outcome) = landing.await
+                        && let Some(result) = outcome.downcast_ref::<Result<T, Arc<E>>>()
+                    {
+                        return result.clone();
+                    }
+                }
+            }
+        };
+
+        // 로더를 맡기 직전에 다른 요청이 채웠을 수 있다.
+        match self.lookup(key).await {
+            Lookup::Fresh(value) | Lookup::Stale(value) => return Ok(value),
+            Lookup::Miss => {}
+        }
+
+        let result = self.load(key, tags, load).await.map_err(Arc::new);
+        guard.land(Arc::new(result.clone()));
+        result
+    }
+
+    /// 키 하나를 지웁니다.
+    pub async fn invalidate(&self, key: &str) {
+        if let Err(e) = self.entries.delete(key).await {
+            eprintln!("[cache] failed to invalidate `{}`: {}", self.entries.key(key), e);
+        }
+    }
+
+    /// 태그 버전을 올려 이 태그가 달린 값을 모두 무효화합니다. 값은 다음 조회 때 다시 읽힙니다.
+    ///
+    /// 버전은 백엔드에서 원자적으로 올리므로 인스턴스 사이의 시계 차이나 같은 밀리초의 무효화에도 겹치지 않습니다.
+    pub async fn invalidate_tag(&self, tag: &str) {
+        // 태그 키가 밀려났다가 다시 시작해도 예전 버전과 겹치지 않도록 현재 시각에서 시작한다.
+        if let Err(e) = self.tags.increment(tag, now_millis().max(1)).await {
+            eprintln!("[cache] failed to invalidate tag `{}`: {}", self.tags.key(tag), e);
+        }
+    }
+
+    async fn lookup<T: DeserializeOwned>(&self, key: &str) -> Lookup<T> {
+        let entry = match self.entries.get::<Entry<T>>(key).await {
+            Ok(Some(entry)) => entry,
+            Ok(None) => return Lookup::Miss,
+            Err(e) => {
+                eprintln!("[cache] failed to read `{}`: {}", self.entries.key(key), e);
+                return Lookup::Miss;
+            }
+        };
+
+        for (tag, version) in &entry.tags {
+            if self.tag_version(tag).await != Some(*version) {
+                return Lookup::Miss;
+            }
+        }
+
+        if entry.fresh_until > now_millis() {
+            Lookup::Fresh(entry.value)
+        } else {
+            Lookup::Stale(entry.value)
+        }
+    }
+
+    // 태그 버전이 없으면 0 이다. 백엔드가 태그 키를 밀어내도 0 으로 돌아갈 뿐이므로
+    // 무효화된 값이 되살아나지 않는다. 읽기에 실패하면 `None` 으로 미스 처리한다.
+    async fn tag_version(&self, tag: &str) -> Option<u64> {
+        match self.tags.get::<u64>(tag).await {
+            Ok(version) => Some(version.unwrap_or(0)),
+            Err(e) => {
+                eprintln!("[cache] failed to read tag `{}`: {}", self.tags.key(tag), e);
+                None
+            }
+        }
+    }
+
+    async fn load<T, E, F, Fut>(&self, key: &str, tags: &[&str], load: F) -> Result<T, E>
+    where
+        T: Serialize + Sync,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        // 로더보다 먼저 태그 버전을 읽어야, 로딩 중에 무효화된 값이 새 버전으로 저장되지 않는다.
+        let mut versions = Vec::with_capacity(tags.len());
+        for tag in tags {
+            versions.push((tag.to_string(), self.tag_version(tag).await));
+        }
+
+        let value = load().await?;
+
+        let Some(versions) = versions
+            .into_iter()
+            .map(|(tag, version)| version.map(|version| (tag, version)))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return Ok(value);
+        };
+
+        let entry = Entry {
+            value,
+            fresh_until: now_millis() + self.ttl.as_millis() as u64,
+            tags: versions,
+        };
+
+        if let Err(e) = self
+            .entries
+            .set(key, &entry, Some(self.ttl + self.stale_while_revalidate))
+            .await
+        {
+            eprintln!("[cache] failed to write `{}`: {}", self.entries.key(key), e);
+        }
+
+        Ok(entry.value)
+    }
+
+    // 이미 같은 키를 읽는 중이면 건너뛴다.
+    fn revalidate<T, E, F, Fut>(&self, key: &str, tags: &[&str], load: F)
+    where
+        T: Serialize + Clone + Send + Sync + 'static,
+        E: Display + Send + Sync + 'static,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+    {
+        let this = self.clone();
+        let key = key.to_string();
+        let tags = tags.iter().map(|tag| tag.to_string()).collect::<Vec<_>>();
+
+        tokio::spawn(async move {
+            let Flight::Leader(guard) = this.flights.join(&key) else {
+                return;
+            };
+
+            let tags = tags.iter().map(String::as_str).collect::<Vec<_>>();
+            let result = this.load(&key, &tags, load).await.map_err(Arc::new);
+            if let Err(e) = &result {
+                eprintln!("[cache] failed to revalidate `{}`: {}", this.entries.key(&key), e);
+            }
+
+            guard.land(Arc::new(result));
+        });
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use crate::cache::Cache;
+    use crate::memory::MemoryCache;
+
+    fn read_through() -> ReadThrough {
+        let cache: Arc<dyn Cache> = Arc::new(MemoryCache::new(64));
+        ReadThrough::new(Namespace::new(cache, "test"))
+    }
+
+    async fn load(cache: &ReadThrough, calls: &Arc<AtomicUsize>, tags: &[&str]) -> Result<usize, Arc<String>> {
+        let calls = calls.clone();
+
+        cache
+            .get_or_load("key", tags, move || async move {
+                ntex::time::sleep(Duration::from_millis(20)).await;
+                Ok(calls.fetch_add(1, Ordering::SeqCst))
+            })
+            .await
+    }
+
+    #[ntex::test]
+    async fn test_concurrent_misses_load_once() {
+        let cache = read_through();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let results = futures::future::join_all((0..5).map(|_| load(&cache, &calls, &[]))).await;
+
+        assert!(results.into_iter().all(|result| result == Ok(0)));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[ntex::test]
+    async fn test_failed_load_is_not_cached() {
+        let cache = read_through();
+
+        let failed = cache
+            .get_or_load("key", &[], || async { Err::<u32, _>("unavailable") })
+            .await;
+        let loaded = cache
+            .get_or_load("key", &[], || async { Ok::<_, String>(1) })
+            .await;
+
+        assert_eq!(failed, Err(Arc::new("unavailable")));
+        assert_eq!(loaded, Ok(1));
+    }
+
+    #[ntex::test]
+    async fn test_waiters_share_failed_load() {
+        let cache = read_through();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let results = futures::future::join_all((0..5).map(|_| {
+            let calls = calls.clone();
+            cache.get_or_load("key", &[], move || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                ntex::time::sleep(Duration::from_millis(20)).await;
+                Err::<u32, _>("unavailable")
+            })
+        }))
+        .await;
+
+        assert!(results.into_iter().all(|result| result == Err(Arc::new("unavailable"))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[ntex::test]
+    async fn test_cancelled_loader_does_not_leak_flight() {
+        let cache = read_through();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let leader = {
+            let cache = cache.clone();
+            let calls = calls.clone();
+            tokio::spawn(async move { load(&cache, &calls, &[]).await })
+        };
+        ntex::time::sleep(Duration::from_millis(5)).await;
+        let waiter = {
+            let cache = cache.clone();
+            let calls = calls.clone();
+            tokio::spawn(async move { load(&cache, &calls, &[]).await })
+        };
+        ntex::time::sleep(Duration::from_millis(5)).await;
+        leader.abort();
+
+        assert_eq!(waiter.await.unwrap(), Ok(0));
+        assert!(cache.flights.keys.lock().unwrap().is_empty());
+    }
+
+    #[ntex::test]
+    async fn test_stale_value_is_served_while_revalidating() {
+        let cache = read_through()
+            .with_ttl(Duration::from_millis(30))
+            .with_stale_while_revalidate(Duration::from_secs(5));
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        assert_eq!(load(&cache, &calls, &[]).await, Ok(0));
+        ntex::time::sleep(Duration::from_millis(40)).await;
+
+        assert_eq!(load(&cache, &calls, &[]).await, Ok(0));
+        ntex::time::sleep(Duration::from_millis(40)).await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(load(&cache, &calls, &[]).await, Ok(1));
+    }
+
+    #[ntex::test]
+    async fn test_invalidate_tag_and_key() {
+        let cache = read_through().with_ttl(Duration::from_secs(60));
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        assert_eq!(load(&cache, &calls, &["user:1"]).await, Ok(0));
+        cache.invalidate_tag("user:2").await;
+        assert_eq!(load(&cache, &calls, &["user:1"]).await, Ok(0));
+
+        cache.invalidate_tag("user:1").await;
+        assert_eq!(load(&cache, &calls, &["user:1"]).await, Ok(1));
+        assert_eq!(load(&cache, &calls, &["user:1"]).await, Ok(1));
+
+        cache.invalidate("key").await;
+        assert_eq!(load(&cache, &calls, &["user:1"]).await, Ok(2));
+    }
+}
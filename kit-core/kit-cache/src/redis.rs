@@ -0,0 +1,151 @@
+use std::time::Duration;
+use futures::future::BoxFuture;
+use redis::aio::ConnectionManager;
+use redis::{Client, Script, cmd};
+use crate::cache::Cache;
+use crate::error::CacheError;
+
+// ARGV[1] 이 "1" 이면 ARGV[2] 와 현재 값을 비교하고, "0" 이면 키가 없어야 한다.
+// ARGV[4] 는 밀리초 단위 만료 시간이며 0 이면 만료되지 않는다.
+const COMPARE_AND_SWAP: &str = r#"
+local current = redis.call('GET', KEYS[1])
+if ARGV[1] == '1' then
+    if current ~= ARGV[2] then return 0 end
+elseif current then
+    return 0
+end
+if ARGV[4] == '0' then
+    redis.call('SET', KEYS[1], ARGV[3])
+else
+    redis.call('SET', KEYS[1], ARGV[3], 'PX', ARGV[4])
+end
+return 1
+"#;
+
+// 키가 있으면 INCR, 없으면 ARGV[1] 로 시작한다.
+const INCREMENT: &str = r#"
+if redis.call('EXISTS', KEYS[1]) == 1 then
+    return redis.call('INCR', KEYS[1])
+end
+redis.call('SET', KEYS[1], ARGV[1])
+return tonumber(ARGV[1])
+"#;
+
+/// Redis 캐시
+///
+/// 연결이 끊기면 [`ConnectionManager`] 가 다시 연결합니다. 복제해서 여러 곳에서 사용할 수 있습니다.
+#[derive(Clone)]
+pub struct RedisCache {
+    connection: ConnectionManager,
+}
+
+impl RedisCache {
+    /// # 예시
+    ///
+    /// ```ignore
+    /// let cache = RedisCache::connect("redis://127.0.0.1:6379").await?;
+    /// ```
+    pub async fn connect(url: &str) -> Result<Self, CacheError> {
+        let client = Client::open(url)?;
+        let connection = ConnectionManager::new(client).await?;
+
+        Ok(Self { connection })
+    }
+}
+
+fn millis(ttl: Duration) -> u64 {
+    // PX 0 은 허용되지 않으므로 최소 1ms 로 올린다.
+    (ttl.as_millis() as u64).max(1)
+}
+
+impl Cache for RedisCache {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Vec<u8>>, CacheError>> {
+        let mut connection = self.connection.clone();
+
+        Box::pin(async move { Ok(cmd("GET").arg(key).query_async(&mut connection).await?) })
+    }
+
+    fn set<'a>(&'a self, key: &'a str, value: Vec<u8>, ttl: Option<Duration>) -> BoxFuture<'a, Result<(), CacheError>> {
+        let mut connection = self.connection.clone();
+
+        Box::pin(async move {
+            let mut command = cmd("SET");
+            command.arg(key).arg(value);
+
+            if let Some(ttl) = ttl {
+                command.arg("PX").arg(millis(ttl));
+            }
+
+            Ok(command.query_async(&mut connection).await?)
+        })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<bool, CacheError>> {
+        let mut connection = self.connection.clone();
+
+        Box::pin(async move {
+            let deleted: u64 = cmd("DEL").arg(key).query_async(&mut connection).await?;
+            Ok(deleted > 0)
+        })
+    }
+
+    fn ttl<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<Duration>, CacheError>> {
+        let mut connection = self.connection.clone();
+
+        Box::pin(async move {
+            // 키가 없으면 -2, 만료 시간이 없으면 -1
+            let ttl: i64 = cmd("PTTL").arg(key).query_async(&mut connection).await?;
+            Ok(u64::try_from(ttl).ok().map(Duration::from_millis))
+        })
+    }
+
+    fn compare_and_swap<'a>(
+        &'a self,
+        key: &'a str,
+        expected: Option<&'a [u8]>,
+        value: Vec<u8>,
+        ttl: Option<Duration>,
+    ) -> BoxFuture<'a, Result<bool, CacheError>> {
+        let mut connection = self.connection.clone();
+
+        Box::pin(async move {
+            let swapped: i64 = Script::new(COMPARE_AND_SWAP)
+                .key(key)
+                .arg(if expected.is_some() { "1" } else { "0" })
+                .arg(expected.unwrap_or_default())
+                .arg(value)
+                .arg(ttl.map(millis).unwrap_or(0))
+                .invoke_async(&mut connection)
+                .await?;
+
+            Ok(swapped == 1)
+        })
+    }
+
+    fn increment<'a>(&'a self, key: &'a str, initial: u64) -> BoxFuture<'a, Result<u64, CacheError>> {
+        let mut connection = self.connection.clone();
+
+        Box::pin(async move {
+            Ok(Script::new(INCREMENT)
+                .key(key)
+                .arg(initial)
+                .invoke_async(&mut connection)
+                .await?)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::tests::check_backend;
+
+    #[ntex::test]
+    #[ignore = "requires a local redis-server"]
+    async fn test_redis_backend() {
+        let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let cache = RedisCache::connect(&url).await.unwrap();
+
+        check_backend(&cache, "kit-cache-test").await;
+    }
+}
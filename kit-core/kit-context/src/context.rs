@@ -0,0 +1,131 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use ntex::http::Payload;
+use ntex::web::{ErrorRenderer, FromRequest, HttpRequest};
+
+/// 남은 처리 시간(ms). 요청을 받을 때 읽고, 하위 서비스를 호출할 때 남은 시간으로 다시 채웁니다.
+pub const DEADLINE_HEADER: &str = "x-request-timeout-ms";
+/// gateway 가 인증을 마친 뒤 사용자 식별자를 전달하는 헤더
+pub const USER_ID_HEADER: &str = "x-user-id";
+/// gateway 가 전달하는 사용자 역할 목록(쉼표 구분)
+pub const USER_ROLES_HEADER: &str = "x-user-roles";
+
+tokio::task_local! {
+    static CURRENT: Arc<RequestContext>;
+}
+
+/// 인증된 호출자
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    pub id: String,
+    pub roles: Vec<String>,
+}
+
+impl Principal {
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|candidate| candidate == role)
+    }
+}
+
+/// 요청 하나의 처리 범위에서 공유되는 정보
+///
+/// [`crate::Context`] 미들웨어가 요청마다 만들어 태스크 로컬에 담으므로, 핸들러뿐 아니라
+/// 리포지토리나 `HttpClient` 처럼 인자로 전달받지 않은 곳에서도 [`RequestContext::current`] 로 읽을 수 있습니다.
+///
+/// `spawn` 한 태스크에는 전달되지 않으므로 필요하면 [`RequestContext::scope`] 로 다시 감싸야 합니다.
+#[derive(Debug, Clone, Default)]
+pub struct RequestContext {
+    pub request_id: String,
+    pub trace_id: Option<String>,
+    pub principal: Option<Principal>,
+    pub locale: Option<String>,
+    pub deadline: Option<Instant>,
+    pub client_ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl RequestContext {
+    /// 현재 태스크의 요청 컨텍스트. 요청 밖(부트스트랩, 백그라운드 작업)에서는 `None` 입니다.
+    pub fn current() -> Option<Arc<RequestContext>> {
+        CURRENT.try_with(Arc::clone).ok()
+    }
+
+    /// `future` 를 이 컨텍스트 안에서 실행합니다.
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT.scope(Arc::new(self), future).await
+    }
+
+    /// 마감까지 남은 시간. 마감이 지났으면 `Duration::ZERO` 입니다.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining().is_some_and(|remaining| remaining.is_zero())
+    }
+
+    /// 하위 서비스 호출에 이어 붙일 헤더 (요청 ID, 남은 시간)
+    pub fn propagation_headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = vec![(core_filter::REQUEST_ID_HEADER, self.request_id.clone())];
+
+        if let Some(remaining) = self.remaining() {
+            headers.push((DEADLINE_HEADER, remaining.as_millis().to_string()));
+        }
+
+        headers
+    }
+}
+
+/// 핸들러 인자로 현재 요청 컨텍스트를 받습니다.
+impl<Err: ErrorRenderer> FromRequest<Err> for RequestContext {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &HttpRequest, _: &mut Payload) -> Result<Self, Self::Error> {
+        let context = req
+            .extensions()
+            .get::<Arc<RequestContext>>()
+            .map(|context| context.as_ref().clone());
+
+        Ok(context.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[ntex::test]
+    async fn test_current_is_scoped_to_future() {
+        assert!(RequestContext::current().is_none());
+
+        let context = RequestContext {
+            request_id: "abc".to_string(),
+            deadline: Some(Instant::now() + Duration::from_secs(5)),
+            ..Default::default()
+        };
+
+        let request_id = context
+            .scope(async { RequestContext::current().map(|context| context.request_id.clone()) })
+            .await;
+
+        assert_eq!(request_id.as_deref(), Some("abc"));
+        assert!(RequestContext::current().is_none());
+    }
+
+    #[test]
+    fn test_deadline_propagation() {
+        let context = RequestContext {
+            request_id: "abc".to_string(),
+            deadline: Some(Instant::now() - Duration::from_millis(1)),
+            ..Default::default()
+        };
+
+        assert!(context.is_expired());
+        assert_eq!(
+            context.propagation_headers(),
+            vec![("x-request-id", "abc".to_string()), (DEADLINE_HEADER, "0".to_string())]
+        );
+    }
+}
@@ -1,14 +1,5 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+mod context;
+mod middleware;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use context::{Principal, RequestContext, DEADLINE_HEADER, USER_ID_HEADER, USER_ROLES_HEADER};
+pub use middleware::{Context, ContextMiddleware};
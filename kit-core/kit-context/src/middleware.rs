@@ -0,0 +1,243 @@
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use core_filter::CorrelationId;
+use fastrace::prelude::SpanContext;
+use ntex::http::HeaderMap;
+use ntex::http::header::{ACCEPT_LANGUAGE, USER_AGENT};
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web;
+use crate::context::{DEADLINE_HEADER, Principal, RequestContext, USER_ID_HEADER, USER_ROLES_HEADER};
+
+/// 요청마다 [`RequestContext`] 를 만들어 핸들러 실행 범위에 담는 미들웨어
+///
+/// 요청 ID 는 [`core_filter::ExceptionBoundary`] 가 정한 상관 ID 를 그대로 사용하고,
+/// 트레이스 ID 는 현재 스팬에서 읽으므로 `Tracer` 보다 안쪽에서 실행되도록 먼저 `.wrap()` 합니다.
+///
+/// 클라이언트 IP 는 소켓 상대 주소입니다. `X-Forwarded-For` 는 누구나 보낼 수 있으므로
+/// [`Context::with_trusted_proxies`] 로 지정한 프록시에서 온 요청일 때만 따라갑니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// App::new()
+///     .wrap(Context::new().with_timeout(Duration::from_secs(10)))
+///     .wrap(Tracer)
+///     .wrap(ExceptionBoundary)
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    timeout: Option<Duration>,
+    trusted_proxies: Vec<IpAddr>,
+}
+
+impl Context {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 상위 서비스가 `x-request-timeout-ms` 를 보내지 않았을 때 사용할 처리 시간 제한
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// `X-Forwarded-For` 를 믿어도 되는 프록시(게이트웨이, 로드 밸런서) 주소
+    pub fn with_trusted_proxies(mut self, proxies: impl IntoIterator<Item = IpAddr>) -> Self {
+        self.trusted_proxies = proxies.into_iter().collect();
+        self
+    }
+}
+
+impl<S> Middleware<S> for Context {
+    type Service = ContextMiddleware<S>;
+
+    fn create(&self, service: S) -> Self::Service {
+        ContextMiddleware {
+            service,
+            timeout: self.timeout,
+            trusted_proxies: self.trusted_proxies.clone(),
+        }
+    }
+}
+
+pub struct ContextMiddleware<S> {
+    service: S,
+    timeout: Option<Duration>,
+    trusted_proxies: Vec<IpAddr>,
+}
+
+impl<S, Err> Service<web::WebRequest<Err>> for ContextMiddleware<S>
+where
+    S: Service<web::WebRequest<Err>, Response = web::WebResponse, Error = web::Error>,
+    Err: web::ErrorRenderer,
+{
+    type Response = web::WebResponse;
+    type Error = web::Error;
+
+    ntex::forward_ready!(service);
+
+    async fn call(
+        &self,
+        req: web::WebRequest<Err>,
+        ctx: ServiceCtx<'_, Self>,
+    ) -> Result<Self::Response, Self::Error> {
+        let context = self.context(&req);
+        req.extensions_mut().insert(Arc::new(context.clone()));
+
+        context.scope(ctx.call(&self.service, req)).await
+    }
+}
+
+impl<S> ContextMiddleware<S> {
+    fn context<Err>(&self, req: &web::WebRequest<Err>) -> RequestContext {
+        let headers = req.headers();
+
+        let request_id = req
+            .extensions()
+            .get::<CorrelationId>()
+            .cloned()
+            .or_else(|| CorrelationId::from_headers(headers))
+            .unwrap_or_else(CorrelationId::generate);
+
+        let timeout = header(headers, DEADLINE_HEADER)
+            .and_then(|value| value.parse::<u64>().ok())
+            .map(Duration::from_millis)
+            .or(self.timeout);
+
+        RequestContext {
+            request_id: request_id.to_string(),
+            trace_id: SpanContext::current_local_parent()
+                .map(|span_context| format!("{:032x}", span_context.trace_id.0)),
+            principal: principal(headers),
+            locale: header(headers, ACCEPT_LANGUAGE.as_str()).and_then(locale),
+            deadline: timeout.map(|timeout| Instant::now() + timeout),
+            client_ip: client_ip(req.peer_addr().map(|addr| addr.ip()), headers, &self.trusted_proxies)
+                .map(|ip| ip.to_string()),
+            user_agent: header(headers, USER_AGENT.as_str()).map(str::to_string),
+        }
+    }
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+// 신뢰하는 프록시가 붙인 항목만 오른쪽부터 따라가, 처음 만나는 신뢰하지 않는 주소를 클라이언트로 본다.
+fn client_ip(peer: Option<IpAddr>, headers: &HeaderMap, trusted_proxies: &[IpAddr]) -> Option<IpAddr> {
+    let mut client = peer?;
+    let forwarded_for = headers
+        .get_all("x-forwarded-for")
+        .filter_map(|value| value.to_str().ok())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    for hop in forwarded_for.rsplit(',').map(str::trim).filter(|hop| !hop.is_empty()) {
+        if !trusted_proxies.contains(&client) {
+            break;
+        }
+
+        match hop.parse() {
+            Ok(ip) => client = ip,
+            Err(_) => break,
+        }
+    }
+
+    Some(client)
+}
+
+fn principal(headers: &HeaderMap) -> Option<Principal> {
+    let id = header(headers, USER_ID_HEADER)?;
+    let roles = header(headers, USER_ROLES_HEADER)
+        .map(|roles| {
+            roles
+                .split(',')
+                .map(str::trim)
+                .filter(|role| !role.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(Principal {
+        id: id.to_string(),
+        roles,
+    })
+}
+
+// `ko-KR,ko;q=0.9,en;q=0.8` 에서 첫 번째 언어 태그만 사용한다.
+fn locale(accept_language: &str) -> Option<String> {
+    accept_language
+        .split(',')
+        .filter_map(|tag| tag.split(';').next())
+        .map(str::trim)
+        .find(|tag| !tag.is_empty() && *tag != "*")
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::http::header::HeaderName;
+    use ntex::web::{App, HttpResponse, test};
+
+    async fn describe(context: RequestContext) -> HttpResponse {
+        let current = RequestContext::current().unwrap();
+
+        HttpResponse::Ok().body(format!(
+            "{}|{}|{}|{}|{}",
+            current.request_id,
+            context.principal.map(|principal| principal.roles.join(",")).unwrap_or_default(),
+            current.locale.clone().unwrap_or_default(),
+            current.user_agent.clone().unwrap_or_default(),
+            current.remaining().is_some_and(|remaining| remaining <= Duration::from_millis(500)),
+        ))
+    }
+
+    #[ntex::test]
+    async fn test_context_is_available_to_handler() {
+        let app = test::init_service(
+            App::new()
+                .wrap(Context::new().with_timeout(Duration::from_secs(30)))
+                .route("/", web::get().to(describe)),
+        )
+        .await;
+
+        let request = test::TestRequest::get()
+            .uri("/")
+            .header("x-request-id", "req-1")
+            .header(USER_ID_HEADER, "42")
+            .header(USER_ROLES_HEADER, "admin, user")
+            .header(ACCEPT_LANGUAGE, "ko-KR,ko;q=0.9,en;q=0.8")
+            .header(USER_AGENT, "test-agent")
+            .header(DEADLINE_HEADER, "500")
+            .to_request();
+
+        let response = test::call_service(&app, request).await;
+
+        assert_eq!(test::read_body(response).await, "req-1|admin,user|ko-KR|test-agent|true");
+    }
+
+    #[test]
+    fn test_client_ip_follows_forwarded_for_only_from_trusted_proxies() {
+        let proxy: IpAddr = "10.0.0.1".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(HeaderName::from_static("x-forwarded-for"), "6.6.6.6, 203.0.113.7".parse().unwrap());
+
+        let client = client_ip(Some("203.0.113.9".parse().unwrap()), &headers, &[proxy]);
+        assert_eq!(client, Some("203.0.113.9".parse().unwrap()));
+
+        let client = client_ip(Some(proxy), &headers, &[proxy]);
+        assert_eq!(client, Some("203.0.113.7".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_locale_skips_wildcard() {
+        assert_eq!(locale("*, en;q=0.5").as_deref(), Some("en"));
+        assert_eq!(locale("  "), None);
+    }
+}
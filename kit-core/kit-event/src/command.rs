@@ -0,0 +1,288 @@
+use std::any::{Any, type_name};
+use std::error::Error as StdError;
+use std::future::Future;
+use std::sync::Arc;
+use core_container::{ContainerError, Scope};
+use core_filter::ValidationError;
+use futures::future::BoxFuture;
+use ntex::http::Payload;
+use ntex::web::{FromRequest, HttpRequest};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use crate::error::BusError;
+
+/// 상태를 바꾸는 요청
+///
+/// 처리할 핸들러 타입을 함께 선언하며, [`CommandBus`] 는 그 핸들러를 컨테이너에서 꺼내
+/// [`CommandPipeline`] 의 미들웨어를 거쳐 실행합니다.
+/// 결과는 멱등성 저장 등을 위해 JSON 으로 미들웨어를 통과하므로 직렬화할 수 있어야 합니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// impl Command for UserRegisterCommand {
+///     type Result = UserRegisterCommandResult;
+///     type Handler = UserRegisterCommandHandler;
+///
+///     fn validate(&self) -> Result<(), ValidationError> { ... }
+/// }
+/// ```
+pub trait Command: Sized + Send + Sync + 'static {
+    type Result: Serialize + DeserializeOwned + Send + 'static;
+    type Handler: CommandHandler<Self>;
+
+    /// 스팬과 멱등성 키에 쓰이는 이름. 기본값은 타입 이름입니다.
+    fn name() -> &'static str {
+        let name = type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        Ok(())
+    }
+
+    /// 같은 키로 다시 들어온 커맨드는 실행하지 않고 이전 결과를 돌려줍니다. ([`crate::Idempotency`])
+    fn idempotency_key(&self) -> Option<String> {
+        None
+    }
+}
+
+pub trait CommandHandler<C: Command>: Send + Sync + 'static {
+    type Error: Into<Box<dyn StdError + Send + Sync + 'static>> + Send;
+
+    fn handle(&self, command: C) -> impl Future<Output = Result<C::Result, Self::Error>> + Send;
+}
+
+/// 미들웨어가 커맨드 타입을 모른 채 다룰 수 있는 형태
+pub trait DynCommand: Any + Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn validate(&self) -> Result<(), ValidationError>;
+
+    fn idempotency_key(&self) -> Option<String>;
+}
+
+impl<C: Command> DynCommand for C {
+    fn name(&self) -> &'static str {
+        C::name()
+    }
+
+    fn validate(&self) -> Result<(), ValidationError> {
+        Command::validate(self)
+    }
+
+    fn idempotency_key(&self) -> Option<String> {
+        Command::idempotency_key(self)
+    }
+}
+
+type Handle<'a> = Box<dyn FnOnce(Box<dyn DynCommand>) -> BoxFuture<'a, Result<Value, BusError>> + Send + 'a>;
+
+/// 남은 미들웨어와 핸들러
+pub struct Next<'a> {
+    command: Box<dyn DynCommand>,
+    middleware: &'a [Arc<dyn CommandMiddleware>],
+    handle: Handle<'a>,
+}
+
+impl<'a> Next<'a> {
+    pub fn command(&self) -> &dyn DynCommand {
+        self.command.as_ref()
+    }
+
+    pub fn run(self) -> BoxFuture<'a, Result<Value, BusError>> {
+        match self.middleware.split_first() {
+            Some((middleware, rest)) => middleware.handle(Next {
+                command: self.command,
+                middleware: rest,
+                handle: self.handle,
+            }),
+            None => (self.handle)(self.command),
+        }
+    }
+}
+
+/// 모든 커맨드에 공통으로 적용되는 처리
+///
+/// `next.run()` 을 호출하지 않으면 핸들러는 실행되지 않습니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// impl CommandMiddleware for Audit {
+///     fn handle<'a>(&'a self, next: Next<'a>) -> BoxFuture<'a, Result<Value, BusError>> {
+///         Box::pin(async move {
+///             println!("[audit] {}", next.command().name());
+///             next.run().await
+///         })
+///     }
+/// }
+/// ```
+pub trait CommandMiddleware: Send + Sync + 'static {
+    fn handle<'a>(&'a self, next: Next<'a>) -> BoxFuture<'a, Result<Value, BusError>>;
+}
+
+/// 커맨드 미들웨어 목록. 먼저 추가한 미들웨어가 바깥쪽에서 실행됩니다.
+///
+/// 부트스트랩에 인스턴스로 등록하면 [`CommandBus`] 가 사용하고, 없으면 미들웨어 없이 실행합니다.
+#[derive(Clone, Default)]
+pub struct CommandPipeline {
+    middleware: Vec<Arc<dyn CommandMiddleware>>,
+}
+
+impl CommandPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, middleware: impl CommandMiddleware) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+}
+
+/// 커맨드를 미들웨어를 거쳐 등록된 핸들러로 보냅니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// async fn create_user(command: Content<UserRegisterCommand>, commands: CommandBus) -> Result<..., Exception> {
+///     let result = commands.send(command.into_inner()).await?;
+///     Ok(Reply::created(result))
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CommandBus {
+    scope: Scope,
+    pipeline: Arc<CommandPipeline>,
+}
+
+impl CommandBus {
+    pub fn new(scope: Scope, pipeline: Arc<CommandPipeline>) -> Self {
+        Self { scope, pipeline }
+    }
+
+    /// 스코프에 등록된 [`CommandPipeline`] 을 사용합니다.
+    pub async fn from_scope(scope: Scope) -> Result<Self, BusError> {
+        let pipeline = match scope.get::<CommandPipeline>().await {
+            Ok(pipeline) => pipeline,
+            Err(ContainerError::NotRegistered(_)) => Arc::new(CommandPipeline::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self::new(scope, pipeline))
+    }
+
+    pub async fn send<C: Command>(&self, command: C) -> Result<C::Result, BusError> {
+        let handler = self.scope.get::<C::Handler>().await?;
+        let next = Next {
+            command: Box::new(command),
+            middleware: &self.pipeline.middleware,
+            handle: Box::new(move |command| {
+                Box::pin(async move {
+                    let command: Box<dyn Any> = command;
+                    let command = command
+                        .downcast::<C>()
+                        .map_err(|_| BusError::handler(format!("command type changed inside the pipeline: {}", C::name())))?;
+                    let result = handler.handle(*command).await.map_err(BusError::handler)?;
+
+                    serde_json::to_value(result).map_err(BusError::Result)
+                })
+            }),
+        };
+
+        serde_json::from_value(next.run().await?).map_err(BusError::Result)
+    }
+}
+
+impl<Err> FromRequest<Err> for CommandBus {
+    type Error = BusError;
+
+    async fn from_request(req: &HttpRequest, payload: &mut Payload) -> Result<Self, Self::Error> {
+        let scope = <Scope as FromRequest<Err>>::from_request(req, payload).await?;
+        CommandBus::from_scope(scope).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use core_container::Container;
+    use serde::Deserialize;
+
+    struct Rename {
+        name: String,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Renamed {
+        name: String,
+    }
+
+    struct RenameHandler;
+
+    impl Command for Rename {
+        type Result = Renamed;
+        type Handler = RenameHandler;
+
+        fn validate(&self) -> Result<(), ValidationError> {
+            let mut error = ValidationError::new();
+
+            if self.name.is_empty() {
+                error = error.with_field("name", "must not be empty");
+            }
+
+            error.into_result()
+        }
+    }
+
+    impl CommandHandler<Rename> for RenameHandler {
+        type Error = ValidationError;
+
+        async fn handle(&self, command: Rename) -> Result<Renamed, ValidationError> {
+            Ok(Renamed { name: command.name.to_uppercase() })
+        }
+    }
+
+    struct Record(&'static str, Arc<Mutex<Vec<String>>>);
+
+    impl CommandMiddleware for Record {
+        fn handle<'a>(&'a self, next: Next<'a>) -> BoxFuture<'a, Result<Value, BusError>> {
+            Box::pin(async move {
+                self.1.lock().unwrap().push(format!("{} {}", self.0, next.command().name()));
+                next.run().await
+            })
+        }
+    }
+
+    #[ntex::test]
+    async fn test_send_runs_pipeline_in_order() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let container = Container::builder()
+            .singleton(|_| async { Ok(RenameHandler) })
+            .instance(
+                CommandPipeline::new()
+                    .with(Record("outer", calls.clone()))
+                    .with(Record("inner", calls.clone())),
+            )
+            .build();
+        let commands = CommandBus::from_scope(container.scope()).await.unwrap();
+
+        let renamed = commands.send(Rename { name: "jeff".to_string() }).await.unwrap();
+
+        assert_eq!(renamed, Renamed { name: "JEFF".to_string() });
+        assert_eq!(*calls.lock().unwrap(), vec!["outer Rename", "inner Rename"]);
+    }
+
+    #[ntex::test]
+    async fn test_send_without_pipeline() {
+        let container = Container::builder()
+            .singleton(|_| async { Ok(RenameHandler) })
+            .build();
+        let commands = CommandBus::from_scope(container.scope()).await.unwrap();
+
+        assert!(commands.send(Rename { name: String::new() }).await.is_ok());
+    }
+}
@@ -0,0 +1,42 @@
+use std::error::Error as StdError;
+use core_container::ContainerError;
+use core_filter::ValidationError;
+use ntex::web::{DefaultError, WebResponseError};
+use thiserror::Error;
+
+/// 버스가 돌려주는 에러
+///
+/// 감싼 에러는 모두 `source()` 로 꺼낼 수 있어 예외 필터가 원래 타입 기준으로 응답을 만듭니다.
+#[derive(Debug, Error)]
+pub enum BusError {
+    #[error(transparent)]
+    Container(#[from] ContainerError),
+
+    /// [`crate::Validation`] 미들웨어가 거절한 커맨드
+    #[error("{0}")]
+    Validation(#[source] ValidationError),
+
+    /// 커맨드 결과를 JSON 으로 옮기지 못함
+    #[error("failed to transfer command result: {0}")]
+    Result(#[source] serde_json::Error),
+
+    /// 핸들러나 미들웨어가 돌려준 에러
+    #[error("{0}")]
+    Handler(#[source] Box<dyn StdError + Send + Sync + 'static>),
+}
+
+impl BusError {
+    pub fn handler(error: impl Into<Box<dyn StdError + Send + Sync + 'static>>) -> Self {
+        BusError::Handler(error.into())
+    }
+}
+
+/// 버스 주입에 실패하면 500 으로 응답합니다.
+impl WebResponseError<DefaultError> for BusError {}
+
+/// 같은 멱등성 키의 커맨드가 아직 실행 중이라 거절된 커맨드 ([`crate::Idempotency`])
+///
+/// [`BusError::Handler`] 에 담기므로 예외 필터에서 이 타입으로 `409` 응답을 만들 수 있습니다.
+#[derive(Debug, Error)]
+#[error("command `{0}` with the same idempotency key is still being processed")]
+pub struct CommandInProgress(pub &'static str);
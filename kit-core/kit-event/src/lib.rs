@@ -1,14 +1,9 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+mod command;
+mod error;
+mod middleware;
+mod query;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use command::{Command, CommandBus, CommandHandler, CommandMiddleware, CommandPipeline, DynCommand, Next};
+pub use error::{BusError, CommandInProgress};
+pub use middleware::{Idempotency, IdempotencyClaim, IdempotencyStore, MemoryIdempotencyStore, Tracing, Validation};
+pub use query::{Query, QueryBus, QueryHandler};
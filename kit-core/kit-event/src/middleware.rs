@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+use fastrace::prelude::*;
+use futures::future::BoxFuture;
+use kit_context::RequestContext;
+use serde_json::Value;
+use crate::command::{CommandMiddleware, Next};
+use crate::error::{BusError, CommandInProgress};
+
+/// 커맨드마다 `command {name}` 스팬을 만들고, 실패하면 `error` 속성을 남깁니다.
+pub struct Tracing;
+
+impl CommandMiddleware for Tracing {
+    fn handle<'a>(&'a self, next: Next<'a>) -> BoxFuture<'a, Result<Value, BusError>> {
+        let span = Span::enter_with_local_parent(format!("command {}", next.command().name()));
+
+        Box::pin(
+            async move {
+                let result = next.run().await;
+
+                if let Err(e) = &result {
+                    LocalSpan::add_property(|| ("error", e.to_string()));
+                }
+
+                result
+            }
+            .in_span(span),
+        )
+    }
+}
+
+/// 핸들러보다 먼저 [`crate::Command::validate`] 를 실행합니다. 실패하면 [`BusError::Validation`] 입니다.
+pub struct Validation;
+
+impl CommandMiddleware for Validation {
+    fn handle<'a>(&'a self, next: Next<'a>) -> BoxFuture<'a, Result<Value, BusError>> {
+        Box::pin(async move {
+            next.command().validate().map_err(BusError::Validation)?;
+            next.run().await
+        })
+    }
+}
+
+/// 멱등성 키 선점 결과
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdempotencyClaim {
+    /// 처음 보는 키. 커맨드를 실행한 뒤 `complete` 또는 `release` 해야 합니다.
+    Acquired,
+    /// 같은 키의 커맨드가 아직 실행 중
+    InProgress,
+    /// 이미 실행된 키의 결과
+    Completed(Value),
+}
+
+/// 멱등성 키별 커맨드 결과 저장소
+///
+/// 같은 키를 동시에 선점해도 한 커맨드만 `Acquired` 를 받도록 `claim` 은 원자적이어야 합니다. 만료된 키는 없는 것으로 취급합니다.
+pub trait IdempotencyStore: Send + Sync + 'static {
+    /// 키를 선점합니다. 선점은 `lock_timeout` 이 지나면 풀려, 실행 중 죽은 커맨드의 키도 다시 쓸 수 있습니다.
+    fn claim<'a>(&'a self, key: &'a str, lock_timeout: Duration) -> BoxFuture<'a, Result<IdempotencyClaim, BusError>>;
+
+    /// 결과를 저장하고 `ttl` 동안 같은 키의 커맨드에 돌려줍니다.
+    fn complete<'a>(&'a self, key: &'a str, result: Value, ttl: Duration) -> BoxFuture<'a, Result<(), BusError>>;
+
+    /// 선점을 풀어 같은 키로 다시 시도할 수 있게 합니다.
+    fn release<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<(), BusError>>;
+}
+
+struct Entry {
+    result: Option<Value>,
+    expires_at: Instant,
+}
+
+/// 프로세스 메모리에 결과를 보관하는 저장소. 인스턴스가 여러 개면 공유 저장소를 사용해야 합니다.
+#[derive(Clone, Default)]
+pub struct MemoryIdempotencyStore {
+    entries: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+impl MemoryIdempotencyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<String, Entry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl IdempotencyStore for MemoryIdempotencyStore {
+    fn claim<'a>(&'a self, key: &'a str, lock_timeout: Duration) -> BoxFuture<'a, Result<IdempotencyClaim, BusError>> {
+        let mut entries = self.entries();
+        let now = Instant::now();
+
+        entries.retain(|_, entry| entry.expires_at > now);
+        let claim = match entries.get(key) {
+            Some(Entry { result: Some(result), .. }) => IdempotencyClaim::Completed(result.clone()),
+            Some(Entry { result: None, .. }) => IdempotencyClaim::InProgress,
+            None => {
+                entries.insert(key.to_string(), Entry { result: None, expires_at: now + lock_timeout });
+                IdempotencyClaim::Acquired
+            }
+        };
+
+        Box::pin(async move { Ok(claim) })
+    }
+
+    fn complete<'a>(&'a self, key: &'a str, result: Value, ttl: Duration) -> BoxFuture<'a, Result<(), BusError>> {
+        self.entries().insert(key.to_string(), Entry { result: Some(result), expires_at: Instant::now() + ttl });
+
+        Box::pin(async { Ok(()) })
+    }
+
+    fn release<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<(), BusError>> {
+        let mut entries = self.entries();
+        if entries.get(key).is_some_and(|entry| entry.result.is_none()) {
+            entries.remove(key);
+        }
+
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// [`crate::Command::idempotency_key`] 가 있는 커맨드는 실행 전에 키를 선점하고 성공한 결과를 저장해 두어,
+/// 같은 키로 다시 들어오면 핸들러를 실행하지 않고 저장된 결과를 돌려줍니다.
+///
+/// - 키는 커맨드 이름과 호출한 사용자([`RequestContext`] 의 `principal`)별로 구분됩니다.
+/// - 같은 키의 커맨드가 실행 중이면 [`CommandInProgress`] 로 거절합니다.
+/// - 실패한 커맨드는 선점을 풀어 저장하지 않으므로 다시 시도할 수 있습니다.
+pub struct Idempotency<S = MemoryIdempotencyStore> {
+    store: S,
+    ttl: Duration,
+    lock_timeout: Duration,
+}
+
+impl<S: IdempotencyStore> Idempotency<S> {
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            ttl: Duration::from_secs(24 * 60 * 60),
+            lock_timeout: Duration::from_secs(30),
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// 실행 중인 커맨드가 키를 쥐고 있는 최대 시간. 이보다 오래 걸리는 커맨드는 같은 키로 다시 실행될 수 있습니다.
+    pub fn with_lock_timeout(mut self, lock_timeout: Duration) -> Self {
+        self.lock_timeout = lock_timeout;
+        self
+    }
+}
+
+impl<S: IdempotencyStore> CommandMiddleware for Idempotency<S> {
+    fn handle<'a>(&'a self, next: Next<'a>) -> BoxFuture<'a, Result<Value, BusError>> {
+        Box::pin(async move {
+            let Some(key) = next.command().idempotency_key() else {
+                return next.run().await;
+            };
+            let name = next.command().name();
+            let owner = RequestContext::current()
+                .and_then(|context| context.principal.as_ref().map(|principal| principal.id.clone()))
+                .unwrap_or_else(|| "anonymous".to_string());
+            let key = format!("{}:{}:{}", name, owner, key);
+
+            match self.store.claim(&key, self.lock_timeout).await? {
+                IdempotencyClaim::Acquired => {}
+                IdempotencyClaim::InProgress => return Err(BusError::handler(CommandInProgress(name))),
+                IdempotencyClaim::Completed(result) => return Ok(result),
+            }
+
+            match next.run().await {
+                Ok(result) => {
+                    // 커맨드는 이미 반영됐으므로 저장에 실패해도 결과는 돌려준다.
+                    if let Err(e) = self.store.complete(&key, result.clone(), self.ttl).await {
+                        eprintln!("[command] failed to store result of `{}`: {}", name, e);
+                    }
+                    Ok(result)
+                }
+                Err(e) => {
+                    if let Err(release) = self.store.release(&key).await {
+                        eprintln!("[command] failed to release idempotency key of `{}`: {}", name, release);
+                    }
+                    Err(e)
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use core_container::Container;
+    use core_filter::ValidationError;
+    use crate::command::{Command, CommandBus, CommandHandler, CommandPipeline};
+
+    struct Charge {
+        key: &'static str,
+        amount: u32,
+    }
+
+    struct ChargeHandler(AtomicUsize);
+
+    impl Command for Charge {
+        type Result = usize;
+        type Handler = ChargeHandler;
+
+        fn validate(&self) -> Result<(), ValidationError> {
+            let mut error = ValidationError::new();
+
+            if self.amount == 0 {
+                error = error.with_field("amount", "must be positive");
+            }
+
+            error.into_result()
+        }
+
+        fn idempotency_key(&self) -> Option<String> {
+            Some(self.key.to_string())
+        }
+    }
+
+    impl CommandHandler<Charge> for ChargeHandler {
+        type Error = ValidationError;
+
+        async fn handle(&self, charge: Charge) -> Result<usize, ValidationError> {
+            // 금액만큼 걸리는 결제
+            ntex::time::sleep(Duration::from_millis(charge.amount as u64)).await;
+            Ok(self.0.fetch_add(1, Ordering::SeqCst))
+        }
+    }
+
+    async fn bus(pipeline: CommandPipeline) -> CommandBus {
+        let container = Container::builder()
+            .singleton(|_| async { Ok(ChargeHandler(AtomicUsize::new(0))) })
+            .instance(pipeline)
+            .build();
+
+        CommandBus::from_scope(container.scope()).await.unwrap()
+    }
+
+    #[ntex::test]
+    async fn test_validation_rejects_before_handler() {
+        let commands = bus(CommandPipeline::new().with(Validation)).await;
+
+        assert!(matches!(
+            commands.send(Charge { key: "a", amount: 0 }).await,
+            Err(BusError::Validation(_))
+        ));
+        assert_eq!(commands.send(Charge { key: "a", amount: 1 }).await.unwrap(), 0);
+    }
+
+    #[ntex::test]
+    async fn test_idempotency_replays_result_until_expired() {
+        let commands = bus(
+            CommandPipeline::new()
+                .with(Idempotency::new(MemoryIdempotencyStore::new()).with_ttl(Duration::from_millis(50))),
+        )
+        .await;
+
+        assert_eq!(commands.send(Charge { key: "a", amount: 1 }).await.unwrap(), 0);
+        assert_eq!(commands.send(Charge { key: "a", amount: 1 }).await.unwrap(), 0);
+        assert_eq!(commands.send(Charge { key: "b", amount: 1 }).await.unwrap(), 1);
+
+        ntex::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(commands.send(Charge { key: "a", amount: 1 }).await.unwrap(), 2);
+    }
+
+    #[ntex::test]
+    async fn test_idempotency_claims_key_before_running() {
+        let commands = bus(CommandPipeline::new().with(Idempotency::new(MemoryIdempotencyStore::new()))).await;
+
+        let (first, second) = futures::join!(
+            commands.send(Charge { key: "a", amount: 20 }),
+            commands.send(Charge { key: "a", amount: 20 }),
+        );
+
+        assert_eq!(first.unwrap(), 0);
+        let Err(BusError::Handler(e)) = second else {
+            panic!("expected the concurrent command to be rejected");
+        };
+        assert!(e.downcast_ref::<CommandInProgress>().is_some());
+    }
+
+    #[ntex::test]
+    async fn test_idempotency_key_is_scoped_to_principal() {
+        let commands = bus(CommandPipeline::new().with(Idempotency::new(MemoryIdempotencyStore::new()))).await;
+        let caller = |id: &str| RequestContext {
+            principal: Some(kit_context::Principal { id: id.to_string(), roles: Vec::new() }),
+            ..Default::default()
+        };
+
+        let alice = caller("alice").scope(commands.send(Charge { key: "a", amount: 1 })).await;
+        let bob = caller("bob").scope(commands.send(Charge { key: "a", amount: 1 })).await;
+        let alice_again = caller("alice").scope(commands.send(Charge { key: "a", amount: 1 })).await;
+
+        assert_eq!(alice.unwrap(), 0);
+        assert_eq!(bob.unwrap(), 1);
+        assert_eq!(alice_again.unwrap(), 0);
+    }
+}
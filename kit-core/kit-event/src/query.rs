@@ -0,0 +1,127 @@
+use std::error::Error as StdError;
+use std::future::Future;
+use core_container::Scope;
+use ntex::http::Payload;
+use ntex::web::{FromRequest, HttpRequest};
+use crate::error::BusError;
+
+/// 상태를 바꾸지 않는 조회 요청
+///
+/// 처리할 핸들러 타입을 함께 선언하며, [`QueryBus`] 는 그 핸들러를 컨테이너에서 꺼내 실행합니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// pub struct FindUser { pub id: i32 }
+///
+/// impl Query for FindUser {
+///     type Output = UserView;
+///     type Handler = UserQueryHandler;
+/// }
+/// ```
+pub trait Query: Sized + Send + 'static {
+    type Output: Send + 'static;
+    type Handler: QueryHandler<Self>;
+}
+
+pub trait QueryHandler<Q: Query>: Send + Sync + 'static {
+    type Error: Into<Box<dyn StdError + Send + Sync + 'static>> + Send;
+
+    fn handle(&self, query: Q) -> impl Future<Output = Result<Q::Output, Self::Error>> + Send;
+}
+
+/// 조회 요청을 등록된 핸들러로 보냅니다.
+///
+/// 요청 스코프에서 핸들러를 꺼내므로 라우트 핸들러는 조회 타입만 알면 됩니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// async fn get_user(path: Path<i32>, queries: QueryBus) -> Result<Reply<UserView>, Exception> {
+///     let user = queries.ask(FindUser { id: path.into_inner() }).await?;
+///     Ok(Reply::ok(user))
+/// }
+/// ```
+#[derive(Clone)]
+pub struct QueryBus {
+    scope: Scope,
+}
+
+impl QueryBus {
+    pub fn new(scope: Scope) -> Self {
+        Self { scope }
+    }
+
+    pub async fn ask<Q: Query>(&self, query: Q) -> Result<Q::Output, BusError> {
+        let handler = self.scope.get::<Q::Handler>().await?;
+
+        handler.handle(query).await.map_err(BusError::handler)
+    }
+}
+
+impl<Err> FromRequest<Err> for QueryBus {
+    type Error = BusError;
+
+    async fn from_request(req: &HttpRequest, payload: &mut Payload) -> Result<Self, Self::Error> {
+        let scope = <Scope as FromRequest<Err>>::from_request(req, payload).await?;
+        Ok(QueryBus::new(scope))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt;
+    use core_container::{Container, ContainerError};
+
+    struct Double(u32);
+
+    #[derive(Debug)]
+    struct Overflow;
+
+    impl fmt::Display for Overflow {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("overflow")
+        }
+    }
+
+    impl StdError for Overflow {}
+
+    struct Calculator;
+
+    impl Query for Double {
+        type Output = u32;
+        type Handler = Calculator;
+    }
+
+    impl QueryHandler<Double> for Calculator {
+        type Error = Overflow;
+
+        async fn handle(&self, query: Double) -> Result<u32, Overflow> {
+            query.0.checked_mul(2).ok_or(Overflow)
+        }
+    }
+
+    #[ntex::test]
+    async fn test_ask_resolves_handler_from_scope() {
+        let container = Container::builder()
+            .singleton(|_| async { Ok(Calculator) })
+            .build();
+        let queries = QueryBus::new(container.scope());
+
+        assert_eq!(queries.ask(Double(21)).await.unwrap(), 42);
+
+        let error = queries.ask(Double(u32::MAX)).await.unwrap_err();
+        assert!(error.source().unwrap().downcast_ref::<Overflow>().is_some());
+    }
+
+    #[ntex::test]
+    async fn test_ask_without_handler() {
+        let queries = QueryBus::new(Container::builder().build().scope());
+
+        assert!(matches!(
+            queries.ask(Double(1)).await,
+            Err(BusError::Container(ContainerError::NotRegistered(_)))
+        ));
+    }
+}
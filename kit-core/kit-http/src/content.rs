@@ -0,0 +1,181 @@
+use std::ops::Deref;
+use ntex::http::header::{ACCEPT, CONTENT_TYPE};
+use ntex::http::{HeaderMap, Payload};
+use ntex::util::Bytes;
+use ntex::web::{ErrorRenderer, FromRequest, HttpRequest};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use crate::error::ContentError;
+
+const JSON: &str = "application/json";
+const MESSAGE_PACK: &str = "application/msgpack";
+const MESSAGE_PACK_ALIASES: [&str; 3] = [MESSAGE_PACK, "application/x-msgpack", "application/vnd.msgpack"];
+
+/// 요청·응답 본문의 직렬화 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Json,
+    MessagePack,
+}
+
+impl Format {
+    fn from_media_type(media_type: &str) -> Option<Self> {
+        let media_type = media_type.trim().to_ascii_lowercase();
+
+        if media_type == JSON || media_type.ends_with("+json") {
+            Some(Format::Json)
+        } else if MESSAGE_PACK_ALIASES.contains(&media_type.as_str()) {
+            Some(Format::MessagePack)
+        } else {
+            None
+        }
+    }
+
+    /// `Accept` 헤더에서 응답 형식을 고릅니다.
+    ///
+    /// q 값이 높은 순서로 지원하는 형식을 찾고, 헤더가 없거나 `*/*` 뿐이면 JSON 을 사용합니다.
+    pub fn from_accept(headers: &HeaderMap) -> Self {
+        let Some(accept) = headers.get(ACCEPT).and_then(|value| value.to_str().ok()) else {
+            return Format::Json;
+        };
+
+        let mut candidates: Vec<(Option<Format>, f32)> = accept
+            .split(',')
+            .map(|range| {
+                let mut parts = range.split(';');
+                let media_type = parts.next().unwrap_or_default();
+                let quality = parts
+                    .filter_map(|parameter| parameter.trim().strip_prefix("q="))
+                    .find_map(|quality| quality.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+
+                (Self::from_media_type(media_type), quality)
+            })
+            .filter(|(_, quality)| *quality > 0.0)
+            .collect();
+
+        // 같은 q 값이면 먼저 적힌 형식을 사용한다. (sort_by 는 안정 정렬)
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+        candidates
+            .into_iter()
+            .find_map(|(format, _)| format)
+            .unwrap_or_default()
+    }
+
+    /// `Content-Type` 헤더의 형식. 헤더가 없으면 JSON 으로 간주합니다.
+    pub fn from_content_type(headers: &HeaderMap) -> Result<Self, ContentError> {
+        let Some(content_type) = headers.get(CONTENT_TYPE) else {
+            return Ok(Format::Json);
+        };
+
+        let content_type = content_type.to_str().unwrap_or_default();
+        let media_type = content_type.split(';').next().unwrap_or_default();
+
+        Self::from_media_type(media_type)
+            .ok_or_else(|| ContentError::UnsupportedMediaType(content_type.to_string()))
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Format::Json => JSON,
+            Format::MessagePack => MESSAGE_PACK,
+        }
+    }
+
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Bytes, ContentError> {
+        let encoded = match self {
+            Format::Json => serde_json::to_vec(value).map_err(|e| ContentError::Encode(e.to_string()))?,
+            // 필드 이름을 유지해야 JSON 과 같은 구조로 읽을 수 있다.
+            Format::MessagePack => rmp_serde::to_vec_named(value).map_err(|e| ContentError::Encode(e.to_string()))?,
+        };
+
+        Ok(Bytes::from(encoded))
+    }
+
+    pub fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, ContentError> {
+        match self {
+            Format::Json => serde_json::from_slice(bytes).map_err(|e| ContentError::Decode(e.to_string())),
+            Format::MessagePack => rmp_serde::from_slice(bytes).map_err(|e| ContentError::Decode(e.to_string())),
+        }
+    }
+}
+
+/// `Content-Type` 에 따라 JSON 또는 MessagePack 본문을 읽는 추출기
+///
+/// 본문 크기 제한은 ntex 의 `PayloadConfig` 를 따릅니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// #[post("/user")]
+/// async fn create_user(command: Content<UserRegisterCommand>) -> impl Responder { ... }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Content<T>(pub T);
+
+impl<T> Content<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Content<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T, Err> FromRequest<Err> for Content<T>
+where
+    T: DeserializeOwned,
+    Err: ErrorRenderer,
+{
+    type Error = ContentError;
+
+    async fn from_request(req: &HttpRequest, payload: &mut Payload) -> Result<Self, Self::Error> {
+        let format = Format::from_content_type(req.headers())?;
+        let bytes = <Bytes as FromRequest<Err>>::from_request(req, payload).await?;
+
+        format.decode(&bytes).map(Content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::http::header::HeaderValue;
+
+    fn accept(value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn test_accept_negotiation() {
+        assert_eq!(Format::from_accept(&HeaderMap::new()), Format::Json);
+        assert_eq!(Format::from_accept(&accept("*/*")), Format::Json);
+        assert_eq!(Format::from_accept(&accept("application/x-msgpack")), Format::MessagePack);
+        assert_eq!(
+            Format::from_accept(&accept("application/json;q=0.5, application/msgpack")),
+            Format::MessagePack
+        );
+        assert_eq!(
+            Format::from_accept(&accept("text/html, application/msgpack;q=0")),
+            Format::Json
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let value = serde_json::json!({ "id": 1, "name": "kim" });
+
+        for format in [Format::Json, Format::MessagePack] {
+            let bytes = format.encode(&value).unwrap();
+            assert_eq!(format.decode::<serde_json::Value>(&bytes).unwrap(), value);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// 모든 API 응답 본문의 공통 형태
+///
+/// ```json
+/// { "data": { "id": 1 }, "meta": { "pagination": { ... } }, "errors": [] }
+/// ```
+///
+/// `meta` 와 `errors` 는 비어 있으면 생략됩니다.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(rename = "{T}Envelope", description = "모든 API 응답 본문의 공통 형태")]
+pub struct Envelope<T> {
+    pub data: Option<T>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub meta: Map<String, Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<ApiError>,
+}
+
+impl<T> Envelope<T> {
+    pub fn new(data: T) -> Self {
+        Self {
+            data: Some(data),
+            meta: Map::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// 데이터 없이 에러만 담습니다.
+    pub fn from_errors(errors: Vec<ApiError>) -> Self {
+        Self {
+            data: None,
+            meta: Map::new(),
+            errors,
+        }
+    }
+
+    /// 메타 정보를 추가합니다. 직렬화할 수 없는 값은 `null` 로 기록됩니다.
+    pub fn with_meta(mut self, key: impl Into<String>, value: impl Serialize) -> Self {
+        self.meta
+            .insert(key.into(), serde_json::to_value(value).unwrap_or(Value::Null));
+        self
+    }
+
+    /// 일부만 성공한 배치 처리처럼 데이터와 함께 전달할 에러를 추가합니다.
+    pub fn with_error(mut self, error: ApiError) -> Self {
+        self.errors.push(error);
+        self
+    }
+}
+
+/// [`Envelope::errors`] 의 항목
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(description = "데이터와 함께 전달되는 에러 항목")]
+pub struct ApiError {
+    pub code: String,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+}
+
+impl ApiError {
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+            field: None,
+        }
+    }
+
+    pub fn with_field(mut self, field: impl Into<String>) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+}
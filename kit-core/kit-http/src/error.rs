@@ -0,0 +1,101 @@
+use ntex::http::StatusCode;
+use ntex::http::error::PayloadError as HttpPayloadError;
+use ntex::web::error::PayloadError;
+use ntex::web::{DefaultError, WebResponseError};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ContentError {
+    #[error("unsupported content type `{0}`")]
+    UnsupportedMediaType(String),
+
+    #[error("failed to read request body: {0}")]
+    Payload(#[from] PayloadError),
+
+    #[error("failed to decode request body: {0}")]
+    Decode(String),
+
+    #[error("failed to encode response body: {0}")]
+    Encode(String),
+}
+
+impl WebResponseError<DefaultError> for ContentError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ContentError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ContentError::Payload(PayloadError::Payload(HttpPayloadError::Overflow)) => StatusCode::PAYLOAD_TOO_LARGE,
+            ContentError::Payload(_) | ContentError::Decode(_) => StatusCode::BAD_REQUEST,
+            ContentError::Encode(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PaginationError {
+    #[error("invalid pagination parameters: {0}")]
+    Query(#[from] serde_urlencoded::de::Error),
+
+    #[error("`{0}` must be greater than 0")]
+    NotPositive(&'static str),
+}
+
+impl WebResponseError<DefaultError> for PaginationError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum IdempotencyError {
+    #[error("`Idempotency-Key` must be 1 to 255 visible ASCII characters")]
+    InvalidKey,
+
+    #[error("`Idempotency-Key` was already used with a different request")]
+    Mismatch,
+
+    #[error("a request with this `Idempotency-Key` is still being processed")]
+    InProgress,
+
+    #[error("failed to read request body: {0}")]
+    Payload(String),
+
+    #[error("request body is too large for an idempotent request")]
+    PayloadTooLarge,
+
+    #[error("failed to read response body: {0}")]
+    Body(String),
+
+    #[error("idempotency store is unavailable: {0}")]
+    Store(String),
+}
+
+impl WebResponseError<DefaultError> for IdempotencyError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            IdempotencyError::InvalidKey | IdempotencyError::Payload(_) => StatusCode::BAD_REQUEST,
+            IdempotencyError::Mismatch => StatusCode::UNPROCESSABLE_ENTITY,
+            IdempotencyError::InProgress => StatusCode::CONFLICT,
+            IdempotencyError::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            IdempotencyError::Body(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            IdempotencyError::Store(_) => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RateLimitError {
+    #[error("too many requests, retry after {0} seconds")]
+    Exceeded(u64),
+
+    #[error("rate limit `{0}` must be greater than 0")]
+    InvalidQuota(&'static str),
+}
+
+impl WebResponseError<DefaultError> for RateLimitError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            RateLimitError::Exceeded(_) => StatusCode::TOO_MANY_REQUESTS,
+            RateLimitError::InvalidQuota(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
@@ -0,0 +1,428 @@
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::future::{poll_fn, ready};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use futures::future::BoxFuture;
+use futures::stream;
+use kit_context::RequestContext;
+use ntex::http::body::{Body, MessageBody, ResponseBody};
+use ntex::http::header::{CONNECTION, CONTENT_LENGTH, DATE, HeaderName, HeaderValue, TRANSFER_ENCODING};
+use ntex::http::{Method, Payload, StatusCode};
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::util::{Bytes, BytesMut};
+use ntex::web::{self, DefaultError, HttpResponse, WebRequest, WebResponse, WebResponseError};
+use sha2::{Digest, Sha256};
+use crate::error::IdempotencyError;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+const MAX_KEY_LENGTH: usize = 255;
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+// 본문 길이와 연결에 따라 다시 정해지는 헤더
+const UNSTORED_HEADERS: [HeaderName; 4] = [CONTENT_LENGTH, TRANSFER_ENCODING, CONNECTION, DATE];
+
+pub type StoreError = Box<dyn StdError + Send + Sync + 'static>;
+
+/// 저장된 응답
+///
+/// 재전송이 첫 응답과 같도록 `Location`, `Link` 등의 헤더도 함께 저장합니다. 연결마다 달라지는 헤더는 뺍니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Bytes,
+}
+
+/// [`ResponseStore::claim`] 결과
+#[derive(Debug, Clone, PartialEq)]
+pub enum Claim {
+    /// 처음 보는 키. 요청을 처리한 뒤 `complete` 또는 `release` 해야 합니다.
+    Acquired,
+    /// 같은 키의 요청이 아직 처리 중
+    InProgress { fingerprint: String },
+    /// 이미 처리된 키
+    Completed { fingerprint: String, response: StoredResponse },
+}
+
+/// 멱등성 키별 요청 지문과 응답 저장소
+///
+/// 만료된 키는 없는 것으로 취급해야 합니다.
+pub trait ResponseStore: Send + Sync + 'static {
+    /// 키를 선점합니다. 선점은 `lock_timeout` 이 지나면 풀려, 처리 중 죽은 요청의 키도 다시 쓸 수 있습니다.
+    fn claim(&self, key: &str, fingerprint: &str, lock_timeout: Duration) -> BoxFuture<'_, Result<Claim, StoreError>>;
+
+    /// 처리 결과를 저장하고 `ttl` 동안 재전송에 돌려줍니다.
+    fn complete(&self, key: &str, response: StoredResponse, ttl: Duration) -> BoxFuture<'_, Result<(), StoreError>>;
+
+    /// 선점을 풀어 같은 키로 다시 시도할 수 있게 합니다.
+    fn release(&self, key: &str) -> BoxFuture<'_, Result<(), StoreError>>;
+}
+
+struct Entry {
+    fingerprint: String,
+    response: Option<StoredResponse>,
+    expires_at: Instant,
+}
+
+/// 프로세스 메모리 저장소. 테스트나 단일 인스턴스용입니다.
+#[derive(Clone, Default)]
+pub struct MemoryResponseStore {
+    entries: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+impl MemoryResponseStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ResponseStore for MemoryResponseStore {
+    fn claim(&self, key: &str, fingerprint: &str, lock_timeout: Duration) -> BoxFuture<'_, Result<Claim, StoreError>> {
+        let mut entries = self.entries.lock().unwrap();
+        let now = Instant::now();
+        entries.retain(|_, entry| entry.expires_at > now);
+
+        let claim = match entries.get(key) {
+            Some(Entry { fingerprint, response: Some(response), .. }) => Claim::Completed {
+                fingerprint: fingerprint.clone(),
+                response: response.clone(),
+            },
+            Some(Entry { fingerprint, .. }) => Claim::InProgress {
+                fingerprint: fingerprint.clone(),
+            },
+            None => {
+                entries.insert(key.to_string(), Entry {
+                    fingerprint: fingerprint.to_string(),
+                    response: None,
+                    expires_at: now + lock_timeout,
+                });
+                Claim::Acquired
+            }
+        };
+
+        Box::pin(ready(Ok(claim)))
+    }
+
+    fn complete(&self, key: &str, response: StoredResponse, ttl: Duration) -> BoxFuture<'_, Result<(), StoreError>> {
+        if let Some(entry) = self.entries.lock().unwrap().get_mut(key) {
+            entry.response = Some(response);
+            entry.expires_at = Instant::now() + ttl;
+        }
+
+        Box::pin(ready(Ok(())))
+    }
+
+    fn release(&self, key: &str) -> BoxFuture<'_, Result<(), StoreError>> {
+        self.entries.lock().unwrap().remove(key);
+
+        Box::pin(ready(Ok(())))
+    }
+}
+
+/// `Idempotency-Key` 헤더가 있는 `POST` 요청을 한 번만 처리하는 미들웨어
+///
+/// - 같은 키와 같은 요청(메서드, 경로, 본문)이 다시 오면 저장된 응답을 돌려주고 `Idempotent-Replayed: true` 를 붙입니다.
+/// - 같은 키로 다른 요청이 오면 `422`, 첫 요청이 아직 처리 중이면 `409` 로 응답합니다.
+/// - `5xx` 응답은 저장하지 않으므로 같은 키로 다시 시도할 수 있습니다.
+///
+/// 키는 사용자별로 구분되므로 [`kit_context::Context`] 안쪽에 둡니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// App::new()
+///     .wrap(IdempotencyKey::new(PgResponseStore::new(pool.clone())))
+///     .wrap(Context::new())
+/// ```
+pub struct IdempotencyKey<St> {
+    store: Arc<St>,
+    ttl: Duration,
+    lock_timeout: Duration,
+}
+
+impl<St: ResponseStore> IdempotencyKey<St> {
+    pub fn new(store: St) -> Self {
+        Self {
+            store: Arc::new(store),
+            ttl: Duration::from_secs(24 * 60 * 60),
+            lock_timeout: Duration::from_secs(60),
+        }
+    }
+
+    /// 처리된 응답을 보관하는 기간 (기본 24시간)
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// 처리 중인 키의 선점 유지 시간 (기본 60초). 요청 타임아웃보다 길어야 합니다.
+    pub fn with_lock_timeout(mut self, lock_timeout: Duration) -> Self {
+        self.lock_timeout = lock_timeout;
+        self
+    }
+}
+
+impl<St, S> Middleware<S> for IdempotencyKey<St> {
+    type Service = IdempotencyKeyMiddleware<St, S>;
+
+    fn create(&self, service: S) -> Self::Service {
+        IdempotencyKeyMiddleware {
+            service,
+            store: self.store.clone(),
+            ttl: self.ttl,
+            lock_timeout: self.lock_timeout,
+        }
+    }
+}
+
+pub struct IdempotencyKeyMiddleware<St, S> {
+    service: S,
+    store: Arc<St>,
+    ttl: Duration,
+    lock_timeout: Duration,
+}
+
+impl<St, S> Service<WebRequest<DefaultError>> for IdempotencyKeyMiddleware<St, S>
+where
+    St: ResponseStore,
+    S: Service<WebRequest<DefaultError>, Response = WebResponse, Error = web::Error>,
+{
+    type Response = WebResponse;
+    type Error = web::Error;
+
+    ntex::forward_ready!(service);
+
+    async fn call(
+        &self,
+        mut req: WebRequest<DefaultError>,
+        ctx: ServiceCtx<'_, Self>,
+    ) -> Result<Self::Response, Self::Error> {
+        if req.method() != Method::POST {
+            return ctx.call(&self.service, req).await;
+        }
+
+        let key = match req.headers().get(IDEMPOTENCY_KEY_HEADER).map(|value| value.to_str()) {
+            None => return ctx.call(&self.service, req).await,
+            Some(Ok(key)) if !key.trim().is_empty() && key.len() <= MAX_KEY_LENGTH => key.trim().to_string(),
+            Some(_) => return Ok(req.render_error(IdempotencyError::InvalidKey)),
+        };
+        let owner = req
+            .extensions()
+            .get::<Arc<RequestContext>>()
+            .and_then(|context| context.principal.as_ref().map(|principal| principal.id.clone()))
+            .unwrap_or_else(|| "anonymous".to_string());
+        let key = format!("{}:{}", owner, key);
+
+        let body = match read_payload(&mut req).await {
+            Ok(body) => body,
+            Err(e) => return Ok(req.render_error(e)),
+        };
+        let fingerprint = fingerprint(req.method(), &req.uri().to_string(), &body);
+        req.set_payload(Payload::from_stream(stream::once(ready(Ok(body)))));
+
+        match self.store.claim(&key, &fingerprint, self.lock_timeout).await {
+            Err(e) => Ok(req.render_error(IdempotencyError::Store(e.to_string()))),
+            Ok(Claim::InProgress { fingerprint: stored } | Claim::Completed { fingerprint: stored, .. })
+                if stored != fingerprint =>
+            {
+                Ok(req.render_error(IdempotencyError::Mismatch))
+            }
+            Ok(Claim::InProgress { .. }) => Ok(req.render_error(IdempotencyError::InProgress)),
+            Ok(Claim::Completed { response, .. }) => Ok(req.into_response(replay(response))),
+            Ok(Claim::Acquired) => {
+                let result = ctx.call(&self.service, req).await;
+
+                match result {
+                    Ok(response) if !response.status().is_server_error() => Ok(self.complete(&key, response).await),
+                    other => {
+                        if let Err(e) = self.store.release(&key).await {
+                            eprintln!("[idempotency] failed to release key: {}", e);
+                        }
+                        other
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<St: ResponseStore, S> IdempotencyKeyMiddleware<St, S> {
+    // 응답 본문을 모아 저장한 뒤 같은 본문으로 응답한다.
+    async fn complete(&self, key: &str, mut response: WebResponse) -> WebResponse {
+        let body = match read_body(response.take_body()).await {
+            Ok(body) => body,
+            Err(e) => {
+                if let Err(e) = self.store.release(key).await {
+                    eprintln!("[idempotency] failed to release key: {}", e);
+                }
+                let req = response.request().clone();
+                let error = IdempotencyError::Body(e.to_string());
+                return WebResponse::new(error.error_response(&req), req);
+            }
+        };
+
+        let stored = StoredResponse {
+            status: response.status().as_u16(),
+            headers: response
+                .headers()
+                .iter()
+                .filter(|(name, _)| !UNSTORED_HEADERS.contains(name))
+                .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+                .collect(),
+            body: body.clone(),
+        };
+
+        if let Err(e) = self.store.complete(key, stored, self.ttl).await {
+            eprintln!("[idempotency] failed to store response: {}", e);
+        }
+
+        response.map_body(|_, _| ResponseBody::Body(Body::Bytes(body)))
+    }
+}
+
+async fn read_payload(req: &mut WebRequest<DefaultError>) -> Result<Bytes, IdempotencyError> {
+    let mut payload = req.take_payload();
+    let mut body = BytesMut::new();
+
+    while let Some(chunk) = payload.recv().await {
+        let chunk = chunk.map_err(|e| IdempotencyError::Payload(e.to_string()))?;
+
+        if body.len() + chunk.len() > MAX_BODY_SIZE {
+            return Err(IdempotencyError::PayloadTooLarge);
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body.freeze())
+}
+
+async fn read_body(mut body: ResponseBody<Body>) -> Result<Bytes, Box<dyn StdError>> {
+    let mut bytes = BytesMut::new();
+
+    while let Some(chunk) = poll_fn(|cx| body.poll_next_chunk(cx)).await {
+        bytes.extend_from_slice(&chunk?);
+    }
+
+    Ok(bytes.freeze())
+}
+
+fn fingerprint(method: &Method, uri: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str());
+    hasher.update(b"\n");
+    hasher.update(uri);
+    hasher.update(b"\n");
+    hasher.update(body);
+
+    hex::encode(hasher.finalize())
+}
+
+fn replay(stored: StoredResponse) -> HttpResponse {
+    let status = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let mut response = HttpResponse::build(status);
+
+    for (name, value) in &stored.headers {
+        response.header(name.as_str(), value.as_str());
+    }
+
+    response
+        .header(HeaderName::from_static(IDEMPOTENT_REPLAYED_HEADER), HeaderValue::from_static("true"))
+        .body(stored.body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use ntex::web::{App, test};
+
+    async fn app(
+        calls: Arc<AtomicUsize>,
+        status: StatusCode,
+    ) -> ntex::service::Pipeline<impl Service<ntex::http::Request, Response = WebResponse, Error = web::Error>> {
+        test::init_service(
+            App::new()
+                .wrap(IdempotencyKey::new(MemoryResponseStore::new()))
+                .route(
+                    "/user",
+                    web::post().to(move |body: Bytes| {
+                        let id = calls.fetch_add(1, Ordering::SeqCst);
+                        async move {
+                            HttpResponse::build(status)
+                                .header("location", format!("/user/{}", id))
+                                .body(format!("{}:{}", id, body.len()))
+                        }
+                    }),
+                ),
+        )
+        .await
+    }
+
+    fn post(key: &str, body: &'static str) -> ntex::http::Request {
+        test::TestRequest::post()
+            .uri("/user")
+            .header(IDEMPOTENCY_KEY_HEADER, key)
+            .set_payload(body)
+            .to_request()
+    }
+
+    #[ntex::test]
+    async fn test_retry_replays_stored_response() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone(), StatusCode::CREATED).await;
+
+        let first = test::call_service(&app, post("a", "jeff")).await;
+        assert_eq!(first.status(), StatusCode::CREATED);
+        assert_eq!(test::read_body(first).await, "0:4");
+
+        let retry = test::call_service(&app, post("a", "jeff")).await;
+        assert_eq!(retry.status(), StatusCode::CREATED);
+        assert_eq!(retry.headers().get(IDEMPOTENT_REPLAYED_HEADER).unwrap(), "true");
+        assert_eq!(retry.headers().get("location").unwrap(), "/user/0");
+        assert_eq!(test::read_body(retry).await, "0:4");
+
+        let mismatch = test::call_service(&app, post("a", "other")).await;
+        assert_eq!(mismatch.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[ntex::test]
+    async fn test_server_error_releases_key() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone(), StatusCode::SERVICE_UNAVAILABLE).await;
+
+        test::call_service(&app, post("a", "jeff")).await;
+        let retry = test::call_service(&app, post("a", "jeff")).await;
+
+        assert!(retry.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[ntex::test]
+    async fn test_requests_without_key_pass_through() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone(), StatusCode::CREATED).await;
+        let request = || test::TestRequest::post().uri("/user").set_payload("jeff").to_request();
+
+        test::call_service(&app, request()).await;
+        test::call_service(&app, request()).await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[ntex::test]
+    async fn test_memory_store_in_progress_expires() {
+        let store = MemoryResponseStore::new();
+
+        assert_eq!(store.claim("a", "x", Duration::from_millis(20)).await.unwrap(), Claim::Acquired);
+        assert_eq!(
+            store.claim("a", "x", Duration::from_millis(20)).await.unwrap(),
+            Claim::InProgress { fingerprint: "x".to_string() }
+        );
+
+        ntex::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(store.claim("a", "y", Duration::from_millis(20)).await.unwrap(), Claim::Acquired);
+    }
+}
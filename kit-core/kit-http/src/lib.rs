@@ -1,14 +1,21 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+mod content;
+mod envelope;
+mod error;
+mod idempotency;
+mod pagination;
+mod rate_limit;
+mod reply;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use content::{Content, Format};
+pub use envelope::{ApiError, Envelope};
+pub use error::{ContentError, IdempotencyError, PaginationError, RateLimitError};
+pub use idempotency::{
+    Claim, IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER, IdempotencyKey, IdempotencyKeyMiddleware,
+    MemoryResponseStore, ResponseStore, StoreError, StoredResponse,
+};
+pub use pagination::{CursorPage, CursorRequest, Page, PageRequest, PaginationConfig};
+pub use rate_limit::{
+    CacheRateLimitStore, Decision, KeyBy, MemoryRateLimitStore, Quota, RateLimit, RateLimitMiddleware, RateLimitRule,
+    RateLimitState, RateLimitStore,
+};
+pub use reply::Reply;
@@ -0,0 +1,284 @@
+use ntex::http::header::LINK;
+use ntex::http::{Payload, Response};
+use ntex::web::{ErrorRenderer, FromRequest, HttpRequest, Responder};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use crate::error::PaginationError;
+use crate::reply::Reply;
+
+/// 페이지 크기 설정. 앱 상태로 등록하지 않으면 기본값(20, 최대 100)을 사용합니다.
+#[derive(Debug, Clone, Copy)]
+pub struct PaginationConfig {
+    pub default_size: u32,
+    pub max_size: u32,
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self {
+            default_size: 20,
+            max_size: 100,
+        }
+    }
+}
+
+impl PaginationConfig {
+    fn of(req: &HttpRequest) -> Self {
+        req.app_state::<PaginationConfig>().copied().unwrap_or_default()
+    }
+
+    // 0 은 거부하고, 최대값을 넘으면 최대값으로 맞춘다.
+    fn size(&self, requested: Option<u32>, name: &'static str) -> Result<u32, PaginationError> {
+        match requested {
+            Some(0) => Err(PaginationError::NotPositive(name)),
+            Some(size) => Ok(size.min(self.max_size)),
+            None => Ok(self.default_size),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PageQuery {
+    page: Option<u32>,
+    size: Option<u32>,
+}
+
+/// `?page=2&size=20` 형태의 페이지 번호 기반 요청. 페이지는 1부터 시작합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageRequest {
+    pub page: u32,
+    pub size: u32,
+}
+
+impl PageRequest {
+    /// SQL `OFFSET` 값
+    pub fn offset(&self) -> i64 {
+        (self.page as i64 - 1) * self.size as i64
+    }
+
+    /// SQL `LIMIT` 값
+    pub fn limit(&self) -> i64 {
+        self.size as i64
+    }
+}
+
+impl<Err: ErrorRenderer> FromRequest<Err> for PageRequest {
+    type Error = PaginationError;
+
+    async fn from_request(req: &HttpRequest, _: &mut Payload) -> Result<Self, Self::Error> {
+        let query: PageQuery = serde_urlencoded::from_str(req.query_string())?;
+        let config = PaginationConfig::of(req);
+
+        Ok(PageRequest {
+            page: match query.page {
+                Some(0) => return Err(PaginationError::NotPositive("page")),
+                page => page.unwrap_or(1),
+            },
+            size: config.size(query.size, "size")?,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct CursorQuery {
+    cursor: Option<String>,
+    limit: Option<u32>,
+}
+
+/// `?cursor=...&limit=20` 형태의 커서 기반 요청
+///
+/// 커서 값의 형식은 리포지토리가 정합니다. (마지막 항목의 ID 등)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CursorRequest {
+    pub cursor: Option<String>,
+    pub limit: u32,
+}
+
+impl<Err: ErrorRenderer> FromRequest<Err> for CursorRequest {
+    type Error = PaginationError;
+
+    async fn from_request(req: &HttpRequest, _: &mut Payload) -> Result<Self, Self::Error> {
+        let query: CursorQuery = serde_urlencoded::from_str(req.query_string())?;
+        let config = PaginationConfig::of(req);
+
+        Ok(CursorRequest {
+            cursor: query.cursor.filter(|cursor| !cursor.is_empty()),
+            limit: config.size(query.limit, "limit")?,
+        })
+    }
+}
+
+/// 페이지 번호 기반 응답
+///
+/// `meta.pagination` 에 페이지 정보를 담고, `Link` 헤더로 first/prev/next/last 링크를 제공합니다.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub page: u32,
+    pub size: u32,
+    pub total: u64,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, request: &PageRequest, total: u64) -> Self {
+        Self {
+            items,
+            page: request.page,
+            size: request.size,
+            total,
+        }
+    }
+
+    pub fn total_pages(&self) -> u64 {
+        self.total.div_ceil(self.size.max(1) as u64)
+    }
+}
+
+impl<T: Serialize, Err: ErrorRenderer> Responder<Err> for Page<T> {
+    async fn respond_to(self, req: &HttpRequest) -> Response {
+        let page = self.page as u64;
+        let last = self.total_pages().max(1);
+        let size = self.size.to_string();
+
+        let mut links = vec![(1, "first")];
+        if page > 1 {
+            links.push((page.min(last + 1) - 1, "prev"));
+        }
+        if page < last {
+            links.push((page + 1, "next"));
+        }
+        links.push((last, "last"));
+
+        let links = links
+            .into_iter()
+            .map(|(target, rel)| link(req, &[("page", target.to_string()), ("size", size.clone())], rel))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let pagination = json!({
+            "page": self.page,
+            "size": self.size,
+            "total": self.total,
+            "total_pages": self.total_pages(),
+        });
+
+        let reply = Reply::ok(self.items)
+            .with_meta("pagination", pagination)
+            .with_header(LINK, links);
+
+        Responder::<Err>::respond_to(reply, req).await
+    }
+}
+
+/// 커서 기반 응답. 다음 페이지가 있으면 `Link: <...>; rel="next"` 를 제공합니다.
+#[derive(Debug, Clone)]
+pub struct CursorPage<T> {
+    pub items: Vec<T>,
+    pub limit: u32,
+    pub next_cursor: Option<String>,
+}
+
+impl<T> CursorPage<T> {
+    pub fn new(items: Vec<T>, request: &CursorRequest, next_cursor: Option<String>) -> Self {
+        Self {
+            items,
+            limit: request.limit,
+            next_cursor,
+        }
+    }
+}
+
+impl<T: Serialize, Err: ErrorRenderer> Responder<Err> for CursorPage<T> {
+    async fn respond_to(self, req: &HttpRequest) -> Response {
+        let pagination = json!({
+            "limit": self.limit,
+            "next_cursor": self.next_cursor,
+        });
+
+        let mut reply = Reply::ok(self.items).with_meta("pagination", pagination);
+
+        if let Some(cursor) = &self.next_cursor {
+            let next = link(req, &[("cursor", cursor.clone()), ("limit", self.limit.to_string())], "next");
+            reply = reply.with_header(LINK, next);
+        }
+
+        Responder::<Err>::respond_to(reply, req).await
+    }
+}
+
+// 현재 요청의 경로와 쿼리를 유지한 채 페이지 관련 파라미터만 바꾼 링크를 만든다.
+fn link(req: &HttpRequest, params: &[(&str, String)], rel: &str) -> String {
+    let mut query: Vec<(String, String)> = serde_urlencoded::from_str(req.query_string()).unwrap_or_default();
+    query.retain(|(key, _)| !params.iter().any(|(name, _)| name == key));
+    query.extend(params.iter().map(|(name, value)| (name.to_string(), value.clone())));
+
+    let query = serde_urlencoded::to_string(&query).unwrap_or_default();
+
+    format!("<{}?{}>; rel=\"{}\"", req.path(), query, rel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::http::StatusCode;
+    use ntex::web::{self, App, test};
+    use serde_json::Value;
+
+    #[ntex::test]
+    async fn test_page_links_and_meta() {
+        let app = test::init_service(App::new().route(
+            "/users",
+            web::get().to(|request: PageRequest| async move {
+                let items: Vec<u64> = (0..request.limit() as u64).map(|i| i + request.offset() as u64).collect();
+                Page::new(items, &request, 45)
+            }),
+        ))
+        .await;
+
+        let request = test::TestRequest::get().uri("/users?name=kim&page=2&size=20").to_request();
+        let response = test::call_service(&app, request).await;
+
+        assert_eq!(
+            response.headers().get(LINK).unwrap(),
+            "</users?name=kim&page=1&size=20>; rel=\"first\", \
+             </users?name=kim&page=1&size=20>; rel=\"prev\", \
+             </users?name=kim&page=3&size=20>; rel=\"next\", \
+             </users?name=kim&page=3&size=20>; rel=\"last\""
+        );
+
+        let body: Value = serde_json::from_slice(&test::read_body(response).await).unwrap();
+        assert_eq!(body["data"][0], 20);
+        assert_eq!(body["meta"]["pagination"]["total_pages"], 3);
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/users?page=0").to_request()).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[ntex::test]
+    async fn test_cursor_request_limits() {
+        let app = test::init_service(
+            App::new()
+                .state(PaginationConfig { default_size: 10, max_size: 50 })
+                .route(
+                    "/events",
+                    web::get().to(|request: CursorRequest| async move {
+                        let next = format!("{}-next", request.cursor.clone().unwrap_or_default());
+                        CursorPage::new(vec![request.limit], &request, Some(next))
+                    }),
+                ),
+        )
+        .await;
+
+        let request = test::TestRequest::get().uri("/events?cursor=abc&limit=500").to_request();
+        let response = test::call_service(&app, request).await;
+
+        assert_eq!(
+            response.headers().get(LINK).unwrap(),
+            "</events?cursor=abc-next&limit=50>; rel=\"next\""
+        );
+
+        let body: Value = serde_json::from_slice(&test::read_body(response).await).unwrap();
+        assert_eq!(body["data"], json!([50]));
+        assert_eq!(body["meta"]["pagination"]["next_cursor"], "abc-next");
+    }
+}
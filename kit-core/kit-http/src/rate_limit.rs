@@ -0,0 +1,681 @@
+use std::collections::HashMap;
+use std::future::ready;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use futures::future::BoxFuture;
+use kit_cache::{Cache, Namespace};
+use kit_context::RequestContext;
+use ntex::http::header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER};
+use ntex::http::Method;
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web::{self, DefaultError, WebRequest, WebResponse};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::error::RateLimitError;
+use crate::idempotency::StoreError;
+
+const MEMORY_STORE_PRUNE_THRESHOLD: usize = 10_000;
+const CACHE_STORE_MAX_ATTEMPTS: usize = 8;
+
+/// 허용량과 계산 방식
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quota {
+    /// `capacity` 개까지 한 번에 쓸 수 있고, `period` 동안 `capacity` 개가 고르게 다시 채워집니다.
+    TokenBucket { capacity: u32, period: Duration },
+    /// 직전 `window` 동안 `limit` 개까지 허용합니다. 이전 구간의 개수를 지난 비율만큼 빼서 경계에서 두 배로 몰리지 않게 합니다.
+    SlidingWindow { limit: u32, window: Duration },
+}
+
+/// 키별 계산 상태. 저장소는 이 값을 그대로 보관합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RateLimitState {
+    Bucket { tokens: f64, updated_at: u64 },
+    Window { started_at: u64, current: u32, previous: u32 },
+}
+
+/// 요청 하나에 대한 판정
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// 허용량이 모두 회복되거나 현재 구간이 끝날 때까지 남은 시간
+    pub reset: Duration,
+    /// 거절된 경우 다시 허용될 때까지 남은 시간
+    pub retry_after: Option<Duration>,
+}
+
+impl Quota {
+    /// `capacity` 가 0 이면 [`RateLimitError::InvalidQuota`] 를 돌려줍니다.
+    pub fn token_bucket(capacity: u32, period: Duration) -> Result<Self, RateLimitError> {
+        if capacity == 0 {
+            return Err(RateLimitError::InvalidQuota("capacity"));
+        }
+
+        Ok(Quota::TokenBucket { capacity, period })
+    }
+
+    /// `limit` 이 0 이면 [`RateLimitError::InvalidQuota`] 를 돌려줍니다.
+    pub fn sliding_window(limit: u32, window: Duration) -> Result<Self, RateLimitError> {
+        if limit == 0 {
+            return Err(RateLimitError::InvalidQuota("limit"));
+        }
+
+        Ok(Quota::SlidingWindow { limit, window })
+    }
+
+    pub fn limit(&self) -> u32 {
+        match self {
+            Quota::TokenBucket { capacity, .. } => *capacity,
+            Quota::SlidingWindow { limit, .. } => *limit,
+        }
+    }
+
+    /// 상태를 보관해야 하는 시간. 이보다 오래 요청이 없으면 처음부터 계산해도 결과가 같습니다.
+    pub fn ttl(&self) -> Duration {
+        match self {
+            Quota::TokenBucket { period, .. } => *period,
+            Quota::SlidingWindow { window, .. } => *window * 2,
+        }
+    }
+
+    /// `RateLimit-Policy` 헤더 값 (`{limit};w={seconds}`)
+    pub fn policy(&self) -> String {
+        let period = match self {
+            Quota::TokenBucket { period, .. } => period,
+            Quota::SlidingWindow { window, .. } => window,
+        };
+
+        format!("{};w={}", self.limit(), period.as_secs().max(1))
+    }
+
+    /// 요청 하나를 반영한 새 상태와 판정. `now` 는 유닉스 밀리초입니다.
+    pub fn acquire(&self, state: Option<RateLimitState>, now: u64) -> (RateLimitState, Decision) {
+        match *self {
+            Quota::TokenBucket { capacity, period } => token_bucket(capacity, period, state, now),
+            Quota::SlidingWindow { limit, window } => sliding_window(limit, window, state, now),
+        }
+    }
+}
+
+fn token_bucket(capacity: u32, period: Duration, state: Option<RateLimitState>, now: u64) -> (RateLimitState, Decision) {
+    let capacity = capacity as f64;
+    // 밀리초당 채워지는 토큰 수
+    let rate = capacity / (period.as_millis().max(1) as f64);
+
+    let mut tokens = match state {
+        Some(RateLimitState::Bucket { tokens, updated_at }) => {
+            (tokens + now.saturating_sub(updated_at) as f64 * rate).min(capacity)
+        }
+        _ => capacity,
+    };
+
+    let allowed = tokens >= 1.0;
+    if allowed {
+        tokens -= 1.0;
+    }
+
+    let decision = Decision {
+        allowed,
+        limit: capacity as u32,
+        remaining: tokens.floor() as u32,
+        reset: millis((capacity - tokens) / rate),
+        retry_after: (!allowed).then(|| millis((1.0 - tokens) / rate)),
+    };
+
+    (RateLimitState::Bucket { tokens, updated_at: now }, decision)
+}
+
+fn sliding_window(limit: u32, window: Duration, state: Option<RateLimitState>, now: u64) -> (RateLimitState, Decision) {
+    let window = window.as_millis().max(1) as u64;
+    let started_at = now - now % window;
+
+    let (previous, mut current) = match state {
+        Some(RateLimitState::Window { started_at: at, current, previous }) if at == started_at => (previous, current),
+        Some(RateLimitState::Window { started_at: at, current, .. }) if at + window == started_at => (current, 0),
+        _ => (0, 0),
+    };
+
+    let elapsed = (now - started_at) as f64;
+    let window_f = window as f64;
+    let mut estimated = previous as f64 * (1.0 - elapsed / window_f) + current as f64;
+
+    let allowed = estimated + 1.0 <= limit as f64;
+    if allowed {
+        current += 1;
+        estimated += 1.0;
+    }
+
+    // 거절되면 추정치가 `limit - 1` 이하로 내려가는 시점을 계산한다.
+    let retry_after = (!allowed).then(|| {
+        let allowance = limit.saturating_sub(1) as f64;
+
+        if current as f64 > allowance {
+            // 다음 구간에서 지금 구간의 개수가 충분히 줄어들 때까지
+            (window_f - elapsed) + window_f * (1.0 - allowance / current as f64)
+        } else {
+            window_f * (1.0 - (allowance - current as f64) / previous as f64) - elapsed
+        }
+    });
+
+    let decision = Decision {
+        allowed,
+        limit,
+        remaining: (limit as f64 - estimated).max(0.0).floor() as u32,
+        reset: Duration::from_millis(window - (now - started_at)),
+        retry_after: retry_after.map(millis),
+    };
+
+    (RateLimitState::Window { started_at, current, previous }, decision)
+}
+
+fn millis(value: f64) -> Duration {
+    Duration::from_millis(value.max(0.0).ceil() as u64)
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// 키별 상태 저장소
+///
+/// 같은 키의 요청이 동시에 와도 하나씩 반영되도록 `acquire` 는 원자적이어야 합니다.
+pub trait RateLimitStore: Send + Sync + 'static {
+    fn acquire<'a>(&'a self, key: &'a str, quota: &'a Quota) -> BoxFuture<'a, Result<Decision, StoreError>>;
+}
+
+/// 프로세스 메모리 저장소. 인스턴스마다 따로 계산됩니다.
+#[derive(Clone, Default)]
+pub struct MemoryRateLimitStore {
+    entries: Arc<Mutex<HashMap<String, (RateLimitState, Instant)>>>,
+}
+
+impl MemoryRateLimitStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RateLimitStore for MemoryRateLimitStore {
+    fn acquire<'a>(&'a self, key: &'a str, quota: &'a Quota) -> BoxFuture<'a, Result<Decision, StoreError>> {
+        // 상태는 한 번에 바꿔 넣으므로 다른 요청이 패닉을 냈어도 그대로 이어 쓸 수 있다.
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+
+        if entries.len() > MEMORY_STORE_PRUNE_THRESHOLD {
+            entries.retain(|_, (_, expires_at)| *expires_at > now);
+        }
+
+        let state = entries
+            .get(key)
+            .filter(|(_, expires_at)| *expires_at > now)
+            .map(|(state, _)| *state);
+        let (state, decision) = quota.acquire(state, now_millis());
+        entries.insert(key.to_string(), (state, now + quota.ttl()));
+
+        Box::pin(ready(Ok(decision)))
+    }
+}
+
+/// kit-cache 저장소. Redis 백엔드를 쓰면 인스턴스가 여러 개여도 함께 계산됩니다.
+///
+/// 상태는 `rate-limit:{key}` 에 저장되고, 동시에 바뀌면 compare-and-swap 으로 다시 계산합니다.
+#[derive(Clone)]
+pub struct CacheRateLimitStore {
+    namespace: Namespace,
+}
+
+impl CacheRateLimitStore {
+    pub fn new(cache: Arc<dyn Cache>) -> Self {
+        Self {
+            namespace: Namespace::new(cache, "rate-limit"),
+        }
+    }
+}
+
+impl RateLimitStore for CacheRateLimitStore {
+    fn acquire<'a>(&'a self, key: &'a str, quota: &'a Quota) -> BoxFuture<'a, Result<Decision, StoreError>> {
+        Box::pin(async move {
+            for _ in 0..CACHE_STORE_MAX_ATTEMPTS {
+                let current = self.namespace.get::<RateLimitState>(key).await?;
+                let (state, decision) = quota.acquire(current, now_millis());
+
+                if self
+                    .namespace
+                    .compare_and_swap(key, current.as_ref(), &state, Some(quota.ttl()))
+                    .await?
+                {
+                    return Ok(decision);
+                }
+            }
+
+            Err(format!("too much contention on rate limit key `{}`", key).into())
+        })
+    }
+}
+
+/// 허용량을 나눠 세는 기준
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyBy {
+    /// 클라이언트 IP ([`RequestContext::client_ip`]). 컨텍스트가 없으면 소켓의 상대 주소로 셉니다.
+    ///
+    /// `X-Forwarded-For` 는 [`kit_context::Context::with_trusted_proxies`] 로 신뢰한 프록시가 붙인 것만 반영됩니다.
+    Ip,
+    /// 인증된 사용자 ID. 익명 요청은 IP 로 셉니다.
+    User,
+    /// API 키 헤더 값. 헤더가 없으면 IP 로 셉니다. 키는 해시로만 저장됩니다.
+    ApiKey(&'static str),
+    /// 규칙에 걸린 라우트 전체를 하나로 셉니다. 경로 파라미터가 달라도 같은 규칙이면 함께 셉니다.
+    Route,
+}
+
+/// 이름, 허용량, 키 기준, 적용할 라우트로 이루어진 규칙
+///
+/// 라우트 패턴의 `{name}` 은 경로 한 부분과 일치합니다. 라우트를 지정하지 않으면 모든 요청에 적용됩니다.
+#[derive(Debug, Clone)]
+pub struct RateLimitRule {
+    name: String,
+    quota: Quota,
+    key_by: KeyBy,
+    method: Option<Method>,
+    path: Option<String>,
+}
+
+impl RateLimitRule {
+    pub fn new(name: impl Into<String>, quota: Quota) -> Self {
+        Self {
+            name: name.into(),
+            quota,
+            key_by: KeyBy::Ip,
+            method: None,
+            path: None,
+        }
+    }
+
+    pub fn key_by(mut self, key_by: KeyBy) -> Self {
+        self.key_by = key_by;
+        self
+    }
+
+    pub fn route(mut self, method: Method, path: impl Into<String>) -> Self {
+        self.method = Some(method);
+        self.path = Some(path.into());
+        self
+    }
+
+    /// 메서드와 관계없이 경로에만 적용합니다.
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.method = None;
+        self.path = Some(path.into());
+        self
+    }
+
+    fn matches(&self, method: &Method, path: &str) -> bool {
+        self.method.as_ref().is_none_or(|expected| expected == method)
+            && self.path.as_deref().is_none_or(|pattern| matches_path(pattern, path))
+    }
+
+    fn key(&self, req: &WebRequest<DefaultError>) -> String {
+        // 클라이언트가 보낸 헤더로 바꿀 수 없는 주소만 쓴다.
+        let ip = || {
+            let ip = req
+                .extensions()
+                .get::<Arc<RequestContext>>()
+                .and_then(|context| context.client_ip.clone())
+                .or_else(|| req.peer_addr().map(|addr| addr.ip().to_string()))
+                .unwrap_or_else(|| "unknown".to_string());
+            format!("ip:{}", ip)
+        };
+
+        let identity = match &self.key_by {
+            KeyBy::Ip => ip(),
+            KeyBy::User => {
+                let user = req
+                    .extensions()
+                    .get::<Arc<RequestContext>>()
+                    .and_then(|context| context.principal.as_ref().map(|principal| format!("user:{}", principal.id)));
+                user.unwrap_or_else(ip)
+            }
+            KeyBy::ApiKey(header) => req
+                .headers()
+                .get(*header)
+                .map(|value| format!("key:{}", hex::encode(Sha256::digest(value.as_bytes()))))
+                .unwrap_or_else(ip),
+            KeyBy::Route => format!(
+                "route:{} {}",
+                self.method.as_ref().map_or("*", Method::as_str),
+                self.path.as_deref().unwrap_or("*")
+            ),
+        };
+
+        format!("{}:{}", self.name, identity)
+    }
+}
+
+fn matches_path(pattern: &str, path: &str) -> bool {
+    let mut pattern = pattern.trim_end_matches('/').split('/');
+    let mut path = path.trim_end_matches('/').split('/');
+
+    loop {
+        match (pattern.next(), path.next()) {
+            (None, None) => return true,
+            (Some(expected), Some(actual)) if expected.starts_with('{') && expected.ends_with('}') => {
+                if actual.is_empty() {
+                    return false;
+                }
+            }
+            (Some(expected), Some(actual)) if expected == actual => {}
+            _ => return false,
+        }
+    }
+}
+
+/// 규칙별로 요청 수를 제한하는 미들웨어
+///
+/// - 걸린 규칙 중 하나라도 허용량을 넘으면 `429` 와 `Retry-After` 로 응답하고 핸들러를 실행하지 않습니다.
+/// - 응답에는 가장 여유가 적은 규칙의 `RateLimit-Limit`, `RateLimit-Remaining`, `RateLimit-Reset`, `RateLimit-Policy` 를 붙입니다.
+/// - 저장소에 접근할 수 없으면 요청을 막지 않고 통과시킵니다.
+///
+/// 사용자와 IP 는 요청 컨텍스트에서 읽으므로 [`kit_context::Context`] 안쪽에 둡니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// App::new()
+///     .wrap(
+///         RateLimit::new(CacheRateLimitStore::new(cache.clone()))
+///             .rule(RateLimitRule::new("register", Quota::sliding_window(10, Duration::from_secs(3600))?).route(Method::POST, "/v1/user"))
+///             .rule(RateLimitRule::new("api", Quota::token_bucket(100, Duration::from_secs(60))?).key_by(KeyBy::User)),
+///     )
+///     .wrap(Context::new())
+/// ```
+pub struct RateLimit<St> {
+    store: Arc<St>,
+    rules: Arc<Vec<RateLimitRule>>,
+}
+
+impl<St: RateLimitStore> RateLimit<St> {
+    pub fn new(store: St) -> Self {
+        Self {
+            store: Arc::new(store),
+            rules: Arc::new(Vec::new()),
+        }
+    }
+
+    pub fn rule(mut self, rule: RateLimitRule) -> Self {
+        Arc::make_mut(&mut self.rules).push(rule);
+        self
+    }
+}
+
+impl<St> Clone for RateLimit<St> {
+    fn clone(&self) -> Self {
+        Self {
+            store: self.store.clone(),
+            rules: self.rules.clone(),
+        }
+    }
+}
+
+impl<St, S> Middleware<S> for RateLimit<St> {
+    type Service = RateLimitMiddleware<St, S>;
+
+    fn create(&self, service: S) -> Self::Service {
+        RateLimitMiddleware {
+            service,
+            store: self.store.clone(),
+            rules: self.rules.clone(),
+        }
+    }
+}
+
+pub struct RateLimitMiddleware<St, S> {
+    service: S,
+    store: Arc<St>,
+    rules: Arc<Vec<RateLimitRule>>,
+}
+
+impl<St, S> Service<WebRequest<DefaultError>> for RateLimitMiddleware<St, S>
+where
+    St: RateLimitStore,
+    S: Service<WebRequest<DefaultError>, Response = WebResponse, Error = web::Error>,
+{
+    type Response = WebResponse;
+    type Error = web::Error;
+
+    ntex::forward_ready!(service);
+
+    async fn call(&self, req: WebRequest<DefaultError>, ctx: ServiceCtx<'_, Self>) -> Result<Self::Response, Self::Error> {
+        // 가장 여유가 적은 규칙의 판정. 거절된 판정이 있으면 그것을 쓴다.
+        let mut tightest: Option<(Decision, &RateLimitRule)> = None;
+
+        for rule in self.rules.iter().filter(|rule| rule.matches(req.method(), req.path())) {
+            let decision = match self.store.acquire(&rule.key(&req), &rule.quota).await {
+                Ok(decision) => decision,
+                Err(e) => {
+                    eprintln!("[rate-limit] failed to check `{}`: {}", rule.name, e);
+                    continue;
+                }
+            };
+
+            let tighter = tightest.as_ref().is_none_or(|(current, _)| {
+                (!decision.allowed, current.remaining) > (!current.allowed, decision.remaining)
+            });
+            if tighter {
+                tightest = Some((decision, rule));
+            }
+        }
+
+        let Some((decision, rule)) = tightest else {
+            return ctx.call(&self.service, req).await;
+        };
+
+        if !decision.allowed {
+            let retry_after = seconds(decision.retry_after.unwrap_or(decision.reset));
+            let mut response = req.render_error(RateLimitError::Exceeded(retry_after));
+
+            response.headers_mut().insert(RETRY_AFTER, HeaderValue::from(retry_after));
+            insert_headers(response.headers_mut(), &decision, &rule.quota);
+            return Ok(response);
+        }
+
+        let mut response = ctx.call(&self.service, req).await?;
+        insert_headers(response.headers_mut(), &decision, &rule.quota);
+        Ok(response)
+    }
+}
+
+// 초 단위 헤더는 올림하고, 0 이면 곧바로 재시도하지 않도록 1 로 둔다.
+fn seconds(duration: Duration) -> u64 {
+    duration.as_millis().div_ceil(1000).max(1) as u64
+}
+
+fn insert_headers(headers: &mut HeaderMap, decision: &Decision, quota: &Quota) {
+    headers.insert(HeaderName::from_static("ratelimit-limit"), HeaderValue::from(decision.limit));
+    headers.insert(HeaderName::from_static("ratelimit-remaining"), HeaderValue::from(decision.remaining));
+    headers.insert(HeaderName::from_static("ratelimit-reset"), HeaderValue::from(seconds(decision.reset)));
+
+    if let Ok(policy) = HeaderValue::from_str(&quota.policy()) {
+        headers.insert(HeaderName::from_static("ratelimit-policy"), policy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kit_cache::MemoryCache;
+    use ntex::http::StatusCode;
+    use ntex::web::{App, HttpResponse, test};
+
+    #[test]
+    fn test_token_bucket_refills_over_period() {
+        let quota = Quota::token_bucket(2, Duration::from_secs(10)).unwrap();
+
+        let (state, first) = quota.acquire(None, 0);
+        let (state, second) = quota.acquire(Some(state), 0);
+        let (state, denied) = quota.acquire(Some(state), 1_000);
+        let (_, refilled) = quota.acquire(Some(state), 5_000);
+
+        assert!(first.allowed && second.allowed);
+        assert_eq!(second.remaining, 0);
+        assert!(!denied.allowed);
+        assert_eq!(denied.retry_after, Some(Duration::from_secs(4)));
+        assert!(refilled.allowed);
+    }
+
+    #[test]
+    fn test_sliding_window_weights_previous_window() {
+        let quota = Quota::sliding_window(4, Duration::from_secs(10)).unwrap();
+
+        let mut state = None;
+        for _ in 0..4 {
+            let (next, decision) = quota.acquire(state, 9_000);
+            assert!(decision.allowed);
+            state = Some(next);
+        }
+
+        // 다음 구간의 1/4 지점: 이전 4개 * 3/4 = 3 이므로 하나만 더 허용된다.
+        let (next, allowed) = quota.acquire(state, 12_500);
+        let (_, denied) = quota.acquire(Some(next), 12_500);
+
+        assert!(allowed.allowed);
+        assert_eq!(allowed.remaining, 0);
+        assert!(!denied.allowed);
+        assert_eq!(denied.retry_after, Some(Duration::from_millis(2_500)));
+    }
+
+    #[test]
+    fn test_rejects_empty_quota() {
+        assert!(matches!(Quota::token_bucket(0, Duration::from_secs(1)), Err(RateLimitError::InvalidQuota("capacity"))));
+        assert!(matches!(Quota::sliding_window(0, Duration::from_secs(1)), Err(RateLimitError::InvalidQuota("limit"))));
+    }
+
+    #[test]
+    fn test_matches_path_pattern() {
+        assert!(matches_path("/v1/user", "/v1/user"));
+        assert!(matches_path("/v1/user/{id}", "/v1/user/7/"));
+        assert!(!matches_path("/v1/user/{id}", "/v1/user"));
+        assert!(!matches_path("/v1/user", "/v1/user/7"));
+    }
+
+    async fn app<St: RateLimitStore>(
+        limit: RateLimit<St>,
+    ) -> ntex::service::Pipeline<impl Service<ntex::http::Request, Response = WebResponse, Error = web::Error>> {
+        test::init_service(
+            App::new()
+                .wrap(limit)
+                .route("/user", web::post().to(|| async { HttpResponse::Created().finish() }))
+                .route("/user", web::get().to(|| async { HttpResponse::Ok().finish() }))
+                .route("/user/{id}", web::get().to(|| async { HttpResponse::Ok().finish() })),
+        )
+        .await
+    }
+
+    // `Context` 가 신뢰한 클라이언트 IP 를 남긴 요청
+    fn request(method: Method, ip: &str) -> ntex::http::Request {
+        let request = test::TestRequest::with_uri("/user").method(method).to_request();
+        request.extensions_mut().insert(Arc::new(RequestContext {
+            client_ip: Some(ip.to_string()),
+            ..Default::default()
+        }));
+        request
+    }
+
+    #[ntex::test]
+    async fn test_rejects_over_limit_per_ip_and_route() {
+        let app = app(
+            RateLimit::new(MemoryRateLimitStore::new())
+                .rule(RateLimitRule::new("register", Quota::sliding_window(2, Duration::from_secs(60)).unwrap()).route(Method::POST, "/user")),
+        )
+        .await;
+
+        for remaining in ["1", "0"] {
+            let response = test::call_service(&app, request(Method::POST, "10.0.0.1")).await;
+            assert_eq!(response.status(), StatusCode::CREATED);
+            assert_eq!(response.headers().get("ratelimit-remaining").unwrap(), remaining);
+            assert_eq!(response.headers().get("ratelimit-policy").unwrap(), "2;w=60");
+        }
+
+        let limited = test::call_service(&app, request(Method::POST, "10.0.0.1")).await;
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(limited.headers().get(RETRY_AFTER).is_some());
+
+        let other_ip = test::call_service(&app, request(Method::POST, "10.0.0.2")).await;
+        let other_route = test::call_service(&app, request(Method::GET, "10.0.0.1")).await;
+        assert_eq!(other_ip.status(), StatusCode::CREATED);
+        assert_eq!(other_route.status(), StatusCode::OK);
+        assert!(other_route.headers().get("ratelimit-limit").is_none());
+    }
+
+    #[ntex::test]
+    async fn test_rotating_forwarded_for_does_not_reset_limit() {
+        let app = app(
+            RateLimit::new(MemoryRateLimitStore::new())
+                .rule(RateLimitRule::new("api", Quota::token_bucket(1, Duration::from_secs(60)).unwrap())),
+        )
+        .await;
+
+        // 컨텍스트가 없으면 헤더가 아닌 소켓 주소로 센다.
+        let mut statuses = Vec::new();
+        for ip in ["10.0.0.1", "10.0.0.2", "10.0.0.3"] {
+            let request = test::TestRequest::with_uri("/user").header("x-forwarded-for", ip).to_request();
+            statuses.push(test::call_service(&app, request).await.status());
+        }
+
+        assert_eq!(statuses, [StatusCode::OK, StatusCode::TOO_MANY_REQUESTS, StatusCode::TOO_MANY_REQUESTS]);
+    }
+
+    #[ntex::test]
+    async fn test_route_key_uses_rule_pattern() {
+        let app = app(
+            RateLimit::new(MemoryRateLimitStore::new()).rule(
+                RateLimitRule::new("profile", Quota::token_bucket(1, Duration::from_secs(60)).unwrap())
+                    .key_by(KeyBy::Route)
+                    .route(Method::GET, "/user/{id}"),
+            ),
+        )
+        .await;
+
+        let first = test::call_service(&app, test::TestRequest::with_uri("/user/1").to_request()).await;
+        let other_id = test::call_service(&app, test::TestRequest::with_uri("/user/2").to_request()).await;
+
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(other_id.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[ntex::test]
+    async fn test_anonymous_user_falls_back_to_ip() {
+        let app = app(
+            RateLimit::new(MemoryRateLimitStore::new())
+                .rule(RateLimitRule::new("api", Quota::token_bucket(1, Duration::from_secs(60)).unwrap()).key_by(KeyBy::User)),
+        )
+        .await;
+
+        let first = test::call_service(&app, request(Method::GET, "10.0.0.1")).await;
+        let other_ip = test::call_service(&app, request(Method::GET, "10.0.0.2")).await;
+        let limited = test::call_service(&app, request(Method::GET, "10.0.0.1")).await;
+
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(other_ip.status(), StatusCode::OK);
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[ntex::test]
+    async fn test_cache_store_shares_state() {
+        let cache: Arc<dyn Cache> = Arc::new(MemoryCache::new(16));
+        let rule = || RateLimitRule::new("api", Quota::token_bucket(1, Duration::from_secs(60)).unwrap()).key_by(KeyBy::Route);
+        let first = app(RateLimit::new(CacheRateLimitStore::new(cache.clone())).rule(rule())).await;
+        let second = app(RateLimit::new(CacheRateLimitStore::new(cache)).rule(rule())).await;
+
+        let allowed = test::call_service(&first, request(Method::GET, "10.0.0.1")).await;
+        let limited = test::call_service(&second, request(Method::GET, "10.0.0.2")).await;
+
+        assert_eq!(allowed.status(), StatusCode::OK);
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(limited.headers().get(RETRY_AFTER).unwrap(), "60");
+    }
+}
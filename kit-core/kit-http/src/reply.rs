@@ -0,0 +1,169 @@
+use ntex::http::header::{CONTENT_TYPE, HeaderName, HeaderValue, LOCATION};
+use ntex::http::{Response, StatusCode};
+use ntex::web::{ErrorRenderer, HttpRequest, Responder};
+use serde::Serialize;
+use crate::content::Format;
+use crate::envelope::{ApiError, Envelope};
+
+/// [`Envelope`] 로 감싼 응답
+///
+/// 상태 코드별 생성 함수를 제공하며, 본문은 요청의 `Accept` 헤더에 따라 JSON 또는 MessagePack 으로 직렬화됩니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// async fn create_user(...) -> Result<Reply<UserRegisterCommandResult>, Exception> {
+///     let result = handler.handle(command).await?;
+///     Ok(Reply::created(result))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Reply<T> {
+    status: StatusCode,
+    envelope: Option<Envelope<T>>,
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+impl<T> Reply<T> {
+    pub fn new(status: StatusCode, data: T) -> Self {
+        Self::from_envelope(status, Envelope::new(data))
+    }
+
+    pub fn from_envelope(status: StatusCode, envelope: Envelope<T>) -> Self {
+        Self {
+            status,
+            envelope: Some(envelope),
+            headers: Vec::new(),
+        }
+    }
+
+    /// `200 OK`
+    pub fn ok(data: T) -> Self {
+        Self::new(StatusCode::OK, data)
+    }
+
+    /// `201 Created`
+    pub fn created(data: T) -> Self {
+        Self::new(StatusCode::CREATED, data)
+    }
+
+    /// `202 Accepted`. 비동기로 처리될 작업을 접수했을 때 사용합니다.
+    pub fn accepted(data: T) -> Self {
+        Self::new(StatusCode::ACCEPTED, data)
+    }
+
+    /// 데이터 없이 에러 목록만 응답합니다.
+    pub fn errors(status: StatusCode, errors: Vec<ApiError>) -> Self {
+        Self::from_envelope(status, Envelope::from_errors(errors))
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn envelope(&self) -> Option<&Envelope<T>> {
+        self.envelope.as_ref()
+    }
+
+    pub fn with_status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn with_meta(mut self, key: impl Into<String>, value: impl Serialize) -> Self {
+        self.envelope = self.envelope.map(|envelope| envelope.with_meta(key, value));
+        self
+    }
+
+    /// 값이 올바른 헤더가 아니면 무시합니다.
+    pub fn with_header(mut self, name: HeaderName, value: impl AsRef<str>) -> Self {
+        if let Ok(value) = HeaderValue::from_str(value.as_ref()) {
+            self.headers.push((name, value));
+        }
+        self
+    }
+
+    /// 생성된 리소스의 위치 (`Location` 헤더)
+    pub fn with_location(self, location: impl AsRef<str>) -> Self {
+        self.with_header(LOCATION, location)
+    }
+}
+
+impl Reply<()> {
+    /// `204 No Content`. 본문 없이 응답합니다.
+    pub fn no_content() -> Self {
+        Self {
+            status: StatusCode::NO_CONTENT,
+            envelope: None,
+            headers: Vec::new(),
+        }
+    }
+}
+
+impl<T: Serialize, Err: ErrorRenderer> Responder<Err> for Reply<T> {
+    async fn respond_to(self, req: &HttpRequest) -> Response {
+        let mut response = Response::build(self.status);
+        for (name, value) in self.headers {
+            response.header(name, value);
+        }
+
+        let Some(envelope) = self.envelope else {
+            return response.finish();
+        };
+
+        let format = Format::from_accept(req.headers());
+        match format.encode(&envelope) {
+            Ok(body) => response.header(CONTENT_TYPE, format.content_type()).body(body),
+            Err(e) => {
+                eprintln!("[http] {} {}: {}", req.method(), req.path(), e);
+                Response::InternalServerError().finish()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::web::{self, App, test};
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct User {
+        id: i32,
+    }
+
+    #[ntex::test]
+    async fn test_reply_is_negotiated() {
+        let app = test::init_service(
+            App::new()
+                .route("/user", web::post().to(|| async {
+                    Reply::created(User { id: 7 }).with_location("/user/7").with_meta("version", 1)
+                }))
+                .route("/user", web::delete().to(|| async { Reply::no_content() })),
+        )
+        .await;
+
+        let response = test::call_service(&app, test::TestRequest::post().uri("/user").to_request()).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers().get(LOCATION).unwrap(), "/user/7");
+        assert_eq!(
+            test::read_body(response).await,
+            r#"{"data":{"id":7},"meta":{"version":1}}"#
+        );
+
+        let request = test::TestRequest::post()
+            .uri("/user")
+            .header("accept", "application/msgpack")
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.headers().get(CONTENT_TYPE).unwrap(), "application/msgpack");
+
+        let envelope: Envelope<User> = Format::MessagePack.decode(&test::read_body(response).await).unwrap();
+        assert_eq!(envelope.data, Some(User { id: 7 }));
+
+        let response = test::call_service(&app, test::TestRequest::delete().uri("/user").to_request()).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert!(test::read_body(response).await.is_empty());
+    }
+}
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS lock_tokens (
+    name VARCHAR(255) PRIMARY KEY,
+    token BIGINT NOT NULL,
+    acquired_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
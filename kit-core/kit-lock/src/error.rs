@@ -0,0 +1,26 @@
+use std::error::Error as StdError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LockError {
+    #[error("lock backend error: {0}")]
+    Backend(#[source] Box<dyn StdError + Send + Sync + 'static>),
+
+    #[error("timed out waiting for lock `{0}`")]
+    Timeout(String),
+
+    #[error("lost lock `{0}` before the work completed")]
+    Lost(String),
+}
+
+impl From<redis::RedisError> for LockError {
+    fn from(error: redis::RedisError) -> Self {
+        LockError::Backend(Box::new(error))
+    }
+}
+
+impl From<sqlx::Error> for LockError {
+    fn from(error: sqlx::Error) -> Self {
+        LockError::Backend(Box::new(error))
+    }
+}
@@ -0,0 +1,95 @@
+use std::future::Future;
+use tokio::time::sleep;
+use crate::lock::DistributedLock;
+
+/// 여러 인스턴스 중 하나에서만 작업을 실행하기 위한 리더 선출
+///
+/// 리더가 된 인스턴스는 락을 연장하면서 작업을 실행하고, 나머지는 재시도 간격마다 다시 선출을 시도합니다.
+/// 리더가 죽으면 락 만료 시간 뒤에 다른 인스턴스가 리더가 됩니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// let election = LeaderElection::new(lock, "outbox-relay");
+/// ntex::rt::spawn(async move { election.run(|token| relay.run(token)).await });
+/// ```
+#[derive(Clone)]
+pub struct LeaderElection {
+    lock: DistributedLock,
+    name: String,
+}
+
+impl LeaderElection {
+    pub fn new(lock: DistributedLock, name: impl Into<String>) -> Self {
+        Self {
+            lock,
+            name: name.into(),
+        }
+    }
+
+    /// 리더가 될 때마다 펜싱 토큰으로 `worker` 를 실행합니다. 반환하지 않습니다.
+    ///
+    /// 리더 자리를 잃으면 `worker` 는 중단됩니다. `worker` 가 끝나면 리더 자리를 내려놓고 다시 선출에 참여합니다.
+    pub async fn run<F, Fut>(&self, mut worker: F)
+    where
+        F: FnMut(u64) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        loop {
+            if let Err(e) = self.lock.with_lock(&self.name, &mut worker).await {
+                eprintln!("[leader] `{}`: {}", self.name, e);
+            }
+
+            sleep(self.lock.retry_interval()).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use futures::future::{join_all, select};
+    use crate::memory::MemoryLock;
+
+    #[ntex::test]
+    async fn test_only_one_instance_leads_at_a_time() {
+        let lock = DistributedLock::new(MemoryLock::new())
+            .with_ttl(Duration::from_millis(60))
+            .with_retry_interval(Duration::from_millis(10));
+        let leaders = Arc::new(AtomicUsize::new(0));
+        let terms = Arc::new(AtomicUsize::new(0));
+
+        let instances = (0..3).map(|_| {
+            let election = LeaderElection::new(lock.clone(), "relay");
+            let leaders = leaders.clone();
+            let terms = terms.clone();
+
+            async move {
+                election
+                    .run(|_| {
+                        let leaders = leaders.clone();
+                        let terms = terms.clone();
+
+                        async move {
+                            assert_eq!(leaders.fetch_add(1, Ordering::SeqCst), 0);
+                            terms.fetch_add(1, Ordering::SeqCst);
+                            sleep(Duration::from_millis(30)).await;
+                            leaders.fetch_sub(1, Ordering::SeqCst);
+                        }
+                    })
+                    .await
+            }
+        });
+
+        select(
+            Box::pin(join_all(instances)),
+            Box::pin(sleep(Duration::from_millis(200))),
+        )
+        .await;
+
+        assert!(terms.load(Ordering::SeqCst) >= 2);
+    }
+}
@@ -0,0 +1,56 @@
+use std::time::Duration;
+use futures::future::BoxFuture;
+use crate::error::LockError;
+
+/// 락 저장소
+///
+/// 이름별로 한 곳만 락을 잡을 수 있고, 잡을 때마다 이전보다 큰 펜싱 토큰을 발급해야 합니다.
+pub trait LockBackend: Send + Sync + 'static {
+    /// 락을 잡지 못하면 기다리지 않고 `None` 을 돌려줍니다.
+    fn try_acquire<'a>(&'a self, name: &'a str, ttl: Duration) -> BoxFuture<'a, Result<Option<Lease>, LockError>>;
+}
+
+/// 백엔드별로 잡은 락을 연장하고 푸는 방법
+pub trait LeaseHandle: Send + 'static {
+    /// 아직 락을 쥐고 있으면 만료 시간을 `ttl` 로 다시 잡고 `true` 를 돌려줍니다.
+    fn renew(&mut self, ttl: Duration) -> BoxFuture<'_, Result<bool, LockError>>;
+
+    fn release(self: Box<Self>) -> BoxFuture<'static, Result<(), LockError>>;
+}
+
+/// 잡은 락
+///
+/// 만료 시간 안에 [`Lease::renew`] 하지 않으면 다른 인스턴스가 락을 가져갈 수 있습니다.
+/// 락으로 보호하는 자원에 쓸 때는 [`Lease::token`] 을 함께 넘겨, 더 작은 토큰의 늦은 쓰기를 거절하게 합니다.
+pub struct Lease {
+    name: String,
+    token: u64,
+    handle: Box<dyn LeaseHandle>,
+}
+
+impl Lease {
+    pub fn new(name: impl Into<String>, token: u64, handle: impl LeaseHandle) -> Self {
+        Self {
+            name: name.into(),
+            token,
+            handle: Box::new(handle),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 펜싱 토큰. 같은 이름의 락을 잡을 때마다 커집니다.
+    pub fn token(&self) -> u64 {
+        self.token
+    }
+
+    pub async fn renew(&mut self, ttl: Duration) -> Result<bool, LockError> {
+        self.handle.renew(ttl).await
+    }
+
+    pub async fn release(self) -> Result<(), LockError> {
+        self.handle.release().await
+    }
+}
@@ -0,0 +1,15 @@
+mod error;
+mod lease;
+mod leader;
+mod lock;
+mod memory;
+mod postgres;
+mod redis;
+
+pub use error::LockError;
+pub use lease::{Lease, LeaseHandle, LockBackend};
+pub use leader::LeaderElection;
+pub use lock::DistributedLock;
+pub use memory::MemoryLock;
+pub use postgres::PgAdvisoryLock;
+pub use redis::RedisLock;
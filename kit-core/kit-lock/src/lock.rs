@@ -0,0 +1,175 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use futures::future::{Either, select};
+use tokio::time::{Instant, sleep};
+use crate::error::LockError;
+use crate::lease::{Lease, LockBackend};
+
+/// 인스턴스 사이에서 공유되는 이름 있는 락
+///
+/// # 예시
+///
+/// ```ignore
+/// let lock = DistributedLock::new(PgAdvisoryLock::new(pool));
+///
+/// match lock.with_lock("daily-report", |token| report.run(token)).await? {
+///     Some(result) => println!("done: {:?}", result),
+///     None => println!("another instance is running the report"),
+/// }
+/// ```
+#[derive(Clone)]
+pub struct DistributedLock {
+    backend: Arc<dyn LockBackend>,
+    ttl: Duration,
+    retry_interval: Duration,
+}
+
+impl DistributedLock {
+    pub fn new(backend: impl LockBackend) -> Self {
+        Self {
+            backend: Arc::new(backend),
+            ttl: Duration::from_secs(30),
+            retry_interval: Duration::from_millis(500),
+        }
+    }
+
+    /// 락 만료 시간. [`DistributedLock::with_lock`] 은 이 시간의 1/3 마다 연장합니다.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// [`DistributedLock::acquire`] 와 리더 선출이 다시 시도하는 간격
+    pub fn with_retry_interval(mut self, interval: Duration) -> Self {
+        self.retry_interval = interval;
+        self
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn retry_interval(&self) -> Duration {
+        self.retry_interval
+    }
+
+    pub async fn try_acquire(&self, name: &str) -> Result<Option<Lease>, LockError> {
+        self.backend.try_acquire(name, self.ttl).await
+    }
+
+    /// 락을 잡을 때까지 `timeout` 동안 다시 시도합니다.
+    pub async fn acquire(&self, name: &str, timeout: Duration) -> Result<Lease, LockError> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if let Some(lease) = self.try_acquire(name).await? {
+                return Ok(lease);
+            }
+
+            if Instant::now() + self.retry_interval > deadline {
+                return Err(LockError::Timeout(name.to_string()));
+            }
+
+            sleep(self.retry_interval).await;
+        }
+    }
+
+    /// 락을 잡으면 펜싱 토큰으로 `work` 를 실행하고, 끝날 때까지 락을 연장한 뒤 풉니다.
+    ///
+    /// 다른 곳이 락을 쥐고 있으면 실행하지 않고 `None` 을 돌려줍니다.
+    /// 연장에 실패하면 락을 잃은 것으로 보고 `work` 를 중단한 뒤 [`LockError::Lost`] 를 돌려줍니다.
+    pub async fn with_lock<T, F, Fut>(&self, name: &str, work: F) -> Result<Option<T>, LockError>
+    where
+        F: FnOnce(u64) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let Some(mut lease) = self.try_acquire(name).await? else {
+            return Ok(None);
+        };
+
+        let outcome = {
+            let work = std::pin::pin!(work(lease.token()));
+            let keep_alive = std::pin::pin!(self.keep_alive(&mut lease));
+
+            match select(work, keep_alive).await {
+                Either::Left((value, _)) => Ok(value),
+                Either::Right((error, _)) => Err(error),
+            }
+        };
+
+        match outcome {
+            Ok(value) => {
+                if let Err(e) = lease.release().await {
+                    eprintln!("[lock] failed to release `{}`: {}", name, e);
+                }
+
+                Ok(Some(value))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // 락을 잃을 때까지 연장한다.
+    async fn keep_alive(&self, lease: &mut Lease) -> LockError {
+        loop {
+            sleep(self.ttl / 3).await;
+
+            match lease.renew(self.ttl).await {
+                Ok(true) => {}
+                Ok(false) => return LockError::Lost(lease.name().to_string()),
+                Err(e) => {
+                    eprintln!("[lock] failed to renew `{}`: {}", lease.name(), e);
+                    return LockError::Lost(lease.name().to_string());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemoryLock;
+
+    #[ntex::test]
+    async fn test_acquire_times_out_while_held() {
+        let lock = DistributedLock::new(MemoryLock::new()).with_retry_interval(Duration::from_millis(10));
+        let lease = lock.try_acquire("job").await.unwrap().unwrap();
+
+        assert!(matches!(
+            lock.acquire("job", Duration::from_millis(50)).await,
+            Err(LockError::Timeout(_))
+        ));
+
+        lease.release().await.unwrap();
+        assert!(lock.acquire("job", Duration::from_millis(50)).await.is_ok());
+    }
+
+    #[ntex::test]
+    async fn test_with_lock_renews_until_work_completes() {
+        let lock = DistributedLock::new(MemoryLock::new()).with_ttl(Duration::from_millis(60));
+
+        let other = lock.clone();
+
+        let result = lock
+            .with_lock("job", |token| async move {
+                sleep(Duration::from_millis(150)).await;
+                assert!(other.try_acquire("job").await.unwrap().is_none());
+                token
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result, Some(1));
+        assert_eq!(lock.try_acquire("job").await.unwrap().unwrap().token(), 2);
+    }
+
+    #[ntex::test]
+    async fn test_with_lock_skips_when_held() {
+        let lock = DistributedLock::new(MemoryLock::new());
+        let _lease = lock.try_acquire("job").await.unwrap().unwrap();
+
+        assert_eq!(lock.with_lock("job", |_| async { 1 }).await.unwrap(), None);
+    }
+}
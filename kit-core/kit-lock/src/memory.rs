@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use futures::future::{BoxFuture, ready};
+use crate::error::LockError;
+use crate::lease::{Lease, LeaseHandle, LockBackend};
+
+#[derive(Default)]
+struct State {
+    held: HashMap<String, (u64, Instant)>,
+    tokens: HashMap<String, u64>,
+}
+
+/// 한 프로세스 안에서만 유효한 락. 테스트와 단일 인스턴스 실행에 사용합니다.
+#[derive(Clone, Default)]
+pub struct MemoryLock {
+    state: Arc<Mutex<State>>,
+    owners: Arc<AtomicU64>,
+}
+
+impl MemoryLock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl LockBackend for MemoryLock {
+    fn try_acquire<'a>(&'a self, name: &'a str, ttl: Duration) -> BoxFuture<'a, Result<Option<Lease>, LockError>> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+
+        if state.held.get(name).is_some_and(|(_, expires_at)| *expires_at > now) {
+            return Box::pin(ready(Ok(None)));
+        }
+
+        let owner = self.owners.fetch_add(1, Ordering::Relaxed);
+        state.held.insert(name.to_string(), (owner, now + ttl));
+
+        let token = state.tokens.entry(name.to_string()).or_default();
+        *token += 1;
+
+        let handle = MemoryLease {
+            state: self.state.clone(),
+            name: name.to_string(),
+            owner,
+        };
+
+        Box::pin(ready(Ok(Some(Lease::new(name, *token, handle)))))
+    }
+}
+
+struct MemoryLease {
+    state: Arc<Mutex<State>>,
+    name: String,
+    owner: u64,
+}
+
+impl LeaseHandle for MemoryLease {
+    fn renew(&mut self, ttl: Duration) -> BoxFuture<'_, Result<bool, LockError>> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+
+        let renewed = match state.held.get_mut(&self.name) {
+            Some((owner, expires_at)) if *owner == self.owner && *expires_at > now => {
+                *expires_at = now + ttl;
+                true
+            }
+            _ => false,
+        };
+
+        Box::pin(ready(Ok(renewed)))
+    }
+
+    fn release(self: Box<Self>) -> BoxFuture<'static, Result<(), LockError>> {
+        let mut state = self.state.lock().unwrap();
+
+        if state.held.get(&self.name).is_some_and(|(owner, _)| *owner == self.owner) {
+            state.held.remove(&self.name);
+        }
+
+        Box::pin(ready(Ok(())))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// 백엔드 공통 동작. Redis, Postgres 테스트도 같은 검사를 사용합니다.
+    pub(crate) async fn check_backend(backend: &dyn LockBackend, name: &str) {
+        let ttl = Duration::from_millis(200);
+
+        let mut first = backend.try_acquire(name, ttl).await.unwrap().unwrap();
+        assert!(backend.try_acquire(name, ttl).await.unwrap().is_none());
+        assert!(first.renew(ttl).await.unwrap());
+
+        let token = first.token();
+        first.release().await.unwrap();
+
+        let second = backend.try_acquire(name, ttl).await.unwrap().unwrap();
+        assert!(second.token() > token);
+        second.release().await.unwrap();
+    }
+
+    #[ntex::test]
+    async fn test_memory_backend() {
+        check_backend(&MemoryLock::new(), "memory").await;
+    }
+
+    #[ntex::test]
+    async fn test_expired_lease_cannot_be_renewed() {
+        let backend = MemoryLock::new();
+        let mut first = backend.try_acquire("job", Duration::from_millis(20)).await.unwrap().unwrap();
+
+        ntex::time::sleep(Duration::from_millis(30)).await;
+        let second = backend.try_acquire("job", Duration::from_secs(1)).await.unwrap().unwrap();
+
+        assert!(!first.renew(Duration::from_secs(1)).await.unwrap());
+        first.release().await.unwrap();
+        assert!(backend.try_acquire("job", Duration::from_secs(1)).await.unwrap().is_none());
+        assert_eq!(second.token(), 2);
+    }
+}
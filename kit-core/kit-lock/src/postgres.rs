@@ -0,0 +1,126 @@
+use std::time::Duration;
+use futures::future::BoxFuture;
+use sqlx::pool::PoolConnection;
+use sqlx::{PgPool, Postgres};
+use crate::error::LockError;
+use crate::lease::{Lease, LeaseHandle, LockBackend};
+
+/// Postgres 세션 advisory lock
+///
+/// 락을 잡은 커넥션을 풀에서 빼서 쥐고 있으므로, 인스턴스가 죽어 커넥션이 끊기면 락도 바로 풀립니다.
+/// 그래서 `ttl` 은 쓰지 않고, 연장은 커넥션이 살아 있는지만 확인합니다.
+/// 락 이름은 `hashtextextended` 로 64비트 키가 되고, 펜싱 토큰은 `lock_tokens` 테이블에 저장됩니다.
+#[derive(Debug, Clone)]
+pub struct PgAdvisoryLock {
+    pool: PgPool,
+}
+
+impl PgAdvisoryLock {
+    /// `lock_tokens` 테이블을 만드는 마이그레이션. 애플리케이션의 마이그레이션에 등록해 적용합니다.
+    pub const MIGRATION: &'static str = include_str!("../migrations/create_lock_tokens.sql");
+
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl LockBackend for PgAdvisoryLock {
+    fn try_acquire<'a>(&'a self, name: &'a str, _: Duration) -> BoxFuture<'a, Result<Option<Lease>, LockError>> {
+        Box::pin(async move {
+            let mut connection = self.pool.acquire().await?;
+
+            let acquired = sqlx::query_scalar::<_, bool>("SELECT pg_try_advisory_lock(hashtextextended($1, 0))")
+                .bind(name)
+                .fetch_one(&mut *connection)
+                .await?;
+
+            if !acquired {
+                return Ok(None);
+            }
+
+            // 여기서부터 실패하면 핸들의 drop 이 커넥션을 닫아 락을 푼다.
+            let mut handle = PgLease {
+                connection: Some(connection),
+                name: name.to_string(),
+            };
+
+            let token = sqlx::query_scalar::<_, i64>(
+                r#"
+                INSERT INTO lock_tokens (name, token)
+                VALUES ($1, 1)
+                ON CONFLICT (name) DO UPDATE
+                SET token = lock_tokens.token + 1, acquired_at = CURRENT_TIMESTAMP
+                RETURNING token
+                "#
+            )
+                .bind(name)
+                .fetch_one(&mut **handle.connection())
+                .await?;
+
+            Ok(Some(Lease::new(name, token as u64, handle)))
+        })
+    }
+}
+
+struct PgLease {
+    connection: Option<PoolConnection<Postgres>>,
+    name: String,
+}
+
+impl PgLease {
+    fn connection(&mut self) -> &mut PoolConnection<Postgres> {
+        self.connection.as_mut().expect("lease already released")
+    }
+}
+
+impl LeaseHandle for PgLease {
+    fn renew(&mut self, _: Duration) -> BoxFuture<'_, Result<bool, LockError>> {
+        Box::pin(async move {
+            match sqlx::query("SELECT 1").execute(&mut **self.connection()).await {
+                Ok(_) => Ok(true),
+                Err(sqlx::Error::Io(_)) => Ok(false),
+                Err(e) => Err(e.into()),
+            }
+        })
+    }
+
+    fn release(mut self: Box<Self>) -> BoxFuture<'static, Result<(), LockError>> {
+        Box::pin(async move {
+            let name = self.name.clone();
+
+            sqlx::query("SELECT pg_advisory_unlock(hashtextextended($1, 0))")
+                .bind(name)
+                .execute(&mut **self.connection())
+                .await?;
+
+            // 락을 푼 커넥션만 풀로 돌려보낸다.
+            drop(self.connection.take());
+            Ok(())
+        })
+    }
+}
+
+impl Drop for PgLease {
+    // 풀지 않은 락이 풀 커넥션에 남지 않도록 커넥션을 닫는다.
+    fn drop(&mut self) {
+        if let Some(connection) = self.connection.as_mut() {
+            connection.close_on_drop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::tests::check_backend;
+
+    #[ntex::test]
+    #[ignore = "requires a local postgres"]
+    async fn test_postgres_backend() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let backend = PgAdvisoryLock::new(PgPool::connect(&url).await.unwrap());
+        sqlx::raw_sql(PgAdvisoryLock::MIGRATION).execute(&backend.pool).await.unwrap();
+
+        check_backend(&backend, "kit-lock-test").await;
+    }
+}
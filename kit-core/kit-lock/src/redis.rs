@@ -0,0 +1,134 @@
+use std::time::Duration;
+use futures::future::BoxFuture;
+use redis::aio::ConnectionManager;
+use redis::{Client, Script};
+use crate::error::LockError;
+use crate::lease::{Lease, LeaseHandle, LockBackend};
+
+// 락을 잡았으면 펜싱 토큰을 올려 돌려주고, 아니면 0
+const ACQUIRE: &str = r#"
+if redis.call('SET', KEYS[1], ARGV[1], 'NX', 'PX', ARGV[2]) then
+    return redis.call('INCR', KEYS[2])
+end
+return 0
+"#;
+
+const RENEW: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('PEXPIRE', KEYS[1], ARGV[2])
+end
+return 0
+"#;
+
+const RELEASE: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+/// Redis 락
+///
+/// 락은 `lock:{name}` 에 소유자 값으로, 펜싱 토큰은 `lock:{name}:token` 에 저장됩니다.
+/// 연장과 해제는 소유자 값이 같을 때만 적용되므로, 만료 뒤 다른 인스턴스가 잡은 락을 건드리지 않습니다.
+#[derive(Clone)]
+pub struct RedisLock {
+    connection: ConnectionManager,
+}
+
+impl RedisLock {
+    pub async fn connect(url: &str) -> Result<Self, LockError> {
+        let client = Client::open(url)?;
+        let connection = ConnectionManager::new(client).await?;
+
+        Ok(Self { connection })
+    }
+}
+
+fn millis(ttl: Duration) -> u64 {
+    (ttl.as_millis() as u64).max(1)
+}
+
+// 프로세스와 시각, 순번으로 인스턴스마다 겹치지 않는 소유자 값을 만든다.
+fn owner() -> String {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    format!("{}-{}-{}", std::process::id(), now.as_nanos(), SEQUENCE.fetch_add(1, Ordering::Relaxed))
+}
+
+impl LockBackend for RedisLock {
+    fn try_acquire<'a>(&'a self, name: &'a str, ttl: Duration) -> BoxFuture<'a, Result<Option<Lease>, LockError>> {
+        let mut connection = self.connection.clone();
+
+        Box::pin(async move {
+            let key = format!("lock:{}", name);
+            let owner = owner();
+
+            let token: u64 = Script::new(ACQUIRE)
+                .key(&key)
+                .key(format!("{}:token", key))
+                .arg(&owner)
+                .arg(millis(ttl))
+                .invoke_async(&mut connection)
+                .await?;
+
+            if token == 0 {
+                return Ok(None);
+            }
+
+            Ok(Some(Lease::new(name, token, RedisLease { connection, key, owner })))
+        })
+    }
+}
+
+struct RedisLease {
+    connection: ConnectionManager,
+    key: String,
+    owner: String,
+}
+
+impl LeaseHandle for RedisLease {
+    fn renew(&mut self, ttl: Duration) -> BoxFuture<'_, Result<bool, LockError>> {
+        Box::pin(async move {
+            let renewed: i64 = Script::new(RENEW)
+                .key(&self.key)
+                .arg(&self.owner)
+                .arg(millis(ttl))
+                .invoke_async(&mut self.connection)
+                .await?;
+
+            Ok(renewed == 1)
+        })
+    }
+
+    fn release(mut self: Box<Self>) -> BoxFuture<'static, Result<(), LockError>> {
+        Box::pin(async move {
+            let _: i64 = Script::new(RELEASE)
+                .key(&self.key)
+                .arg(&self.owner)
+                .invoke_async(&mut self.connection)
+                .await?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::tests::check_backend;
+
+    #[ntex::test]
+    #[ignore = "requires a local redis-server"]
+    async fn test_redis_backend() {
+        let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let backend = RedisLock::connect(&url).await.unwrap();
+
+        check_backend(&backend, "kit-lock-test").await;
+    }
+}
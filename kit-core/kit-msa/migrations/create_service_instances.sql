@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS service_instances (
+    service VARCHAR(255) NOT NULL,
+    id VARCHAR(255) NOT NULL,
+    address VARCHAR(255) NOT NULL,
+    metadata JSONB NOT NULL DEFAULT '{}',
+    health VARCHAR(16) NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (service, id)
+);
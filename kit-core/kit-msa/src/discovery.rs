@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::time::Duration;
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use tokio::time::{sleep, timeout};
+use crate::error::RegistryError;
+use crate::instance::{Health, ServiceInstance, check_service};
+use crate::registry::RegistryBackend;
+
+struct Watched {
+    instances: RwLock<Vec<ServiceInstance>>,
+    cursor: AtomicUsize,
+}
+
+/// 서비스 이름으로 인스턴스를 찾는 클라이언트
+///
+/// 서비스를 처음 찾을 때 목록을 읽고, 이후에는 백그라운드에서 변경 알림과 주기적인 재조회로 목록을 최신으로 유지합니다.
+/// 찾은 인스턴스는 라운드 로빈으로 나누며, `Passing` 이 없을 때만 `Warning` 을 고르고 `Critical` 은 고르지 않습니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// let discovery = Discovery::new(PgRegistry::new(pool));
+/// let instance = discovery.resolve("user-service").await?;
+/// ```
+#[derive(Clone)]
+pub struct Discovery {
+    backend: Arc<dyn RegistryBackend>,
+    refresh_interval: Duration,
+    services: Arc<Mutex<HashMap<String, Arc<Watched>>>>,
+}
+
+impl Discovery {
+    pub fn new(backend: impl RegistryBackend) -> Self {
+        Self::shared(Arc::new(backend))
+    }
+
+    /// [`crate::ServiceRegistry`] 와 같은 백엔드를 함께 쓸 때
+    pub fn shared(backend: Arc<dyn RegistryBackend>) -> Self {
+        Self {
+            backend,
+            refresh_interval: Duration::from_secs(10),
+            services: Arc::default(),
+        }
+    }
+
+    /// 변경 알림이 없어도 목록을 다시 읽는 간격. 만료로 빠진 인스턴스는 이 간격 안에 반영됩니다.
+    pub fn with_refresh_interval(mut self, interval: Duration) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    pub async fn instances(&self, service: &str) -> Result<Vec<ServiceInstance>, RegistryError> {
+        Ok(self.watched(service).await?.instances.read().unwrap().clone())
+    }
+
+    /// 이번 호출에 쓸 인스턴스
+    pub async fn resolve(&self, service: &str) -> Result<ServiceInstance, RegistryError> {
+        self.candidates(service)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| RegistryError::NoInstance(service.to_string()))
+    }
+
+    /// 고를 수 있는 인스턴스를 이번 호출의 순서대로 돌려줍니다. 첫 번째가 대상이고 나머지는 실패했을 때의 대안입니다.
+    pub async fn candidates(&self, service: &str) -> Result<Vec<ServiceInstance>, RegistryError> {
+        let watched = self.watched(service).await?;
+        let instances = watched.instances.read().unwrap();
+
+        let mut passing = instances.iter().filter(|instance| instance.health == Health::Passing).cloned().collect::<Vec<_>>();
+        let warning = instances.iter().filter(|instance| instance.health == Health::Warning).cloned();
+
+        if !passing.is_empty() {
+            let start = watched.cursor.fetch_add(1, Ordering::Relaxed) % passing.len();
+            passing.rotate_left(start);
+        }
+
+        Ok(passing.into_iter().chain(warning).collect())
+    }
+
+    async fn watched(&self, service: &str) -> Result<Arc<Watched>, RegistryError> {
+        if let Some(watched) = self.services.lock().unwrap().get(service) {
+            return Ok(watched.clone());
+        }
+
+        check_service(service)?;
+        let instances = self.backend.instances(service).await?;
+
+        // 읽는 사이 다른 호출이 먼저 등록했으면 그쪽을 쓴다.
+        let mut services = self.services.lock().unwrap();
+        if let Some(watched) = services.get(service) {
+            return Ok(watched.clone());
+        }
+
+        let watched = Arc::new(Watched {
+            instances: RwLock::new(instances),
+            cursor: AtomicUsize::new(0),
+        });
+        services.insert(service.to_string(), watched.clone());
+        tokio::spawn(follow(self.backend.clone(), service.to_string(), Arc::downgrade(&watched), self.refresh_interval));
+
+        Ok(watched)
+    }
+}
+
+impl fmt::Debug for Discovery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Discovery")
+            .field("refresh_interval", &self.refresh_interval)
+            .field("services", &self.services.lock().unwrap().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+// 변경 알림이나 재조회 간격 중 먼저 오는 쪽에 목록을 다시 읽는다. `Discovery` 가 버려지면 끝난다.
+async fn follow(backend: Arc<dyn RegistryBackend>, service: String, watched: Weak<Watched>, refresh_interval: Duration) {
+    let mut changes: Option<BoxStream<'static, ()>> = None;
+
+    loop {
+        match changes.as_mut() {
+            // 구독을 여는 사이 바뀐 것을 놓치지 않도록 구독 직후에 바로 다시 읽는다.
+            None => match backend.watch(&service).await {
+                Ok(stream) => changes = Some(stream),
+                Err(_) => sleep(refresh_interval).await,
+            },
+            Some(stream) => {
+                if let Ok(None) = timeout(refresh_interval, stream.next()).await {
+                    changes = None;
+                }
+            }
+        }
+
+        if watched.strong_count() == 0 {
+            return;
+        }
+
+        match backend.instances(&service).await {
+            Ok(instances) => match watched.upgrade() {
+                Some(watched) => *watched.instances.write().unwrap() = instances,
+                None => return,
+            },
+            Err(e) => eprintln!("[discovery] failed to refresh `{}`: {}", service, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemoryRegistry;
+
+    #[ntex::test]
+    async fn test_round_robin_over_healthy_instances_and_follows_changes() {
+        let backend = MemoryRegistry::new();
+        let ttl = Duration::from_secs(5);
+        let first = ServiceInstance::new("user-service", "10.0.0.1:80");
+        let second = ServiceInstance::new("user-service", "10.0.0.2:80");
+        backend.register(&first, ttl).await.unwrap();
+        backend.register(&second, ttl).await.unwrap();
+
+        let discovery = Discovery::new(backend.clone());
+        let picked = [
+            discovery.resolve("user-service").await.unwrap().address,
+            discovery.resolve("user-service").await.unwrap().address,
+            discovery.resolve("user-service").await.unwrap().address,
+        ];
+        assert_eq!(picked, ["10.0.0.1:80", "10.0.0.2:80", "10.0.0.1:80"]);
+
+        backend.register(&first.clone().with_health(Health::Critical), ttl).await.unwrap();
+        backend.register(&second.clone().with_health(Health::Warning), ttl).await.unwrap();
+        sleep(Duration::from_millis(20)).await;
+        assert_eq!(discovery.candidates("user-service").await.unwrap(), [second.clone().with_health(Health::Warning)]);
+
+        backend.deregister("user-service", &second.id).await.unwrap();
+        sleep(Duration::from_millis(20)).await;
+        assert!(matches!(discovery.resolve("user-service").await, Err(RegistryError::NoInstance(_))));
+    }
+}
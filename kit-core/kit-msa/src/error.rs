@@ -0,0 +1,90 @@
+use std::error::Error as StdError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    #[error("registry backend error: {0}")]
+    Backend(#[source] Box<dyn StdError + Send + Sync + 'static>),
+
+    #[error("invalid service name `{0}`")]
+    InvalidName(String),
+
+    #[error("no available instance of service `{0}`")]
+    NoInstance(String),
+}
+
+impl RegistryError {
+    pub(crate) fn backend(error: impl StdError + Send + Sync + 'static) -> Self {
+        RegistryError::Backend(Box::new(error))
+    }
+}
+
+impl From<sqlx::Error> for RegistryError {
+    fn from(error: sqlx::Error) -> Self {
+        RegistryError::backend(error)
+    }
+}
+
+impl From<serde_json::Error> for RegistryError {
+    fn from(error: serde_json::Error) -> Self {
+        RegistryError::backend(error)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RpcError {
+    #[error("rpc transport error: {0}")]
+    Nats(#[source] Box<dyn StdError + Send + Sync + 'static>),
+
+    #[error("invalid service name `{0}`")]
+    InvalidName(String),
+
+    #[error("no service is listening on `{0}`")]
+    NoResponders(String),
+
+    #[error("request to `{0}` timed out")]
+    Timeout(String),
+
+    #[error("failed to encode or decode rpc payload: {0}")]
+    Payload(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Remote(#[from] RemoteError),
+}
+
+impl RpcError {
+    pub(crate) fn nats(error: impl StdError + Send + Sync + 'static) -> Self {
+        RpcError::Nats(Box::new(error))
+    }
+}
+
+/// RPC 핸들러가 호출한 쪽에 돌려주는 오류
+///
+/// micro 프로토콜의 `Nats-Service-Error`, `Nats-Service-Error-Code` 헤더로 전달되며, 코드는 HTTP 상태 코드를 따릅니다.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("remote error {code}: {message}")]
+pub struct RemoteError {
+    pub code: u16,
+    pub message: String,
+}
+
+impl RemoteError {
+    pub fn new(code: u16, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(400, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(404, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(500, message)
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use crate::error::RegistryError;
+
+/// 인스턴스가 스스로 알리는 상태. 디스커버리는 `Passing` 을 먼저 고르고, 없을 때만 `Warning` 을 고릅니다.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Health {
+    #[default]
+    Passing,
+    Warning,
+    Critical,
+}
+
+impl Health {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Health::Passing => "passing",
+            Health::Warning => "warning",
+            Health::Critical => "critical",
+        }
+    }
+
+    // 알 수 없는 값은 고르지 않도록 `Critical` 로 읽는다.
+    pub(crate) fn parse(value: &str) -> Self {
+        match value {
+            "passing" => Health::Passing,
+            "warning" => Health::Warning,
+            _ => Health::Critical,
+        }
+    }
+}
+
+/// 레지스트리에 등록되는 서비스 인스턴스 하나
+///
+/// # 예시
+///
+/// ```ignore
+/// let instance = ServiceInstance::new("user-service", "10.0.0.7:8080")
+///     .with_metadata("version", env!("CARGO_PKG_VERSION"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceInstance {
+    pub service: String,
+    /// 서비스 안에서 인스턴스를 구분하는 값. 기본값은 주소입니다.
+    pub id: String,
+    /// `host:port`
+    pub address: String,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+    #[serde(default)]
+    pub health: Health,
+}
+
+impl ServiceInstance {
+    pub fn new(service: impl Into<String>, address: impl Into<String>) -> Self {
+        let address = address.into();
+
+        Self {
+            service: service.into(),
+            id: address.clone(),
+            address,
+            metadata: BTreeMap::new(),
+            health: Health::Passing,
+        }
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn with_health(mut self, health: Health) -> Self {
+        self.health = health;
+        self
+    }
+}
+
+/// 서비스 이름은 URL 호스트와 NATS subject 토큰으로 쓰이므로 영문 소문자, 숫자, `-`, `_` 만 허용합니다.
+pub(crate) fn check_service(name: &str) -> Result<(), RegistryError> {
+    let valid = !name.is_empty()
+        && name
+            .bytes()
+            .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'-' || byte == b'_');
+
+    match valid {
+        true => Ok(()),
+        false => Err(RegistryError::InvalidName(name.to_string())),
+    }
+}
@@ -1,14 +1,17 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+mod discovery;
+mod error;
+mod instance;
+mod memory;
+mod nats;
+mod postgres;
+mod registry;
+mod rpc;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use discovery::Discovery;
+pub use error::{RegistryError, RemoteError, RpcError};
+pub use instance::{Health, ServiceInstance};
+pub use memory::MemoryRegistry;
+pub use nats::NatsRegistry;
+pub use postgres::PgRegistry;
+pub use registry::{Registration, RegistryBackend, ServiceRegistry};
+pub use rpc::{Rpc, RpcClient, RpcServer, RpcService};
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use futures::future::{BoxFuture, ready};
+use futures::stream::{self, BoxStream, StreamExt};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use crate::error::RegistryError;
+use crate::instance::ServiceInstance;
+use crate::registry::RegistryBackend;
+
+type Instances = HashMap<String, HashMap<String, (ServiceInstance, Instant)>>;
+
+/// 한 프로세스 안에서만 유효한 레지스트리. 테스트와 단일 인스턴스 실행에 사용합니다.
+#[derive(Clone)]
+pub struct MemoryRegistry {
+    instances: Arc<Mutex<Instances>>,
+    // 바뀐 서비스 이름
+    changes: broadcast::Sender<String>,
+}
+
+impl MemoryRegistry {
+    pub fn new() -> Self {
+        Self {
+            instances: Arc::default(),
+            changes: broadcast::channel(64).0,
+        }
+    }
+}
+
+impl Default for MemoryRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegistryBackend for MemoryRegistry {
+    fn register<'a>(&'a self, instance: &'a ServiceInstance, ttl: Duration) -> BoxFuture<'a, Result<(), RegistryError>> {
+        let now = Instant::now();
+        let previous = self
+            .instances
+            .lock()
+            .unwrap()
+            .entry(instance.service.clone())
+            .or_default()
+            .insert(instance.id.clone(), (instance.clone(), now + ttl));
+
+        if previous.is_none_or(|(previous, expires_at)| previous != *instance || expires_at <= now) {
+            let _ = self.changes.send(instance.service.clone());
+        }
+
+        Box::pin(ready(Ok(())))
+    }
+
+    fn deregister<'a>(&'a self, service: &'a str, id: &'a str) -> BoxFuture<'a, Result<(), RegistryError>> {
+        let removed = self
+            .instances
+            .lock()
+            .unwrap()
+            .get_mut(service)
+            .and_then(|instances| instances.remove(id));
+
+        if removed.is_some() {
+            let _ = self.changes.send(service.to_string());
+        }
+
+        Box::pin(ready(Ok(())))
+    }
+
+    fn instances<'a>(&'a self, service: &'a str) -> BoxFuture<'a, Result<Vec<ServiceInstance>, RegistryError>> {
+        let now = Instant::now();
+        let mut instances = self
+            .instances
+            .lock()
+            .unwrap()
+            .get(service)
+            .map(|instances| {
+                instances
+                    .values()
+                    .filter(|(_, expires_at)| *expires_at > now)
+                    .map(|(instance, _)| instance.clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        instances.sort_by(|a, b| a.id.cmp(&b.id));
+
+        Box::pin(ready(Ok(instances)))
+    }
+
+    fn watch<'a>(&'a self, service: &'a str) -> BoxFuture<'a, Result<BoxStream<'static, ()>, RegistryError>> {
+        let receiver = self.changes.subscribe();
+        let service = service.to_string();
+
+        let changes = stream::unfold((receiver, service), |(mut receiver, service)| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(changed) if changed == service => return Some(((), (receiver, service))),
+                    Ok(_) => continue,
+                    // 놓친 알림에 이 서비스가 있었을 수 있다.
+                    Err(RecvError::Lagged(_)) => return Some(((), (receiver, service))),
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+
+        Box::pin(ready(Ok(changes.boxed())))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// 백엔드 공통 동작. Postgres, NATS 테스트도 같은 검사를 사용합니다.
+    pub(crate) async fn check_backend(backend: &dyn RegistryBackend, service: &str) {
+        let ttl = Duration::from_secs(5);
+        let first = ServiceInstance::new(service, "10.0.0.1:8080").with_metadata("zone", "a");
+        let second = ServiceInstance::new(service, "10.0.0.2:8080");
+        let mut changes = backend.watch(service).await.unwrap();
+
+        backend.register(&first, ttl).await.unwrap();
+        backend.register(&second, ttl).await.unwrap();
+        changes.next().await.unwrap();
+
+        let mut instances = backend.instances(service).await.unwrap();
+        instances.sort_by(|a, b| a.id.cmp(&b.id));
+        assert_eq!(instances, [first.clone(), second.clone()]);
+
+        backend.deregister(service, &first.id).await.unwrap();
+        assert_eq!(backend.instances(service).await.unwrap(), [second]);
+
+        backend.deregister(service, &instances[1].id).await.unwrap();
+        assert!(backend.instances(service).await.unwrap().is_empty());
+    }
+
+    #[ntex::test]
+    async fn test_memory_backend() {
+        check_backend(&MemoryRegistry::new(), "memory").await;
+    }
+
+    #[ntex::test]
+    async fn test_expired_instance_is_hidden() {
+        let backend = MemoryRegistry::new();
+        let instance = ServiceInstance::new("user-service", "10.0.0.1:8080");
+
+        backend.register(&instance, Duration::from_millis(20)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        assert!(backend.instances("user-service").await.unwrap().is_empty());
+    }
+}
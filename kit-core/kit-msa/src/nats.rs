@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use async_nats::jetstream::{self, kv};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream, StreamExt};
+use crate::error::RegistryError;
+use crate::instance::ServiceInstance;
+use crate::registry::RegistryBackend;
+
+/// NATS JetStream KV 버킷에 인스턴스를 저장하는 레지스트리
+///
+/// 키는 `<service>.<base64url(id)>` 이고, 만료는 버킷의 `max_age` 로 처리합니다.
+/// 그래서 `register` 의 `ttl` 은 쓰지 않으며, [`crate::ServiceRegistry::with_ttl`] 을 버킷을 만들 때의 `ttl` 과 맞춰야 합니다.
+#[derive(Clone)]
+pub struct NatsRegistry {
+    store: kv::Store,
+}
+
+impl NatsRegistry {
+    /// 버킷을 엽니다. 없으면 `ttl` 을 `max_age` 로 해서 만듭니다.
+    pub async fn open(client: async_nats::Client, bucket: &str, ttl: Duration) -> Result<Self, RegistryError> {
+        let jetstream = jetstream::new(client);
+
+        let store = match jetstream.get_key_value(bucket).await {
+            Ok(store) => store,
+            Err(_) => jetstream
+                .create_key_value(kv::Config {
+                    bucket: bucket.to_string(),
+                    description: "service instances".to_string(),
+                    history: 1,
+                    max_age: ttl,
+                    ..Default::default()
+                })
+                .await
+                .map_err(RegistryError::backend)?,
+        };
+
+        Ok(Self { store })
+    }
+}
+
+fn key(service: &str, id: &str) -> String {
+    format!("{}.{}", service, URL_SAFE_NO_PAD.encode(id))
+}
+
+impl RegistryBackend for NatsRegistry {
+    fn register<'a>(&'a self, instance: &'a ServiceInstance, _: Duration) -> BoxFuture<'a, Result<(), RegistryError>> {
+        Box::pin(async move {
+            self.store
+                .put(key(&instance.service, &instance.id), serde_json::to_vec(instance)?.into())
+                .await
+                .map_err(RegistryError::backend)?;
+
+            Ok(())
+        })
+    }
+
+    fn deregister<'a>(&'a self, service: &'a str, id: &'a str) -> BoxFuture<'a, Result<(), RegistryError>> {
+        Box::pin(async move {
+            self.store
+                .delete(key(service, id))
+                .await
+                .map_err(RegistryError::backend)
+        })
+    }
+
+    fn instances<'a>(&'a self, service: &'a str) -> BoxFuture<'a, Result<Vec<ServiceInstance>, RegistryError>> {
+        Box::pin(async move {
+            let prefix = format!("{}.", service);
+            let mut keys = self.store.keys().await.map_err(RegistryError::backend)?;
+            let mut instances = Vec::new();
+
+            while let Some(key) = keys.next().await {
+                let key = key.map_err(RegistryError::backend)?;
+                if !key.starts_with(&prefix) {
+                    continue;
+                }
+
+                // 목록을 읽는 사이 만료되거나 해제된 키는 건너뛴다.
+                if let Some(value) = self.store.get(key).await.map_err(RegistryError::backend)? {
+                    instances.push(serde_json::from_slice::<ServiceInstance>(&value)?);
+                }
+            }
+            instances.sort_by(|a, b| a.id.cmp(&b.id));
+
+            Ok(instances)
+        })
+    }
+
+    fn watch<'a>(&'a self, service: &'a str) -> BoxFuture<'a, Result<BoxStream<'static, ()>, RegistryError>> {
+        Box::pin(async move {
+            let watch = self
+                .store
+                .watch(format!("{}.*", service))
+                .await
+                .map_err(RegistryError::backend)?;
+
+            // 하트비트는 같은 값을 다시 쓰므로 키마다 마지막 값과 비교해 바뀐 것만 알린다.
+            let changes = stream::unfold((watch, HashMap::new()), |(mut watch, mut seen)| async move {
+                loop {
+                    let entry = match watch.next().await {
+                        Some(Ok(entry)) => entry,
+                        Some(Err(_)) | None => return None,
+                    };
+
+                    let changed = match entry.operation {
+                        kv::Operation::Put => seen.insert(entry.key, entry.value.clone()) != Some(entry.value),
+                        kv::Operation::Delete | kv::Operation::Purge => {
+                            seen.remove(&entry.key);
+                            true
+                        }
+                    };
+
+                    if changed {
+                        return Some(((), (watch, seen)));
+                    }
+                }
+            });
+
+            Ok(changes.boxed())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::tests::check_backend;
+
+    #[test]
+    fn test_key_is_a_valid_kv_key() {
+        let key = key("user-service", "10.0.0.1:8080");
+
+        assert!(key.starts_with("user-service."));
+        assert!(key.bytes().all(|byte| byte.is_ascii_alphanumeric() || b"-_.".contains(&byte)));
+    }
+
+    #[ntex::test]
+    #[ignore = "requires a local nats with jetstream"]
+    async fn test_nats_backend() {
+        let url = std::env::var("NATS_URL").unwrap_or_else(|_| "nats://127.0.0.1:4222".to_string());
+        let client = async_nats::connect(url).await.unwrap();
+        let backend = NatsRegistry::open(client, "kit-msa-test", Duration::from_secs(30)).await.unwrap();
+
+        check_backend(&backend, "kit-msa-test").await;
+    }
+}
@@ -0,0 +1,158 @@
+use std::time::Duration;
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream, StreamExt};
+use sqlx::PgPool;
+use sqlx::postgres::PgListener;
+use crate::error::RegistryError;
+use crate::instance::{Health, ServiceInstance};
+use crate::registry::RegistryBackend;
+
+// 바뀐 서비스 이름을 알리는 LISTEN/NOTIFY 채널
+const CHANNEL: &str = "service_instances";
+
+/// Postgres 테이블에 인스턴스를 저장하는 레지스트리
+///
+/// 등록할 때마다 만료 시각을 다시 잡고, 목록은 만료되지 않은 행만 읽습니다.
+/// 내용이 바뀌거나 해제될 때 `NOTIFY service_instances, '<service>'` 로 변경을 알립니다.
+#[derive(Debug, Clone)]
+pub struct PgRegistry {
+    pool: PgPool,
+}
+
+impl PgRegistry {
+    /// `service_instances` 테이블을 만드는 마이그레이션. 애플리케이션의 마이그레이션에 등록해 적용합니다.
+    pub const MIGRATION: &'static str = include_str!("../migrations/create_service_instances.sql");
+
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl RegistryBackend for PgRegistry {
+    fn register<'a>(&'a self, instance: &'a ServiceInstance, ttl: Duration) -> BoxFuture<'a, Result<(), RegistryError>> {
+        Box::pin(async move {
+            // CTE 는 갱신 전의 행을 보므로, 만료되지 않은 같은 내용이 있었는지로 변경 여부를 판단한다.
+            let changed = sqlx::query_scalar::<_, bool>(
+                r#"
+                WITH previous AS (
+                    SELECT address, metadata, health FROM service_instances
+                    WHERE service = $1 AND id = $2 AND expires_at > CURRENT_TIMESTAMP
+                )
+                INSERT INTO service_instances (service, id, address, metadata, health, expires_at)
+                VALUES ($1, $2, $3, $4::jsonb, $5, CURRENT_TIMESTAMP + make_interval(secs => $6))
+                ON CONFLICT (service, id) DO UPDATE
+                SET address = EXCLUDED.address,
+                    metadata = EXCLUDED.metadata,
+                    health = EXCLUDED.health,
+                    expires_at = EXCLUDED.expires_at
+                RETURNING NOT EXISTS (
+                    SELECT 1 FROM previous WHERE address = $3 AND metadata = $4::jsonb AND health = $5
+                )
+                "#
+            )
+                .bind(&instance.service)
+                .bind(&instance.id)
+                .bind(&instance.address)
+                .bind(serde_json::to_string(&instance.metadata)?)
+                .bind(instance.health.as_str())
+                .bind(ttl.as_secs_f64())
+                .fetch_one(&self.pool)
+                .await?;
+
+            if changed {
+                sqlx::query("SELECT pg_notify($1, $2)")
+                    .bind(CHANNEL)
+                    .bind(&instance.service)
+                    .execute(&self.pool)
+                    .await?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn deregister<'a>(&'a self, service: &'a str, id: &'a str) -> BoxFuture<'a, Result<(), RegistryError>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                WITH deleted AS (
+                    DELETE FROM service_instances WHERE service = $2 AND id = $3 RETURNING service
+                )
+                SELECT pg_notify($1, service) FROM deleted
+                "#
+            )
+                .bind(CHANNEL)
+                .bind(service)
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn instances<'a>(&'a self, service: &'a str) -> BoxFuture<'a, Result<Vec<ServiceInstance>, RegistryError>> {
+        Box::pin(async move {
+            let rows = sqlx::query_as::<_, (String, String, String, String, String)>(
+                r#"
+                SELECT service, id, address, metadata::text, health FROM service_instances
+                WHERE service = $1 AND expires_at > CURRENT_TIMESTAMP
+                ORDER BY id
+                "#
+            )
+                .bind(service)
+                .fetch_all(&self.pool)
+                .await?;
+
+            rows.into_iter()
+                .map(|(service, id, address, metadata, health)| {
+                    Ok(ServiceInstance {
+                        service,
+                        id,
+                        address,
+                        metadata: serde_json::from_str(&metadata)?,
+                        health: Health::parse(&health),
+                    })
+                })
+                .collect()
+        })
+    }
+
+    fn watch<'a>(&'a self, service: &'a str) -> BoxFuture<'a, Result<BoxStream<'static, ()>, RegistryError>> {
+        Box::pin(async move {
+            let mut listener = PgListener::connect_with(&self.pool).await?;
+            listener.listen(CHANNEL).await?;
+
+            // 연결이 끊기면 그 사이의 알림을 놓쳤을 수 있으므로 스트림을 끝내 다시 구독하게 한다.
+            let changes = stream::unfold((listener, service.to_string()), |(mut listener, service)| async move {
+                loop {
+                    match listener.try_recv().await {
+                        Ok(Some(notification)) if notification.payload() == service => {
+                            return Some(((), (listener, service)));
+                        }
+                        Ok(Some(_)) => continue,
+                        Ok(None) | Err(_) => return None,
+                    }
+                }
+            });
+
+            Ok(changes.boxed())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::tests::check_backend;
+
+    #[ntex::test]
+    #[ignore = "requires a local postgres"]
+    async fn test_postgres_backend() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+        let backend = PgRegistry::new(PgPool::connect(&url).await.unwrap());
+        sqlx::raw_sql(PgRegistry::MIGRATION).execute(&backend.pool).await.unwrap();
+
+        check_backend(&backend, "kit-msa-test").await;
+    }
+}
@@ -0,0 +1,158 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use crate::error::RegistryError;
+use crate::instance::{Health, ServiceInstance, check_service};
+
+/// 인스턴스 목록 저장소
+pub trait RegistryBackend: Send + Sync + 'static {
+    /// 인스턴스를 등록하거나 갱신합니다. `ttl` 안에 다시 등록하지 않으면 목록에서 빠집니다.
+    fn register<'a>(&'a self, instance: &'a ServiceInstance, ttl: Duration) -> BoxFuture<'a, Result<(), RegistryError>>;
+
+    fn deregister<'a>(&'a self, service: &'a str, id: &'a str) -> BoxFuture<'a, Result<(), RegistryError>>;
+
+    /// 만료되지 않은 인스턴스
+    fn instances<'a>(&'a self, service: &'a str) -> BoxFuture<'a, Result<Vec<ServiceInstance>, RegistryError>>;
+
+    /// 서비스의 인스턴스가 추가, 변경, 해제될 때마다 값을 내는 스트림
+    ///
+    /// 같은 내용으로 다시 등록하는 하트비트와 만료로 빠지는 것은 알리지 않을 수 있으므로,
+    /// 받는 쪽은 주기적으로도 목록을 다시 읽어야 합니다.
+    fn watch<'a>(&'a self, service: &'a str) -> BoxFuture<'a, Result<BoxStream<'static, ()>, RegistryError>>;
+}
+
+/// 이 프로세스의 인스턴스를 등록하고 하트비트로 유지합니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// let registry = ServiceRegistry::new(PgRegistry::new(pool));
+/// let registration = registry.register(ServiceInstance::new("user-service", "10.0.0.7:8080")).await?;
+/// ...
+/// registration.deregister().await?;
+/// ```
+#[derive(Clone)]
+pub struct ServiceRegistry {
+    backend: Arc<dyn RegistryBackend>,
+    ttl: Duration,
+}
+
+impl ServiceRegistry {
+    pub fn new(backend: impl RegistryBackend) -> Self {
+        Self::shared(Arc::new(backend))
+    }
+
+    /// [`crate::Discovery`] 와 같은 백엔드를 함께 쓸 때
+    pub fn shared(backend: Arc<dyn RegistryBackend>) -> Self {
+        Self {
+            backend,
+            ttl: Duration::from_secs(15),
+        }
+    }
+
+    /// 하트비트가 끊긴 인스턴스가 목록에서 빠지기까지의 시간. 하트비트는 이 시간의 1/3 마다 보냅니다.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub async fn register(&self, instance: ServiceInstance) -> Result<Registration, RegistryError> {
+        check_service(&instance.service)?;
+        self.backend.register(&instance, self.ttl).await?;
+
+        let instance = Arc::new(Mutex::new(instance));
+        let changed = Arc::new(Notify::new());
+        let heartbeat = tokio::spawn(heartbeat(self.backend.clone(), instance.clone(), changed.clone(), self.ttl));
+
+        Ok(Registration {
+            backend: self.backend.clone(),
+            instance,
+            changed,
+            heartbeat,
+        })
+    }
+}
+
+/// 등록한 인스턴스. 버리면 하트비트만 멈추고, 레지스트리에서는 `ttl` 뒤에 빠집니다.
+pub struct Registration {
+    backend: Arc<dyn RegistryBackend>,
+    instance: Arc<Mutex<ServiceInstance>>,
+    changed: Arc<Notify>,
+    heartbeat: JoinHandle<()>,
+}
+
+impl Registration {
+    pub fn instance(&self) -> ServiceInstance {
+        self.instance.lock().unwrap().clone()
+    }
+
+    /// 상태가 바뀌면 다음 하트비트를 기다리지 않고 바로 다시 등록합니다.
+    pub fn set_health(&self, health: Health) {
+        let mut instance = self.instance.lock().unwrap();
+
+        if instance.health != health {
+            instance.health = health;
+            self.changed.notify_one();
+        }
+    }
+
+    /// 하트비트를 멈추고 레지스트리에서 바로 뺍니다. 종료할 때 호출합니다.
+    pub async fn deregister(self) -> Result<(), RegistryError> {
+        self.heartbeat.abort();
+        let instance = self.instance();
+
+        self.backend.deregister(&instance.service, &instance.id).await
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.heartbeat.abort();
+    }
+}
+
+async fn heartbeat(backend: Arc<dyn RegistryBackend>, instance: Arc<Mutex<ServiceInstance>>, changed: Arc<Notify>, ttl: Duration) {
+    loop {
+        let _ = timeout(ttl / 3, changed.notified()).await;
+
+        let current = instance.lock().unwrap().clone();
+        if let Err(e) = backend.register(&current, ttl).await {
+            eprintln!("[registry] heartbeat for `{}` failed: {}", current.service, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemoryRegistry;
+
+    #[ntex::test]
+    async fn test_heartbeat_keeps_instance_until_deregistered() {
+        let backend = MemoryRegistry::new();
+        let registry = ServiceRegistry::new(backend.clone()).with_ttl(Duration::from_millis(90));
+
+        let registration = registry.register(ServiceInstance::new("user-service", "10.0.0.1:80")).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(backend.instances("user-service").await.unwrap().len(), 1);
+
+        registration.set_health(Health::Critical);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(backend.instances("user-service").await.unwrap()[0].health, Health::Critical);
+
+        registration.deregister().await.unwrap();
+        assert!(backend.instances("user-service").await.unwrap().is_empty());
+    }
+
+    #[ntex::test]
+    async fn test_rejects_invalid_service_name() {
+        let registry = ServiceRegistry::new(MemoryRegistry::new());
+        let result = registry.register(ServiceInstance::new("User Service", "10.0.0.1:80")).await;
+
+        assert!(matches!(result, Err(RegistryError::InvalidName(_))));
+    }
+}
@@ -0,0 +1,410 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use async_nats::service::error::Error as ServiceError;
+use async_nats::service::{Info, NATS_SERVICE_ERROR, NATS_SERVICE_ERROR_CODE, PingResponse, Service, ServiceExt, Stats};
+use async_nats::{Client, Message, RequestErrorKind};
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::{FutureExt, StreamExt};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, timeout_at};
+use crate::error::{RemoteError, RpcError};
+use crate::instance::check_service;
+
+// `$SRV` 질의에 여러 인스턴스가 답하므로 이 시간 동안 온 응답을 모은다.
+const GATHER: Duration = Duration::from_millis(500);
+
+/// 요청 타입 하나가 RPC 메서드 하나인 계약
+///
+/// subject 는 `<SERVICE>.<METHOD>` 이고 요청과 응답은 JSON 으로 주고받습니다.
+/// 계약 타입은 호출하는 쪽과 처리하는 쪽이 함께 쓰는 크레이트에 둡니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize)]
+/// struct FindUser {
+///     id: i32,
+/// }
+///
+/// impl Rpc for FindUser {
+///     type Response = UserView;
+///     const SERVICE: &'static str = "user-service";
+///     const METHOD: &'static str = "find-user";
+/// }
+/// ```
+pub trait Rpc: Serialize + DeserializeOwned + Send + 'static {
+    type Response: Serialize + DeserializeOwned + Send + 'static;
+
+    const SERVICE: &'static str;
+
+    const METHOD: &'static str;
+
+    fn subject() -> String {
+        format!("{}.{}", Self::SERVICE, Self::METHOD)
+    }
+}
+
+/// 계약 타입으로 다른 서비스를 호출하는 클라이언트
+///
+/// 같은 서비스의 인스턴스들은 같은 큐 그룹으로 구독하므로 요청마다 한 인스턴스만 받습니다.
+#[derive(Clone)]
+pub struct RpcClient {
+    client: Client,
+    timeout: Duration,
+}
+
+impl RpcClient {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// 응답을 기다리는 시간. 넘으면 [`RpcError::Timeout`] 입니다.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub async fn call<R: Rpc>(&self, request: &R) -> Result<R::Response, RpcError> {
+        let subject = R::subject();
+        let payload = serde_json::to_vec(request)?;
+
+        let reply = self
+            .client
+            .send_request(
+                subject.clone(),
+                async_nats::Request::new()
+                    .payload(payload.into())
+                    .timeout(Some(self.timeout)),
+            )
+            .await
+            .map_err(|e| match e.kind() {
+                RequestErrorKind::TimedOut => RpcError::Timeout(subject.clone()),
+                RequestErrorKind::NoResponders => RpcError::NoResponders(subject.clone()),
+                RequestErrorKind::Other => RpcError::nats(e),
+            })?;
+
+        decode_reply(&reply)
+    }
+
+    /// 실행 중인 `service` 인스턴스들. 서비스 이름을 비우면 모든 서비스입니다.
+    pub async fn ping(&self, service: &str) -> Result<Vec<PingResponse>, RpcError> {
+        self.gather("PING", service).await
+    }
+
+    /// 인스턴스마다의 설명과 엔드포인트 목록
+    pub async fn info(&self, service: &str) -> Result<Vec<Info>, RpcError> {
+        self.gather("INFO", service).await
+    }
+
+    /// 인스턴스마다의 엔드포인트별 요청 수, 오류 수, 처리 시간
+    pub async fn stats(&self, service: &str) -> Result<Vec<Stats>, RpcError> {
+        self.gather("STATS", service).await
+    }
+
+    async fn gather<T: DeserializeOwned>(&self, verb: &str, service: &str) -> Result<Vec<T>, RpcError> {
+        let subject = match service {
+            "" => format!("$SRV.{}", verb),
+            service => format!("$SRV.{}.{}", verb, service),
+        };
+        let inbox = self.client.new_inbox();
+        let mut replies = self.client.subscribe(inbox.clone()).await.map_err(RpcError::nats)?;
+
+        self.client
+            .publish_with_reply(subject, inbox, Bytes::new())
+            .await
+            .map_err(RpcError::nats)?;
+
+        let deadline = Instant::now() + GATHER;
+        let mut responses = Vec::new();
+        while let Ok(Some(reply)) = timeout_at(deadline, replies.next()).await {
+            responses.push(serde_json::from_slice(&reply.payload)?);
+        }
+
+        Ok(responses)
+    }
+}
+
+// 오류 응답은 micro 프로토콜대로 본문 없이 헤더로 온다.
+fn decode_reply<T: DeserializeOwned>(reply: &Message) -> Result<T, RpcError> {
+    if let Some(message) = reply.headers.as_ref().and_then(|headers| headers.get(NATS_SERVICE_ERROR)) {
+        let code = reply
+            .headers
+            .as_ref()
+            .and_then(|headers| headers.get(NATS_SERVICE_ERROR_CODE))
+            .and_then(|code| code.as_str().parse().ok())
+            .unwrap_or(500);
+
+        return Err(RpcError::Remote(RemoteError::new(code, message.as_str())));
+    }
+
+    Ok(serde_json::from_slice(&reply.payload)?)
+}
+
+type Handler = Arc<dyn Fn(Bytes) -> BoxFuture<'static, Result<Bytes, RemoteError>> + Send + Sync>;
+
+/// 계약 타입의 요청을 처리하는 NATS micro 서비스
+///
+/// 시작하면 핸들러마다 `<SERVICE>.<METHOD>` 를 큐 그룹으로 구독하고,
+/// `$SRV.PING`, `$SRV.INFO`, `$SRV.STATS` 에도 답해서 `nats micro ls` 같은 도구로 찾고 살펴볼 수 있습니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// let service = RpcServer::new("user-service", env!("CARGO_PKG_VERSION"))
+///     .handle(move |request: FindUser| {
+///         let repository = repository.clone();
+///         async move { repository.find(request.id).await.map_err(|_| RemoteError::not_found("user not found")) }
+///     })
+///     .start(client)
+///     .await?;
+/// ```
+pub struct RpcServer {
+    name: String,
+    version: String,
+    description: Option<String>,
+    queue_group: Option<String>,
+    handlers: Vec<(String, Handler)>,
+}
+
+impl RpcServer {
+    /// `version` 은 micro 프로토콜에 따라 SemVer 형식이어야 합니다.
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            description: None,
+            queue_group: None,
+            handlers: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// 요청을 나눠 받을 큐 그룹. 기본값은 micro 프로토콜의 기본값인 `q` 입니다.
+    pub fn queue_group(mut self, queue_group: impl Into<String>) -> Self {
+        self.queue_group = Some(queue_group.into());
+        self
+    }
+
+    /// `R` 요청을 처리할 핸들러를 등록합니다. 요청은 도착하는 대로 동시에 처리합니다.
+    pub fn handle<R, F, Fut>(mut self, handler: F) -> Self
+    where
+        R: Rpc,
+        F: Fn(R) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R::Response, RemoteError>> + Send + 'static,
+    {
+        self.handlers.push((R::subject(), typed(handler)));
+        self
+    }
+
+    pub async fn start(self, client: Client) -> Result<RpcService, RpcError> {
+        if check_service(&self.name).is_err() {
+            return Err(RpcError::InvalidName(self.name));
+        }
+
+        let mut builder = client.service_builder();
+        if let Some(description) = self.description {
+            builder = builder.description(description);
+        }
+        if let Some(queue_group) = self.queue_group {
+            builder = builder.queue_group(queue_group);
+        }
+        let service = builder.start(self.name, self.version).await.map_err(RpcError::Nats)?;
+
+        let mut tasks = Vec::with_capacity(self.handlers.len());
+        for (subject, handler) in self.handlers {
+            let endpoint = match service.endpoint(subject).await {
+                Ok(endpoint) => endpoint,
+                Err(e) => {
+                    tasks.iter().for_each(JoinHandle::abort);
+                    let _ = service.stop().await;
+                    return Err(RpcError::Nats(e));
+                }
+            };
+
+            tasks.push(tokio::spawn(endpoint.for_each_concurrent(None, move |request| {
+                let handler = handler.clone();
+
+                async move {
+                    // 응답 받을 곳이 없는 publish 는 처리하지 않는다.
+                    if request.message.reply.is_none() {
+                        return;
+                    }
+
+                    let response = handler(request.message.payload.clone()).await.map_err(|e| ServiceError {
+                        status: e.message,
+                        code: e.code as usize,
+                    });
+
+                    if let Err(e) = request.respond(response).await {
+                        eprintln!("[rpc] failed to respond on `{}`: {}", request.message.subject, e);
+                    }
+                }
+            })));
+        }
+
+        Ok(RpcService {
+            service: Some(service),
+            tasks,
+        })
+    }
+}
+
+// 요청을 풀지 못하면 핸들러를 부르지 않고 400 으로 답한다.
+fn typed<R, F, Fut>(handler: F) -> Handler
+where
+    R: Rpc,
+    F: Fn(R) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<R::Response, RemoteError>> + Send + 'static,
+{
+    Arc::new(move |payload: Bytes| {
+        let request = match serde_json::from_slice::<R>(&payload) {
+            Ok(request) => request,
+            Err(e) => return futures::future::ready(Err(RemoteError::bad_request(e.to_string()))).boxed(),
+        };
+        let response = handler(request);
+
+        async move {
+            let response = response.await?;
+            serde_json::to_vec(&response)
+                .map(Bytes::from)
+                .map_err(|e| RemoteError::internal(e.to_string()))
+        }
+        .boxed()
+    })
+}
+
+/// 실행 중인 [`RpcServer`]. 버리면 구독을 모두 해제하며, 같은 이름의 다른 인스턴스는 계속 요청을 받습니다.
+pub struct RpcService {
+    service: Option<Service>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl RpcService {
+    pub async fn info(&self) -> Option<Info> {
+        Some(self.service.as_ref()?.info().await)
+    }
+
+    /// 처리 중인 요청을 버리고 구독을 모두 해제합니다.
+    pub async fn stop(mut self) -> Result<(), RpcError> {
+        self.tasks.drain(..).for_each(|task| task.abort());
+
+        match self.service.take() {
+            Some(service) => service.stop().await.map_err(RpcError::Nats),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for RpcService {
+    fn drop(&mut self) {
+        self.tasks.iter().for_each(JoinHandle::abort);
+
+        // `$SRV` 구독은 서비스의 백그라운드 작업이 들고 있으므로 멈추라고 알려야 한다.
+        if let (Some(service), Ok(runtime)) = (self.service.take(), tokio::runtime::Handle::try_current()) {
+            runtime.spawn(async move {
+                let _ = service.stop().await;
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_nats::HeaderMap;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Echo {
+        text: String,
+    }
+
+    impl Rpc for Echo {
+        type Response = Echo;
+        const SERVICE: &'static str = "kit-msa-test";
+        const METHOD: &'static str = "echo";
+    }
+
+    fn reply(payload: &'static [u8], headers: Option<HeaderMap>) -> Message {
+        Message {
+            subject: "_INBOX.test".into(),
+            reply: None,
+            payload: Bytes::from_static(payload),
+            headers,
+            status: None,
+            description: None,
+            length: payload.len(),
+        }
+    }
+
+    #[test]
+    fn test_decode_reply() {
+        let mut headers = HeaderMap::new();
+        headers.insert(NATS_SERVICE_ERROR, "user not found");
+        headers.insert(NATS_SERVICE_ERROR_CODE, "404");
+
+        let echo = decode_reply::<Echo>(&reply(br#"{"text":"hi"}"#, None)).unwrap();
+        let error = decode_reply::<Echo>(&reply(b"", Some(headers))).unwrap_err();
+
+        assert_eq!(echo.text, "hi");
+        assert!(matches!(error, RpcError::Remote(remote) if remote == RemoteError::not_found("user not found")));
+    }
+
+    #[ntex::test]
+    async fn test_typed_handler_rejects_malformed_request() {
+        let handler = typed(|request: Echo| async move {
+            match request.text.as_str() {
+                "" => Err(RemoteError::bad_request("text is empty")),
+                _ => Ok(request),
+            }
+        });
+
+        assert_eq!(handler(Bytes::from_static(br#"{"text":"hi"}"#)).await.unwrap(), r#"{"text":"hi"}"#);
+        assert_eq!(handler(Bytes::from_static(br#"{"text":""}"#)).await.unwrap_err().message, "text is empty");
+        assert_eq!(handler(Bytes::from_static(b"not json")).await.unwrap_err().code, 400);
+    }
+
+    #[ntex::test]
+    #[ignore = "requires a local nats"]
+    async fn test_call_over_nats() {
+        let url = std::env::var("NATS_URL").unwrap_or_else(|_| "nats://127.0.0.1:4222".to_string());
+        let client = async_nats::connect(url).await.unwrap();
+        let rpc = RpcClient::new(client.clone()).with_timeout(Duration::from_secs(1));
+
+        assert!(matches!(rpc.call(&Echo { text: "hi".into() }).await, Err(RpcError::NoResponders(_))));
+
+        let service = RpcServer::new("kit-msa-test", "0.1.0")
+            .handle(|request: Echo| async move {
+                match request.text.as_str() {
+                    "" => Err(RemoteError::bad_request("text is empty")),
+                    _ => Ok(request),
+                }
+            })
+            .start(client)
+            .await
+            .unwrap();
+
+        let echo = rpc.call(&Echo { text: "hi".into() }).await.unwrap();
+        let error = rpc.call(&Echo { text: String::new() }).await.unwrap_err();
+        assert_eq!(echo.text, "hi");
+        assert!(matches!(error, RpcError::Remote(remote) if remote.code == 400));
+
+        let stats = rpc.stats("kit-msa-test").await.unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].endpoints[0].subject, "kit-msa-test.echo");
+        assert_eq!((stats[0].endpoints[0].requests, stats[0].endpoints[0].errors), (2, 1));
+
+        service.stop().await.unwrap();
+    }
+}
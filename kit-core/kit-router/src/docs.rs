@@ -0,0 +1,97 @@
+use std::sync::Arc;
+use ntex::web::{self, HttpResponse, ServiceConfig};
+use serde_json::Value;
+
+const SPEC_PATH: &str = "/openapi.json";
+
+const SWAGGER_UI: &str = r##"<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="utf-8">
+    <title>API Docs</title>
+    <link rel="stylesheet" href="/docs/swagger-ui.css">
+</head>
+<body>
+    <div id="swagger-ui"></div>
+    <script src="/docs/swagger-ui-bundle.js"></script>
+    <script>
+        window.onload = () => {
+            window.ui = SwaggerUIBundle({ url: "/openapi.json", dom_id: "#swagger-ui" });
+        };
+    </script>
+</body>
+</html>
+"##;
+
+// Swagger UI 5.17.14 배포본 (Apache-2.0, assets/swagger-ui/LICENSE)
+const SWAGGER_UI_BUNDLE: &str = include_str!("../assets/swagger-ui/swagger-ui-bundle.js");
+const SWAGGER_UI_CSS: &str = include_str!("../assets/swagger-ui/swagger-ui.css");
+
+/// OpenAPI 문서와 문서 화면을 제공합니다.
+///
+/// - `GET /openapi.json`: 문서 원본
+/// - `GET /docs`: Swagger UI
+///
+/// 화면의 스크립트와 스타일은 바이너리에 포함해 `/docs/` 아래에서 제공하므로 외부 CDN 에 접근하지 못하는 환경에서도 열립니다.
+#[derive(Debug, Clone)]
+pub struct ApiDocs {
+    spec: Arc<String>,
+}
+
+impl ApiDocs {
+    pub fn new(spec: &Value) -> Self {
+        Self {
+            spec: Arc::new(serde_json::to_string(spec).unwrap_or_default()),
+        }
+    }
+
+    pub fn configure(&self, cfg: &mut ServiceConfig) {
+        let spec = self.spec.clone();
+
+        cfg.route(
+            SPEC_PATH,
+            web::get().to(move || {
+                let spec = spec.clone();
+                async move {
+                    HttpResponse::Ok()
+                        .content_type("application/json")
+                        .body(spec.as_str().to_string())
+                }
+            }),
+        )
+        .route("/docs", web::get().to(|| async { asset("text/html; charset=utf-8", SWAGGER_UI) }))
+        .route(
+            "/docs/swagger-ui-bundle.js",
+            web::get().to(|| async { asset("application/javascript; charset=utf-8", SWAGGER_UI_BUNDLE) }),
+        )
+        .route("/docs/swagger-ui.css", web::get().to(|| async { asset("text/css; charset=utf-8", SWAGGER_UI_CSS) }));
+    }
+}
+
+fn asset(content_type: &'static str, body: &'static str) -> HttpResponse {
+    HttpResponse::Ok().content_type(content_type).body(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::web::{App, test};
+    use serde_json::json;
+
+    #[ntex::test]
+    async fn test_serves_spec_and_pages() {
+        let docs = ApiDocs::new(&json!({ "openapi": "3.1.0" }));
+        let app = test::init_service(App::new().configure(|cfg| docs.configure(cfg))).await;
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/openapi.json").to_request()).await;
+        assert_eq!(test::read_body(response).await, r#"{"openapi":"3.1.0"}"#);
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/docs").to_request()).await;
+        let page = test::read_body(response).await;
+        assert!(std::str::from_utf8(&page).unwrap().contains("src=\"/docs/swagger-ui-bundle.js\""));
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/docs/swagger-ui-bundle.js").to_request()).await;
+        assert_eq!(response.headers().get("content-type").unwrap(), "application/javascript; charset=utf-8");
+        assert!(test::read_body(response).await.starts_with(b"/*!"));
+    }
+}
@@ -0,0 +1,302 @@
+use std::any::type_name;
+use std::sync::Arc;
+use ntex::http::{Method, RequestHead};
+use ntex::service::boxed::{self, BoxService};
+use ntex::service::{Middleware, Service};
+use ntex::web::guard::Guard;
+use ntex::web::{self, DefaultError, FromRequest, Handler, Route, ServiceConfig, WebRequest, WebResponse};
+use crate::operation::Operation;
+use crate::pattern::MarkPattern;
+use crate::table::RouteInfo;
+use crate::version::Versioning;
+
+type RouteFactory = Arc<dyn Fn() -> Route + Send + Sync>;
+type SharedGuard = Arc<dyn Guard + Send + Sync>;
+type WebService = BoxService<WebRequest<DefaultError>, WebResponse, web::Error>;
+type Wrap = Arc<dyn Fn(WebService) -> WebService + Send + Sync>;
+
+#[derive(Clone)]
+struct RouteEntry {
+    method: Method,
+    path: String,
+    handler: &'static str,
+    factory: RouteFactory,
+    operation: Operation,
+}
+
+/// 같은 경로 접두사, 버전, 가드, 미들웨어를 공유하는 라우트 묶음
+///
+/// # 예시
+///
+/// ```ignore
+/// RouteGroup::new("/user")
+///     .version(1)
+///     .guard(guard::Header("x-user-id", "..."))
+///     .wrap(RequestLogger)
+///     .post("", create_user)
+///     .get("/{id}", find_user)
+///     .group(RouteGroup::new("/admin").delete("/{id}", delete_user))
+/// ```
+#[derive(Clone, Default)]
+pub struct RouteGroup {
+    prefix: String,
+    version: Option<u16>,
+    tag: Option<String>,
+    routes: Vec<RouteEntry>,
+    groups: Vec<RouteGroup>,
+    guards: Vec<SharedGuard>,
+    middleware: Vec<Wrap>,
+}
+
+impl RouteGroup {
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: normalize(&prefix.into()),
+            ..Default::default()
+        }
+    }
+
+    /// 그룹의 API 버전. 경로 방식이면 `/v{version}` 이 접두사 앞에 붙습니다.
+    ///
+    /// 중첩된 그룹은 바깥 그룹의 버전을 따릅니다.
+    pub fn version(mut self, version: u16) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    pub fn route<H, Args>(mut self, method: Method, path: &str, handler: H) -> Self
+    where
+        H: Handler<Args, DefaultError> + Clone + Send + Sync + 'static,
+        Args: FromRequest<DefaultError> + 'static,
+        Args::Error: Into<<DefaultError as web::ErrorRenderer>::Container>,
+    {
+        let route_method = method.clone();
+
+        self.routes.push(RouteEntry {
+            method,
+            path: normalize(path),
+            handler: type_name::<H>(),
+            factory: Arc::new(move || web::route().method(route_method.clone()).to(handler.clone())),
+            operation: Operation::default(),
+        });
+        self
+    }
+
+    /// 직전에 추가한 라우트의 OpenAPI 문서. 그룹에 라우트가 없으면 아무것도 하지 않습니다.
+    pub fn doc(mut self, operation: Operation) -> Self {
+        if let Some(route) = self.routes.last_mut() {
+            route.operation = operation;
+        }
+        self
+    }
+
+    /// 문서에서 그룹의 라우트를 묶을 태그. 하위 그룹은 자신의 태그가 없으면 이 태그를 사용합니다.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    pub fn get<H, Args>(self, path: &str, handler: H) -> Self
+    where
+        H: Handler<Args, DefaultError> + Clone + Send + Sync + 'static,
+        Args: FromRequest<DefaultError> + 'static,
+        Args::Error: Into<<DefaultError as web::ErrorRenderer>::Container>,
+    {
+        self.route(Method::GET, path, handler)
+    }
+
+    pub fn post<H, Args>(self, path: &str, handler: H) -> Self
+    where
+        H: Handler<Args, DefaultError> + Clone + Send + Sync + 'static,
+        Args: FromRequest<DefaultError> + 'static,
+        Args::Error: Into<<DefaultError as web::ErrorRenderer>::Container>,
+    {
+        self.route(Method::POST, path, handler)
+    }
+
+    pub fn put<H, Args>(self, path: &str, handler: H) -> Self
+    where
+        H: Handler<Args, DefaultError> + Clone + Send + Sync + 'static,
+        Args: FromRequest<DefaultError> + 'static,
+        Args::Error: Into<<DefaultError as web::ErrorRenderer>::Container>,
+    {
+        self.route(Method::PUT, path, handler)
+    }
+
+    pub fn patch<H, Args>(self, path: &str, handler: H) -> Self
+    where
+        H: Handler<Args, DefaultError> + Clone + Send + Sync + 'static,
+        Args: FromRequest<DefaultError> + 'static,
+        Args::Error: Into<<DefaultError as web::ErrorRenderer>::Container>,
+    {
+        self.route(Method::PATCH, path, handler)
+    }
+
+    pub fn delete<H, Args>(self, path: &str, handler: H) -> Self
+    where
+        H: Handler<Args, DefaultError> + Clone + Send + Sync + 'static,
+        Args: FromRequest<DefaultError> + 'static,
+        Args::Error: Into<<DefaultError as web::ErrorRenderer>::Container>,
+    {
+        self.route(Method::DELETE, path, handler)
+    }
+
+    /// 하위 그룹을 추가합니다. 하위 그룹은 이 그룹의 접두사, 가드, 미들웨어를 이어받습니다.
+    pub fn group(mut self, group: RouteGroup) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    /// 그룹의 모든 라우트에 적용할 가드. 가드를 통과하지 못하면 다음 그룹에서 경로를 찾습니다.
+    pub fn guard(mut self, guard: impl Guard + Send + Sync + 'static) -> Self {
+        self.guards.push(Arc::new(guard));
+        self
+    }
+
+    /// 그룹의 모든 라우트에 적용할 미들웨어. ntex 와 마찬가지로 나중에 추가한 미들웨어가 바깥에서 실행됩니다.
+    ///
+    /// 미들웨어는 그룹의 라우트마다 씌우므로, 그룹에 없는 경로는 미들웨어를 거치지 않고 다음 라우트에서 찾습니다.
+    pub fn wrap<M>(mut self, middleware: M) -> Self
+    where
+        M: Middleware<WebService> + Send + Sync + 'static,
+        M::Service: Service<WebRequest<DefaultError>, Response = WebResponse, Error = web::Error> + 'static,
+    {
+        self.middleware.push(Arc::new(move |service| boxed::service(middleware.create(service))));
+        self
+    }
+
+    pub(crate) fn routes(
+        &self,
+        versioning: &Versioning,
+        parent: &str,
+        version: Option<u16>,
+        tag: Option<&str>,
+        table: &mut Vec<RouteInfo>,
+    ) {
+        let prefix = match (version, self.version) {
+            (None, Some(_)) => format!("{}{}{}", parent, versioning.prefix(self.version), self.prefix),
+            _ => format!("{}{}", parent, self.prefix),
+        };
+        let version = version.or(self.version);
+        let tag = self.tag.as_deref().or(tag);
+
+        for route in &self.routes {
+            let mut operation = route.operation.clone();
+            if let Some(tag) = tag
+                && operation.tags.is_empty()
+            {
+                operation.tags.push(tag.to_string());
+            }
+
+            table.push(RouteInfo {
+                method: route.method.to_string(),
+                path: join(&prefix, &route.path),
+                version,
+                handler: route.handler,
+                operation,
+            });
+        }
+
+        for group in &self.groups {
+            group.routes(versioning, &prefix, version, tag, table);
+        }
+    }
+
+    // 스코프를 만들지 않고 라우트마다 전체 경로, 가드, 미들웨어를 붙인 리소스로 등록한다.
+    // 스코프는 접두사가 맞으면 안쪽에 라우트가 없어도 요청을 가로채 뒤에 등록된 라우트를 가리기 때문이다.
+    pub(crate) fn mount(
+        &self,
+        cfg: &mut ServiceConfig,
+        versioning: &Versioning,
+        parent: &str,
+        version: Option<u16>,
+        guards: &[SharedGuard],
+        middleware: &[Wrap],
+    ) {
+        // 버전은 가장 바깥 그룹에서 한 번만 접두사나 가드로 적용한다.
+        let (prefix, version_guard) = match (version, self.version) {
+            (None, Some(_)) => (
+                format!("{}{}{}", parent, versioning.prefix(self.version), self.prefix),
+                versioning.guard(self.version),
+            ),
+            _ => (format!("{}{}", parent, self.prefix), None),
+        };
+        let version = version.or(self.version);
+
+        let mut guards = guards.to_vec();
+        guards.extend(self.guards.iter().cloned());
+        if let Some(version_guard) = version_guard {
+            guards.push(Arc::new(version_guard));
+        }
+
+        // 그룹의 미들웨어가 안쪽, 상위 그룹의 미들웨어가 바깥쪽이다.
+        let middleware = self.middleware.iter().chain(middleware).cloned().collect::<Vec<_>>();
+
+        // 같은 경로의 라우트는 하나의 리소스로 묶어야 메서드가 다를 때 405 대신 다음 라우트를 찾는다.
+        let mut paths: Vec<&str> = Vec::new();
+        for route in &self.routes {
+            if !paths.contains(&route.path.as_str()) {
+                paths.push(&route.path);
+            }
+        }
+
+        for path in paths {
+            let pattern = join(&prefix, path);
+            let mut resource = web::resource(pattern.as_str());
+            for guard in &guards {
+                resource = resource.guard(Shared(guard.clone()));
+            }
+            for route in self.routes.iter().filter(|route| route.path == path) {
+                resource = resource.route((route.factory)());
+            }
+            cfg.service(
+                resource
+                    .wrap(Wrapped(middleware.clone()))
+                    .wrap(MarkPattern::new(&pattern)),
+            );
+        }
+
+        for group in &self.groups {
+            group.mount(cfg, versioning, &prefix, version, &guards, &middleware);
+        }
+    }
+}
+
+// 그룹 미들웨어를 안쪽부터 차례로 씌운다.
+struct Wrapped(Vec<Wrap>);
+
+impl<S> Middleware<S> for Wrapped
+where
+    S: Service<WebRequest<DefaultError>, Response = WebResponse, Error = web::Error> + 'static,
+{
+    type Service = WebService;
+
+    fn create(&self, service: S) -> Self::Service {
+        self.0.iter().fold(boxed::service(service), |service, wrap| wrap(service))
+    }
+}
+
+struct Shared(SharedGuard);
+
+impl Guard for Shared {
+    fn check(&self, request: &RequestHead) -> bool {
+        self.0.check(request)
+    }
+}
+
+// 앞에 `/` 를 붙이고 끝의 `/` 는 뗀다. (`user/` → `/user`, `/` → ``)
+fn normalize(path: &str) -> String {
+    let path = path.trim().trim_end_matches('/');
+
+    if path.is_empty() || path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{}", path)
+    }
+}
+
+fn join(prefix: &str, path: &str) -> String {
+    let joined = format!("{}{}", prefix, path);
+
+    if joined.is_empty() { "/".to_string() } else { joined }
+}
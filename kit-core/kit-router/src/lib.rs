@@ -1,14 +1,17 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+mod docs;
+mod group;
+mod openapi;
+mod operation;
+mod pattern;
+mod router;
+mod table;
+mod version;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use docs::ApiDocs;
+pub use group::RouteGroup;
+pub use openapi::{OpenApi, SecurityScheme};
+pub use operation::Operation;
+pub use pattern::RoutePattern;
+pub use router::Router;
+pub use table::{RouteInfo, RouteTable};
+pub use version::{VersionGuard, Versioning};
@@ -0,0 +1,381 @@
+use std::collections::BTreeMap;
+use schemars::generate::SchemaSettings;
+use schemars::{JsonSchema, SchemaGenerator};
+use serde_json::{Map, Value, json};
+use crate::operation::{Operation, ResponseBody, SchemaFn};
+use crate::table::{RouteInfo, RouteTable};
+
+const OPENAPI_VERSION: &str = "3.1.0";
+
+/// 인증 방식 (`components.securitySchemes`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecurityScheme {
+    /// `Authorization: Bearer <token>`
+    Bearer { format: Option<&'static str> },
+    /// 헤더로 전달하는 API 키
+    ApiKey { header: &'static str },
+}
+
+impl SecurityScheme {
+    fn to_json(&self) -> Value {
+        match self {
+            SecurityScheme::Bearer { format: Some(format) } => {
+                json!({ "type": "http", "scheme": "bearer", "bearerFormat": format })
+            }
+            SecurityScheme::Bearer { format: None } => json!({ "type": "http", "scheme": "bearer" }),
+            SecurityScheme::ApiKey { header } => json!({ "type": "apiKey", "in": "header", "name": header }),
+        }
+    }
+}
+
+/// 라우트 테이블로 OpenAPI 3.1 문서를 만듭니다.
+///
+/// 경로와 메서드는 등록된 라우트에서, 스키마는 [`Operation`] 에 지정한 serde 타입에서 가져옵니다.
+/// 문서가 없는 라우트도 경로와 기본 응답만으로 포함됩니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// let spec = OpenApi::new("server", env!("CARGO_PKG_VERSION"))
+///     .content_types(&["application/json", "application/msgpack"])
+///     .error_schema::<ErrorResponse>()
+///     .generate(&application.route_table());
+/// ```
+#[derive(Debug, Clone)]
+pub struct OpenApi {
+    title: String,
+    version: String,
+    description: Option<String>,
+    servers: Vec<String>,
+    content_types: Vec<&'static str>,
+    error_schema: Option<SchemaFn>,
+    security_schemes: BTreeMap<String, SecurityScheme>,
+}
+
+impl OpenApi {
+    pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            version: version.into(),
+            description: None,
+            servers: Vec::new(),
+            content_types: vec!["application/json"],
+            error_schema: None,
+            security_schemes: BTreeMap::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn server(mut self, url: impl Into<String>) -> Self {
+        self.servers.push(url.into());
+        self
+    }
+
+    /// 요청·응답 본문이 지원하는 형식. 기본값은 `application/json` 입니다.
+    pub fn content_types(mut self, content_types: &[&'static str]) -> Self {
+        self.content_types = content_types.to_vec();
+        self
+    }
+
+    /// [`Operation::error`] 응답의 본문 타입
+    pub fn error_schema<T: JsonSchema>(mut self) -> Self {
+        self.error_schema = Some(|generator| generator.subschema_for::<T>());
+        self
+    }
+
+    pub fn security_scheme(mut self, name: impl Into<String>, scheme: SecurityScheme) -> Self {
+        self.security_schemes.insert(name.into(), scheme);
+        self
+    }
+
+    pub fn generate(&self, table: &RouteTable) -> Value {
+        let mut generator = SchemaSettings::draft2020_12()
+            .with(|settings| {
+                settings.definitions_path = "/components/schemas".into();
+                settings.meta_schema = None;
+            })
+            .into_generator();
+
+        let mut paths = Map::new();
+        for route in table.routes() {
+            let (path, path_parameters) = path_template(&route.path);
+            let operation = self.operation(route, path_parameters, &mut generator);
+
+            if let Value::Object(item) = paths.entry(path).or_insert_with(|| json!({})) {
+                item.insert(route.method.to_ascii_lowercase(), operation);
+            }
+        }
+
+        let mut info = json!({ "title": self.title, "version": self.version });
+        if let Some(description) = &self.description {
+            info["description"] = json!(description);
+        }
+
+        let mut components = Map::new();
+        components.insert("schemas".to_string(), Value::Object(generator.take_definitions(true)));
+        if !self.security_schemes.is_empty() {
+            let schemes = self
+                .security_schemes
+                .iter()
+                .map(|(name, scheme)| (name.clone(), scheme.to_json()))
+                .collect();
+            components.insert("securitySchemes".to_string(), Value::Object(schemes));
+        }
+
+        let mut spec = json!({
+            "openapi": OPENAPI_VERSION,
+            "info": info,
+            "paths": paths,
+            "components": components,
+        });
+        if !self.servers.is_empty() {
+            spec["servers"] = self.servers.iter().map(|url| json!({ "url": url })).collect();
+        }
+
+        spec
+    }
+
+    fn operation(&self, route: &RouteInfo, path_parameters: Vec<String>, generator: &mut SchemaGenerator) -> Value {
+        let doc: &Operation = &route.operation;
+        let mut operation = Map::new();
+
+        operation.insert(
+            "operationId".to_string(),
+            json!(doc.operation_id.clone().unwrap_or_else(|| operation_id(route))),
+        );
+        if let Some(summary) = &doc.summary {
+            operation.insert("summary".to_string(), json!(summary));
+        }
+        if let Some(description) = &doc.description {
+            operation.insert("description".to_string(), json!(description));
+        }
+        if !doc.tags.is_empty() {
+            operation.insert("tags".to_string(), json!(doc.tags));
+        }
+        if doc.deprecated {
+            operation.insert("deprecated".to_string(), json!(true));
+        }
+
+        let mut parameters: Vec<Value> = path_parameters
+            .into_iter()
+            .map(|name| json!({ "name": name, "in": "path", "required": true, "schema": { "type": "string" } }))
+            .collect();
+        if let Some(query) = doc.query {
+            parameters.extend(query_parameters(query(generator).as_value()));
+        }
+        if !parameters.is_empty() {
+            operation.insert("parameters".to_string(), json!(parameters));
+        }
+
+        if let Some(body) = doc.body {
+            operation.insert(
+                "requestBody".to_string(),
+                json!({ "required": true, "content": self.content(body(generator).to_value()) }),
+            );
+        }
+
+        let mut responses = Map::new();
+        for response in &doc.responses {
+            let mut item = json!({ "description": response.description });
+
+            match (&response.body, self.error_schema) {
+                (ResponseBody::Schema(schema), _) => item["content"] = self.content(schema(generator).to_value()),
+                (ResponseBody::Error, Some(schema)) => {
+                    item["content"] = json!({ "application/json": { "schema": schema(generator).to_value() } })
+                }
+                _ => {}
+            }
+
+            responses.insert(response.status.as_u16().to_string(), item);
+        }
+        if responses.is_empty() {
+            responses.insert("200".to_string(), json!({ "description": "OK" }));
+        }
+        operation.insert("responses".to_string(), Value::Object(responses));
+
+        if !doc.security.is_empty() {
+            let security: Vec<Value> = doc.security.iter().map(|scheme| json!({ scheme: [] })).collect();
+            operation.insert("security".to_string(), json!(security));
+        }
+
+        Value::Object(operation)
+    }
+
+    fn content(&self, schema: Value) -> Value {
+        self.content_types
+            .iter()
+            .map(|content_type| (content_type.to_string(), json!({ "schema": schema })))
+            .collect::<Map<_, _>>()
+            .into()
+    }
+}
+
+// `/user/{id:\d+}` → (`/user/{id}`, [`id`])
+fn path_template(path: &str) -> (String, Vec<String>) {
+    let mut parameters = Vec::new();
+
+    let template = path
+        .split('/')
+        .map(|segment| match segment.strip_prefix('{').and_then(|segment| segment.strip_suffix('}')) {
+            Some(parameter) => {
+                let name = parameter.split(':').next().unwrap_or(parameter).to_string();
+                parameters.push(name.clone());
+                format!("{{{}}}", name)
+            }
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    (template, parameters)
+}
+
+fn query_parameters(schema: &Value) -> Vec<Value> {
+    let required: Vec<&str> = schema["required"]
+        .as_array()
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    schema["properties"]
+        .as_object()
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, schema)| {
+                    json!({
+                        "name": name,
+                        "in": "query",
+                        "required": required.contains(&name.as_str()),
+                        "schema": schema,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// 핸들러 함수 이름. 클로저처럼 이름이 없으면 메서드와 경로로 만든다.
+fn operation_id(route: &RouteInfo) -> String {
+    let name = route.handler.rsplit("::").next().unwrap_or(route.handler);
+
+    if name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return name.to_string();
+    }
+
+    let path = route
+        .path
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+
+    format!("{}_{}", route.method.to_ascii_lowercase(), path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RouteGroup, Router};
+    use ntex::http::StatusCode;
+    use ntex::web::HttpResponse;
+    use serde::{Deserialize, Serialize};
+
+    /// 가입 요청
+    #[derive(Deserialize, JsonSchema)]
+    #[allow(dead_code)]
+    struct Register {
+        name: String,
+    }
+
+    #[derive(Serialize, JsonSchema)]
+    struct Registered {
+        id: i32,
+    }
+
+    #[derive(Deserialize, JsonSchema)]
+    #[allow(dead_code)]
+    struct Search {
+        page: Option<u32>,
+        keyword: String,
+    }
+
+    #[derive(Serialize, JsonSchema)]
+    struct Problem {
+        code: String,
+    }
+
+    async fn register() -> HttpResponse {
+        HttpResponse::Created().finish()
+    }
+
+    async fn find() -> HttpResponse {
+        HttpResponse::Ok().finish()
+    }
+
+    #[test]
+    fn test_generate_spec_from_routes() {
+        let router = Router::new().group(
+            RouteGroup::new("/user")
+                .version(1)
+                .tag("user")
+                .post("", register)
+                .doc(
+                    Operation::new()
+                        .summary("회원 가입")
+                        .body::<Register>()
+                        .response::<Registered>(StatusCode::CREATED, "가입 완료")
+                        .error(StatusCode::CONFLICT, "이미 가입된 사용자"),
+                )
+                .get("/{id:\\d+}", find)
+                .doc(Operation::new().query::<Search>().security("bearer")),
+        );
+
+        let spec = OpenApi::new("test", "1.0.0")
+            .content_types(&["application/json", "application/msgpack"])
+            .error_schema::<Problem>()
+            .security_scheme("bearer", SecurityScheme::Bearer { format: Some("JWT") })
+            .generate(&router.table());
+
+        assert_eq!(spec["openapi"], "3.1.0");
+
+        let create = &spec["paths"]["/v1/user"]["post"];
+        assert_eq!(create["operationId"], "register");
+        assert_eq!(create["tags"], json!(["user"]));
+        assert_eq!(
+            create["requestBody"]["content"]["application/msgpack"]["schema"]["$ref"],
+            "#/components/schemas/Register"
+        );
+        assert_eq!(
+            create["responses"]["201"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/Registered"
+        );
+        assert_eq!(
+            create["responses"]["409"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/Problem"
+        );
+
+        let find = &spec["paths"]["/v1/user/{id}"]["get"];
+        let parameters: Vec<(&str, &str, bool)> = find["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|parameter| {
+                (
+                    parameter["name"].as_str().unwrap(),
+                    parameter["in"].as_str().unwrap(),
+                    parameter["required"].as_bool().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(parameters, vec![("id", "path", true), ("keyword", "query", true), ("page", "query", false)]);
+        assert_eq!(find["security"], json!([{ "bearer": [] }]));
+        assert_eq!(find["responses"]["200"]["description"], "OK");
+
+        assert_eq!(spec["components"]["schemas"]["Register"]["description"], "가입 요청");
+        assert_eq!(spec["components"]["securitySchemes"]["bearer"]["bearerFormat"], "JWT");
+    }
+}
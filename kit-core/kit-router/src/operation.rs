@@ -0,0 +1,136 @@
+use ntex::http::StatusCode;
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+
+pub(crate) type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+fn reference<T: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+    generator.subschema_for::<T>()
+}
+
+fn inline<T: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+    T::json_schema(generator)
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum ResponseBody {
+    Empty,
+    Schema(SchemaFn),
+    /// [`crate::OpenApi::error_schema`] 로 지정한 공통 에러 본문
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ResponseDoc {
+    pub(crate) status: StatusCode,
+    pub(crate) description: String,
+    pub(crate) body: ResponseBody,
+}
+
+/// 라우트 하나의 OpenAPI 문서
+///
+/// 본문·응답 스키마는 serde 타입의 `JsonSchema` 구현에서 만들어집니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// RouteGroup::new("/user")
+///     .post("", create_user)
+///     .doc(
+///         Operation::new()
+///             .summary("회원 가입")
+///             .body::<UserRegisterCommand>()
+///             .response::<Envelope<UserRegisterCommandResult>>(StatusCode::CREATED, "가입 완료")
+///             .error(StatusCode::UNPROCESSABLE_ENTITY, "입력값 검증 실패"),
+///     )
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Operation {
+    pub(crate) summary: Option<String>,
+    pub(crate) description: Option<String>,
+    pub(crate) operation_id: Option<String>,
+    pub(crate) tags: Vec<String>,
+    pub(crate) deprecated: bool,
+    pub(crate) query: Option<SchemaFn>,
+    pub(crate) body: Option<SchemaFn>,
+    pub(crate) responses: Vec<ResponseDoc>,
+    pub(crate) security: Vec<String>,
+}
+
+impl Operation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// 지정하지 않으면 핸들러 함수 이름을 사용합니다.
+    pub fn operation_id(mut self, operation_id: impl Into<String>) -> Self {
+        self.operation_id = Some(operation_id.into());
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    pub fn deprecated(mut self) -> Self {
+        self.deprecated = true;
+        self
+    }
+
+    /// 쿼리 파라미터. `T` 의 필드가 각각 파라미터가 됩니다.
+    pub fn query<T: JsonSchema>(mut self) -> Self {
+        self.query = Some(inline::<T>);
+        self
+    }
+
+    /// 요청 본문
+    pub fn body<T: JsonSchema>(mut self) -> Self {
+        self.body = Some(reference::<T>);
+        self
+    }
+
+    pub fn response<T: JsonSchema>(mut self, status: StatusCode, description: impl Into<String>) -> Self {
+        self.responses.push(ResponseDoc {
+            status,
+            description: description.into(),
+            body: ResponseBody::Schema(reference::<T>),
+        });
+        self
+    }
+
+    /// 본문이 없는 응답 (`204 No Content` 등)
+    pub fn empty_response(mut self, status: StatusCode, description: impl Into<String>) -> Self {
+        self.responses.push(ResponseDoc {
+            status,
+            description: description.into(),
+            body: ResponseBody::Empty,
+        });
+        self
+    }
+
+    /// 공통 에러 본문으로 응답하는 경우
+    pub fn error(mut self, status: StatusCode, description: impl Into<String>) -> Self {
+        self.responses.push(ResponseDoc {
+            status,
+            description: description.into(),
+            body: ResponseBody::Error,
+        });
+        self
+    }
+
+    /// [`crate::OpenApi::security_scheme`] 로 등록한 인증 방식 중 이 라우트에 필요한 것
+    pub fn security(mut self, scheme: impl Into<String>) -> Self {
+        self.security.push(scheme.into());
+        self
+    }
+}
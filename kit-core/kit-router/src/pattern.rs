@@ -0,0 +1,61 @@
+use std::sync::Arc;
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web::{ErrorRenderer, HttpRequest, WebRequest, WebResponse};
+
+/// 요청이 매칭된 라우트의 경로 패턴 (`/v1/user/{id}`)
+///
+/// [`crate::Router`] 로 등록한 라우트에만 있으며 라우트 테이블의 `path` 와 같습니다.
+/// 메트릭 라벨이나 스팬 이름처럼 요청마다 값이 달라지면 안 되는 곳에 씁니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutePattern(Arc<str>);
+
+impl RoutePattern {
+    pub fn of(request: &HttpRequest) -> Option<Self> {
+        request.extensions().get::<Self>().cloned()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+// 라우트 리소스마다 걸어 매칭된 패턴을 요청에 남긴다.
+pub(crate) struct MarkPattern(RoutePattern);
+
+impl MarkPattern {
+    pub(crate) fn new(pattern: &str) -> Self {
+        Self(RoutePattern(pattern.into()))
+    }
+}
+
+impl<S> Middleware<S> for MarkPattern {
+    type Service = MarkPatternService<S>;
+
+    fn create(&self, service: S) -> Self::Service {
+        MarkPatternService {
+            pattern: self.0.clone(),
+            service,
+        }
+    }
+}
+
+pub(crate) struct MarkPatternService<S> {
+    pattern: RoutePattern,
+    service: S,
+}
+
+impl<S, Err> Service<WebRequest<Err>> for MarkPatternService<S>
+where
+    S: Service<WebRequest<Err>, Response = WebResponse>,
+    Err: ErrorRenderer,
+{
+    type Response = WebResponse;
+    type Error = S::Error;
+
+    ntex::forward_ready!(service);
+
+    async fn call(&self, req: WebRequest<Err>, ctx: ServiceCtx<'_, Self>) -> Result<Self::Response, Self::Error> {
+        req.extensions_mut().insert(self.pattern.clone());
+        ctx.call(&self.service, req).await
+    }
+}
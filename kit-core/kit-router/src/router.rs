@@ -0,0 +1,224 @@
+use ntex::web::ServiceConfig;
+use crate::group::RouteGroup;
+use crate::table::RouteTable;
+use crate::version::Versioning;
+
+/// 라우트 그룹을 모아 앱에 연결합니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// let router = Router::new()
+///     .versioning(Versioning::Path)
+///     .group(RouteGroup::new("/user").version(1).post("", create_user));
+///
+/// println!("{}", router.table());
+/// App::new().configure(|cfg| router.configure(cfg))
+/// ```
+#[derive(Clone, Default)]
+pub struct Router {
+    versioning: Versioning,
+    groups: Vec<RouteGroup>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn versioning(mut self, versioning: Versioning) -> Self {
+        self.versioning = versioning;
+        self
+    }
+
+    pub fn group(mut self, group: RouteGroup) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    pub fn groups(mut self, groups: impl IntoIterator<Item = RouteGroup>) -> Self {
+        self.groups.extend(groups);
+        self
+    }
+
+    pub fn configure(&self, cfg: &mut ServiceConfig) {
+        for group in &self.groups {
+            group.mount(cfg, &self.versioning, "", None, &[], &[]);
+        }
+    }
+
+    pub fn table(&self) -> RouteTable {
+        let mut routes = Vec::new();
+        for group in &self.groups {
+            group.routes(&self.versioning, "", None, None, &mut routes);
+        }
+
+        RouteTable::new(routes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RoutePattern;
+    use ntex::http::StatusCode;
+    use ntex::http::header::{HeaderName, HeaderValue};
+    use ntex::service::{Middleware, Service, ServiceCtx};
+    use ntex::web::{self, App, HttpResponse, guard, test};
+
+    async fn create_v1() -> HttpResponse {
+        HttpResponse::Created().body("v1")
+    }
+
+    async fn create_v2() -> HttpResponse {
+        HttpResponse::Created().body("v2")
+    }
+
+    async fn list() -> HttpResponse {
+        HttpResponse::Ok().body("list")
+    }
+
+    #[derive(Clone)]
+    struct Stamp;
+
+    impl<S> Middleware<S> for Stamp {
+        type Service = StampMiddleware<S>;
+
+        fn create(&self, service: S) -> Self::Service {
+            StampMiddleware { service }
+        }
+    }
+
+    struct StampMiddleware<S> {
+        service: S,
+    }
+
+    impl<S, Err> Service<web::WebRequest<Err>> for StampMiddleware<S>
+    where
+        S: Service<web::WebRequest<Err>, Response = web::WebResponse, Error = web::Error>,
+        Err: web::ErrorRenderer,
+    {
+        type Response = web::WebResponse;
+        type Error = web::Error;
+
+        ntex::forward_ready!(service);
+
+        async fn call(&self, req: web::WebRequest<Err>, ctx: ServiceCtx<'_, Self>) -> Result<Self::Response, Self::Error> {
+            let mut response = ctx.call(&self.service, req).await?;
+            response
+                .headers_mut()
+                .insert(HeaderName::from_static("x-stamp"), HeaderValue::from_static("admin"));
+            Ok(response)
+        }
+    }
+
+    fn user_groups() -> Vec<RouteGroup> {
+        vec![
+            RouteGroup::new("/user")
+                .version(1)
+                .post("", create_v1)
+                .get("", list)
+                .group(
+                    RouteGroup::new("/admin")
+                        .guard(guard::Header("x-role", "admin"))
+                        .wrap(Stamp)
+                        .get("", list),
+                ),
+            RouteGroup::new("/user").version(2).post("", create_v2),
+        ]
+    }
+
+    #[ntex::test]
+    async fn test_path_versioning_guards_and_middleware() {
+        let router = Router::new().groups(user_groups());
+        let app = test::init_service(App::new().configure(|cfg| router.configure(cfg))).await;
+
+        let response = test::call_service(&app, test::TestRequest::post().uri("/v2/user").to_request()).await;
+        assert_eq!(test::read_body(response).await, "v2");
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/v1/user").to_request()).await;
+        assert_eq!(test::read_body(response).await, "list");
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/v1/user/admin").to_request()).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let request = test::TestRequest::get()
+            .uri("/v1/user/admin")
+            .header("x-role", "admin")
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.headers().get("x-stamp").unwrap(), "admin");
+    }
+
+    #[ntex::test]
+    async fn test_header_versioning() {
+        let router = Router::new()
+            .versioning(Versioning::header("api-version").with_default(1))
+            .groups(user_groups());
+        let app = test::init_service(App::new().configure(|cfg| router.configure(cfg))).await;
+
+        let response = test::call_service(&app, test::TestRequest::post().uri("/user").to_request()).await;
+        assert_eq!(test::read_body(response).await, "v1");
+
+        let request = test::TestRequest::post()
+            .uri("/user")
+            .header("api-version", "v2")
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(test::read_body(response).await, "v2");
+    }
+
+    async fn pattern(req: web::HttpRequest) -> HttpResponse {
+        HttpResponse::Ok().body(RoutePattern::of(&req).map(|pattern| pattern.as_str().to_string()).unwrap_or_default())
+    }
+
+    #[ntex::test]
+    async fn test_marks_matched_route_pattern() {
+        let router = Router::new().group(
+            RouteGroup::new("/user")
+                .version(1)
+                .get("/{id}", pattern)
+                .group(RouteGroup::new("/{id}/device").wrap(Stamp).get("/{device}", pattern)),
+        );
+        let app = test::init_service(App::new().configure(|cfg| router.configure(cfg))).await;
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/v1/user/42").to_request()).await;
+        assert_eq!(test::read_body(response).await, "/v1/user/{id}");
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/v1/user/42/device/7").to_request()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(test::read_body(response).await, "/v1/user/{id}/device/{device}");
+    }
+
+    #[ntex::test]
+    async fn test_group_middleware_does_not_shadow_later_routes() {
+        let router = Router::new()
+            .group(RouteGroup::new("").wrap(Stamp).get("/admin", list))
+            .group(RouteGroup::new("").get("/public", list));
+        let app = test::init_service(App::new().configure(|cfg| router.configure(cfg))).await;
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/public").to_request()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get("x-stamp").is_none());
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/admin").to_request()).await;
+        assert_eq!(response.headers().get("x-stamp").unwrap(), "admin");
+    }
+
+    #[test]
+    fn test_route_table() {
+        let table = Router::new().groups(user_groups()).table();
+        let routes: Vec<(&str, &str)> = table
+            .routes()
+            .iter()
+            .map(|route| (route.method.as_str(), route.path.as_str()))
+            .collect();
+
+        assert_eq!(
+            routes,
+            vec![("GET", "/v1/user"), ("POST", "/v1/user"), ("GET", "/v1/user/admin"), ("POST", "/v2/user")]
+        );
+        assert!(table.routes()[0].handler.ends_with("::list"));
+        assert!(table.to_string().starts_with("METHOD  PATH"));
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+use ntex::http::Response;
+use ntex::web::{ErrorRenderer, HttpRequest, Responder};
+use serde::Serialize;
+use crate::operation::Operation;
+
+/// 등록된 라우트 하나
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteInfo {
+    pub method: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u16>,
+    pub handler: &'static str,
+    /// [`crate::RouteGroup::doc`] 로 남긴 문서. 그룹의 태그가 포함됩니다.
+    #[serde(skip)]
+    pub operation: Operation,
+}
+
+/// [`crate::Router`] 에 등록된 라우트 목록
+///
+/// `Display` 로 표 형태로 출력하거나, 핸들러에서 그대로 반환해 JSON 으로 내려줄 수 있습니다.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(transparent)]
+pub struct RouteTable {
+    routes: Vec<RouteInfo>,
+}
+
+impl RouteTable {
+    pub(crate) fn new(mut routes: Vec<RouteInfo>) -> Self {
+        routes.sort_by(|a, b| (&a.path, a.version, &a.method).cmp(&(&b.path, b.version, &b.method)));
+        Self { routes }
+    }
+
+    pub fn routes(&self) -> &[RouteInfo] {
+        &self.routes
+    }
+
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+}
+
+impl fmt::Display for RouteTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let version = |route: &RouteInfo| route.version.map(|version| format!("v{}", version)).unwrap_or_default();
+
+        let method_width = self.routes.iter().map(|route| route.method.len()).chain([6]).max().unwrap_or(6);
+        let path_width = self.routes.iter().map(|route| route.path.len()).chain([4]).max().unwrap_or(4);
+        let version_width = self.routes.iter().map(|route| version(route).len()).chain([7]).max().unwrap_or(7);
+
+        write!(
+            f,
+            "{:method_width$}  {:path_width$}  {:version_width$}  HANDLER",
+            "METHOD", "PATH", "VERSION"
+        )?;
+
+        for route in &self.routes {
+            write!(
+                f,
+                "\n{:method_width$}  {:path_width$}  {:version_width$}  {}",
+                route.method,
+                route.path,
+                version(route),
+                route.handler
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<Err: ErrorRenderer> Responder<Err> for RouteTable {
+    async fn respond_to(self, _: &HttpRequest) -> Response {
+        Response::Ok().json(&self)
+    }
+}
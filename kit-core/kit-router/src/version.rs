@@ -0,0 +1,73 @@
+use ntex::http::RequestHead;
+use ntex::web::guard::Guard;
+
+/// API 버전을 구분하는 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Versioning {
+    /// `/v1/user` 처럼 경로 앞에 버전을 붙입니다.
+    #[default]
+    Path,
+    /// `api-version: 1` 처럼 헤더로 버전을 고릅니다. 헤더가 없으면 `default` 버전으로 처리합니다.
+    Header {
+        name: &'static str,
+        default: Option<u16>,
+    },
+}
+
+impl Versioning {
+    pub fn header(name: &'static str) -> Self {
+        Versioning::Header { name, default: None }
+    }
+
+    /// 헤더 방식에서 버전 헤더가 없을 때 사용할 버전
+    pub fn with_default(self, version: u16) -> Self {
+        match self {
+            Versioning::Header { name, .. } => Versioning::Header {
+                name,
+                default: Some(version),
+            },
+            Versioning::Path => Versioning::Path,
+        }
+    }
+
+    pub(crate) fn prefix(&self, version: Option<u16>) -> String {
+        match (self, version) {
+            (Versioning::Path, Some(version)) => format!("/v{}", version),
+            _ => String::new(),
+        }
+    }
+
+    pub(crate) fn guard(&self, version: Option<u16>) -> Option<VersionGuard> {
+        match (self, version) {
+            (Versioning::Header { name, default }, Some(version)) => Some(VersionGuard {
+                header: name,
+                version,
+                is_default: *default == Some(version),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// 요청 헤더의 버전(`1`, `v1`)이 그룹의 버전과 같은지 확인합니다.
+#[derive(Debug, Clone)]
+pub struct VersionGuard {
+    header: &'static str,
+    version: u16,
+    is_default: bool,
+}
+
+impl Guard for VersionGuard {
+    fn check(&self, request: &RequestHead) -> bool {
+        let Some(value) = request.headers.get(self.header) else {
+            return self.is_default;
+        };
+
+        value
+            .to_str()
+            .ok()
+            .map(|value| value.trim().trim_start_matches(['v', 'V']))
+            .and_then(|value| value.parse::<u16>().ok())
+            .is_some_and(|version| version == self.version)
+    }
+}
@@ -0,0 +1,30 @@
+use ntex::http::StatusCode;
+use ntex::web::{DefaultError, WebResponseError};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SecurityError {
+    #[error("identity headers are not signed")]
+    Unsigned,
+
+    #[error("identity signature is invalid")]
+    InvalidSignature,
+
+    #[error("identity signature has expired")]
+    Expired,
+
+    #[error("authentication is required")]
+    Unauthenticated,
+
+    #[error("role `{0}` is required")]
+    Forbidden(String),
+}
+
+impl WebResponseError<DefaultError> for SecurityError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            SecurityError::Forbidden(_) => StatusCode::FORBIDDEN,
+            _ => StatusCode::UNAUTHORIZED,
+        }
+    }
+}
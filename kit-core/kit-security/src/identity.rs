@@ -0,0 +1,198 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hmac::{Hmac, Mac};
+use kit_context::{USER_ID_HEADER, USER_ROLES_HEADER};
+use ntex::http::HeaderMap;
+use sha2::Sha256;
+use crate::error::SecurityError;
+
+/// 인증 방식 (`jwt`, `api-key`)
+pub const AUTH_METHOD_HEADER: &str = "x-auth-method";
+/// 서명한 시각(유닉스 초)
+pub const IDENTITY_TIMESTAMP_HEADER: &str = "x-identity-timestamp";
+/// 신원 헤더와 서명 시각에 대한 HMAC-SHA256 (base64url)
+pub const IDENTITY_SIGNATURE_HEADER: &str = "x-identity-signature";
+
+/// 게이트웨이가 만드는 신원 헤더 전체. 게이트웨이는 클라이언트가 보낸 같은 이름의 헤더를 모두 지우고 다시 채웁니다.
+pub const IDENTITY_HEADERS: [&str; 5] = [
+    USER_ID_HEADER,
+    USER_ROLES_HEADER,
+    AUTH_METHOD_HEADER,
+    IDENTITY_TIMESTAMP_HEADER,
+    IDENTITY_SIGNATURE_HEADER,
+];
+
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(60);
+
+/// 게이트웨이가 확인한 호출자
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identity {
+    pub id: String,
+    pub roles: Vec<String>,
+    pub method: String,
+}
+
+/// 게이트웨이와 서비스가 공유하는 비밀 키로 신원 헤더에 서명하고 확인합니다.
+///
+/// 서명 시각이 `max_age` 보다 오래되었거나 앞서 있으면 거절하므로, 가로챈 헤더를 나중에 다시 쓸 수 없습니다.
+#[derive(Clone)]
+pub struct IdentitySigner {
+    key: Vec<u8>,
+    max_age: Duration,
+}
+
+impl IdentitySigner {
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            key: secret.as_ref().to_vec(),
+            max_age: DEFAULT_MAX_AGE,
+        }
+    }
+
+    /// 서명을 받아들이는 시간 차. 게이트웨이와 서비스의 시계 차이보다 커야 합니다. (기본 60초)
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// 업스트림 요청에 붙일 신원 헤더
+    pub fn sign(&self, identity: &Identity) -> Vec<(&'static str, String)> {
+        self.sign_at(identity, now())
+    }
+
+    fn sign_at(&self, identity: &Identity, timestamp: u64) -> Vec<(&'static str, String)> {
+        let roles = identity.roles.join(",");
+        let timestamp = timestamp.to_string();
+        let signature = URL_SAFE_NO_PAD.encode(
+            self.mac(&identity.id, &roles, &identity.method, &timestamp)
+                .finalize()
+                .into_bytes(),
+        );
+
+        vec![
+            (USER_ID_HEADER, identity.id.clone()),
+            (USER_ROLES_HEADER, roles),
+            (AUTH_METHOD_HEADER, identity.method.clone()),
+            (IDENTITY_TIMESTAMP_HEADER, timestamp),
+            (IDENTITY_SIGNATURE_HEADER, signature),
+        ]
+    }
+
+    /// 신원 헤더가 없으면 `None`, 있으면 서명을 확인한 신원을 돌려줍니다.
+    pub fn verify(&self, headers: &HeaderMap) -> Result<Option<Identity>, SecurityError> {
+        self.verify_at(headers, now())
+    }
+
+    fn verify_at(&self, headers: &HeaderMap, now: u64) -> Result<Option<Identity>, SecurityError> {
+        if IDENTITY_HEADERS.iter().all(|name| !headers.contains_key(*name)) {
+            return Ok(None);
+        }
+
+        let id = header(headers, USER_ID_HEADER).ok_or(SecurityError::Unsigned)?;
+        let signature = header(headers, IDENTITY_SIGNATURE_HEADER).ok_or(SecurityError::Unsigned)?;
+        let timestamp = header(headers, IDENTITY_TIMESTAMP_HEADER).ok_or(SecurityError::Unsigned)?;
+        let roles = header(headers, USER_ROLES_HEADER).unwrap_or("");
+        let method = header(headers, AUTH_METHOD_HEADER).unwrap_or("");
+
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| SecurityError::InvalidSignature)?;
+        self.mac(id, roles, method, timestamp)
+            .verify_slice(&signature)
+            .map_err(|_| SecurityError::InvalidSignature)?;
+
+        // 서명을 확인한 뒤에만 시각을 본다. 위조한 헤더에 만료 여부를 알려주지 않기 위해서다.
+        let signed_at = timestamp.parse::<u64>().map_err(|_| SecurityError::InvalidSignature)?;
+        if now.abs_diff(signed_at) > self.max_age.as_secs() {
+            return Err(SecurityError::Expired);
+        }
+
+        Ok(Some(Identity {
+            id: id.to_string(),
+            roles: roles
+                .split(',')
+                .map(str::trim)
+                .filter(|role| !role.is_empty())
+                .map(str::to_string)
+                .collect(),
+            method: method.to_string(),
+        }))
+    }
+
+    // 헤더 값에는 줄바꿈이 들어갈 수 없으므로 줄바꿈으로 구분하면 경계가 모호하지 않다.
+    fn mac(&self, id: &str, roles: &str, method: &str, timestamp: &str) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(format!("v1\n{}\n{}\n{}\n{}", id, roles, method, timestamp).as_bytes());
+        mac
+    }
+}
+
+impl std::fmt::Debug for IdentitySigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IdentitySigner")
+            .field("max_age", &self.max_age)
+            .finish_non_exhaustive()
+    }
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::http::header::{HeaderName, HeaderValue};
+
+    fn identity() -> Identity {
+        Identity {
+            id: "42".to_string(),
+            roles: vec!["admin".to_string(), "user".to_string()],
+            method: "jwt".to_string(),
+        }
+    }
+
+    fn headers(signed: Vec<(&'static str, String)>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in signed {
+            headers.insert(HeaderName::from_static(name), HeaderValue::from_str(&value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_verify_signed_identity() {
+        let signer = IdentitySigner::new("secret");
+        let headers = headers(signer.sign_at(&identity(), 1_000));
+
+        assert_eq!(signer.verify_at(&headers, 1_030), Ok(Some(identity())));
+        assert_eq!(signer.verify_at(&HeaderMap::new(), 1_030), Ok(None));
+    }
+
+    #[test]
+    fn test_rejects_tampered_unsigned_and_expired() {
+        let signer = IdentitySigner::new("secret");
+
+        let mut tampered = headers(signer.sign_at(&identity(), 1_000));
+        tampered.insert(HeaderName::from_static(USER_ROLES_HEADER), HeaderValue::from_static("admin,root"));
+        let other_key = headers(IdentitySigner::new("other").sign_at(&identity(), 1_000));
+        let mut unsigned = HeaderMap::new();
+        unsigned.insert(HeaderName::from_static(USER_ID_HEADER), HeaderValue::from_static("42"));
+
+        assert_eq!(signer.verify_at(&tampered, 1_000), Err(SecurityError::InvalidSignature));
+        assert_eq!(signer.verify_at(&other_key, 1_000), Err(SecurityError::InvalidSignature));
+        assert_eq!(signer.verify_at(&unsigned, 1_000), Err(SecurityError::Unsigned));
+        assert_eq!(
+            signer.verify_at(&headers(signer.sign_at(&identity(), 1_000)), 1_061),
+            Err(SecurityError::Expired)
+        );
+    }
+}
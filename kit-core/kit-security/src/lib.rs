@@ -1,14 +1,12 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+mod error;
+mod identity;
+mod middleware;
+mod role;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use error::SecurityError;
+pub use identity::{
+    AUTH_METHOD_HEADER, IDENTITY_HEADERS, IDENTITY_SIGNATURE_HEADER, IDENTITY_TIMESTAMP_HEADER, Identity,
+    IdentitySigner,
+};
+pub use middleware::{TrustedIdentity, TrustedIdentityMiddleware};
+pub use role::require_role;
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web::{DefaultError, WebRequest, WebResponse};
+use crate::identity::IdentitySigner;
+
+/// 게이트웨이가 서명한 신원 헤더만 받아들이는 미들웨어
+///
+/// 신원 헤더가 없으면 익명 요청으로 통과시키고, 서명이 없거나 맞지 않거나 만료되었으면 `401` 로 거절합니다.
+/// `kit_context::Context` 가 같은 헤더로 `Principal` 을 만들기 전에 실행되도록 `Context` 보다 나중에 `.wrap()` 합니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// App::new()
+///     .wrap(Context::new())
+///     .wrap(TrustedIdentity::new(IdentitySigner::new(secret)))
+/// ```
+#[derive(Debug, Clone)]
+pub struct TrustedIdentity {
+    signer: Arc<IdentitySigner>,
+}
+
+impl TrustedIdentity {
+    pub fn new(signer: IdentitySigner) -> Self {
+        Self {
+            signer: Arc::new(signer),
+        }
+    }
+}
+
+impl<S> Middleware<S> for TrustedIdentity {
+    type Service = TrustedIdentityMiddleware<S>;
+
+    fn create(&self, service: S) -> Self::Service {
+        TrustedIdentityMiddleware {
+            service,
+            signer: self.signer.clone(),
+        }
+    }
+}
+
+pub struct TrustedIdentityMiddleware<S> {
+    service: S,
+    signer: Arc<IdentitySigner>,
+}
+
+impl<S> Service<WebRequest<DefaultError>> for TrustedIdentityMiddleware<S>
+where
+    S: Service<WebRequest<DefaultError>, Response = WebResponse, Error = ntex::web::Error>,
+{
+    type Response = WebResponse;
+    type Error = ntex::web::Error;
+
+    ntex::forward_ready!(service);
+
+    async fn call(&self, req: WebRequest<DefaultError>, ctx: ServiceCtx<'_, Self>) -> Result<Self::Response, Self::Error> {
+        match self.signer.verify(req.headers()) {
+            Ok(Some(identity)) => {
+                req.extensions_mut().insert(identity);
+                ctx.call(&self.service, req).await
+            }
+            Ok(None) => ctx.call(&self.service, req).await,
+            Err(e) => Ok(req.render_error(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kit_context::{Context, RequestContext, USER_ID_HEADER};
+    use ntex::http::StatusCode;
+    use ntex::web::{self, App, HttpResponse, test};
+    use crate::identity::Identity;
+
+    async fn whoami(context: RequestContext) -> HttpResponse {
+        HttpResponse::Ok().body(context.principal.map(|principal| principal.id).unwrap_or_default())
+    }
+
+    #[ntex::test]
+    async fn test_only_signed_identity_reaches_context() {
+        let signer = IdentitySigner::new("secret");
+        let app = test::init_service(
+            App::new()
+                .wrap(Context::new())
+                .wrap(TrustedIdentity::new(signer.clone()))
+                .route("/", web::get().to(whoami)),
+        )
+        .await;
+
+        let mut signed = test::TestRequest::get().uri("/");
+        for (name, value) in signer.sign(&Identity {
+            id: "42".to_string(),
+            roles: vec!["user".to_string()],
+            method: "api-key".to_string(),
+        }) {
+            signed = signed.header(name, value);
+        }
+        let response = test::call_service(&app, signed.to_request()).await;
+        assert_eq!(test::read_body(response).await, "42");
+
+        let anonymous = test::call_service(&app, test::TestRequest::get().uri("/").to_request()).await;
+        assert_eq!(test::read_body(anonymous).await, "");
+
+        let forged = test::TestRequest::get().uri("/").header(USER_ID_HEADER, "1").to_request();
+        assert_eq!(test::call_service(&app, forged).await.status(), StatusCode::UNAUTHORIZED);
+    }
+}
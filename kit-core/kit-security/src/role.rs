@@ -0,0 +1,39 @@
+use kit_context::{Principal, RequestContext};
+use crate::error::SecurityError;
+
+/// 호출자가 `role` 을 가졌는지 확인합니다. 익명이면 `Unauthenticated`(401), 역할이 없으면 `Forbidden`(403) 입니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// async fn audit_log(context: RequestContext) -> Result<Reply<Vec<Entry>>, Exception> {
+///     require_role(&context, "admin")?;
+///     ...
+/// }
+/// ```
+pub fn require_role<'a>(context: &'a RequestContext, role: &str) -> Result<&'a Principal, SecurityError> {
+    let principal = context.principal.as_ref().ok_or(SecurityError::Unauthenticated)?;
+
+    match principal.has_role(role) {
+        true => Ok(principal),
+        false => Err(SecurityError::Forbidden(role.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_require_role() {
+        let mut context = RequestContext::default();
+        assert_eq!(require_role(&context, "admin"), Err(SecurityError::Unauthenticated));
+
+        context.principal = Some(Principal {
+            id: "42".to_string(),
+            roles: vec!["user".to_string()],
+        });
+        assert_eq!(require_role(&context, "admin"), Err(SecurityError::Forbidden("admin".to_string())));
+        assert_eq!(require_role(&context, "user").map(|principal| principal.id.as_str()), Ok("42"));
+    }
+}
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+use std::env;
+use std::time::Duration;
+use jsonwebtoken::{DecodingKey, Validation, decode, decode_header};
+use kit_security::{Identity, IdentitySigner};
+use ntex::http::HeaderMap;
+use ntex::http::header::{AUTHORIZATION, HeaderName};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use crate::config::{AuthConfig, AuthPolicy, ConfigError};
+use crate::error::GatewayError;
+use crate::jwks::{Jwks, JwksSource};
+use crate::route::Route;
+
+const METHOD_JWT: &str = "jwt";
+const METHOD_API_KEY: &str = "api-key";
+
+#[derive(Debug)]
+struct ApiKey {
+    subject: String,
+    roles: Vec<String>,
+}
+
+/// `Authorization: Bearer` 의 JWT 나 API 키로 호출자를 확인하고, 확인한 신원을 업스트림에 보낼 헤더로 서명합니다.
+#[derive(Debug)]
+pub struct Authenticator {
+    signer: IdentitySigner,
+    jwks: Option<Jwks>,
+    issuer: Option<String>,
+    audience: Option<String>,
+    roles_claim: String,
+    api_key_header: HeaderName,
+    // API 키의 SHA-256 (hex) → 키 주인
+    api_keys: HashMap<String, ApiKey>,
+}
+
+impl Authenticator {
+    /// 서명 키를 환경 변수에서 읽고, JWKS 파일을 읽습니다. 이전 설정과 JWKS 출처가 같으면 받아 둔 키를 이어 씁니다.
+    pub fn new(config: &AuthConfig, previous: Option<&Authenticator>) -> Result<Self, ConfigError> {
+        let secret = match &config.identity_secret {
+            Some(secret) => secret.clone(),
+            None => env::var(&config.identity_secret_env).unwrap_or_default(),
+        };
+        if secret.is_empty() {
+            return Err(ConfigError::Invalid(format!(
+                "`{}` must be set to sign identity headers",
+                config.identity_secret_env
+            )));
+        }
+
+        let source = match (&config.jwks_file, &config.jwks_url) {
+            (Some(path), _) => Some(JwksSource::File(path.clone())),
+            (None, Some(url)) => Some(JwksSource::Url(url.clone())),
+            (None, None) => None,
+        };
+        let jwks = source
+            .map(|source| {
+                Jwks::new(
+                    source,
+                    Duration::from_millis(config.jwks_refresh_ms),
+                    previous.and_then(|previous| previous.jwks.as_ref()),
+                )
+            })
+            .transpose()?;
+
+        Ok(Self {
+            signer: IdentitySigner::new(secret),
+            jwks,
+            issuer: config.issuer.clone(),
+            audience: config.audience.clone(),
+            roles_claim: config.roles_claim.clone(),
+            // 설정 검증을 통과한 이름이다.
+            api_key_header: HeaderName::try_from(config.api_key_header.as_str())
+                .map_err(|_| ConfigError::Invalid(format!("invalid api key header `{}`", config.api_key_header)))?,
+            api_keys: config
+                .api_keys
+                .iter()
+                .map(|key| {
+                    let owner = ApiKey {
+                        subject: key.subject.clone(),
+                        roles: key.roles.clone(),
+                    };
+                    (key.sha256.to_ascii_lowercase(), owner)
+                })
+                .collect(),
+        })
+    }
+
+    pub fn jwks(&self) -> Option<&Jwks> {
+        self.jwks.as_ref()
+    }
+
+    pub fn api_key_header(&self) -> &HeaderName {
+        &self.api_key_header
+    }
+
+    pub fn sign(&self, identity: &Identity) -> Vec<(&'static str, String)> {
+        self.signer.sign(identity)
+    }
+
+    /// 자격 증명이 없으면 `None` 입니다. 있는데 확인하지 못하면 익명으로 낮추지 않고 거절합니다.
+    fn authenticate(&self, headers: &HeaderMap) -> Result<Option<Identity>, GatewayError> {
+        if let Some(value) = headers.get(AUTHORIZATION) {
+            let value = value
+                .to_str()
+                .map_err(|_| GatewayError::Unauthorized("invalid authorization header".to_string()))?;
+
+            // Basic 같은 다른 방식은 업스트림이 처리하도록 그대로 둔다.
+            if let Some((scheme, token)) = value.split_once(' ')
+                && scheme.eq_ignore_ascii_case("bearer")
+            {
+                return self.verify_jwt(token.trim()).map(Some);
+            }
+        }
+
+        match headers.get(&self.api_key_header) {
+            Some(value) => {
+                let key = value
+                    .to_str()
+                    .map_err(|_| GatewayError::Unauthorized("invalid api key".to_string()))?;
+                self.verify_api_key(key).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn verify_jwt(&self, token: &str) -> Result<Identity, GatewayError> {
+        let unauthorized = |message: String| GatewayError::Unauthorized(message);
+
+        let jwks = self
+            .jwks
+            .as_ref()
+            .ok_or_else(|| unauthorized("bearer tokens are not accepted".to_string()))?;
+        let header = decode_header(token).map_err(|e| unauthorized(format!("invalid bearer token: {}", e)))?;
+
+        let keys = jwks.keys();
+        let jwk = match header.kid.as_deref() {
+            Some(kid) => keys.find(kid),
+            None if keys.keys.len() == 1 => keys.keys.first(),
+            None => None,
+        }
+        .ok_or_else(|| unauthorized("bearer token is signed with an unknown key".to_string()))?;
+
+        // 키에 알고리즘이 정해져 있으면 토큰이 고른 알고리즘을 믿지 않는다.
+        if jwk
+            .common
+            .key_algorithm
+            .is_some_and(|algorithm| algorithm.to_string() != format!("{:?}", header.alg))
+        {
+            return Err(unauthorized("bearer token algorithm does not match its key".to_string()));
+        }
+
+        let key = DecodingKey::from_jwk(jwk).map_err(|e| unauthorized(format!("unusable signing key: {}", e)))?;
+        let mut validation = Validation::new(header.alg);
+        validation.set_required_spec_claims(&["exp", "sub"]);
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        match &self.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+
+        let claims = decode::<Map<String, Value>>(token, &key, &validation)
+            .map_err(|e| unauthorized(format!("invalid bearer token: {}", e)))?
+            .claims;
+        let id = claims
+            .get("sub")
+            .and_then(Value::as_str)
+            .ok_or_else(|| unauthorized("bearer token has no subject".to_string()))?;
+
+        Ok(Identity {
+            id: id.to_string(),
+            roles: roles(claim(&claims, &self.roles_claim)),
+            method: METHOD_JWT.to_string(),
+        })
+    }
+
+    fn verify_api_key(&self, key: &str) -> Result<Identity, GatewayError> {
+        let owner = self
+            .api_keys
+            .get(&hex::encode(Sha256::digest(key.as_bytes())))
+            .ok_or_else(|| GatewayError::Unauthorized("invalid api key".to_string()))?;
+
+        Ok(Identity {
+            id: owner.subject.clone(),
+            roles: owner.roles.clone(),
+            method: METHOD_API_KEY.to_string(),
+        })
+    }
+}
+
+/// 라우트 정책에 따라 호출자를 확인합니다. `[auth]` 가 없으면 모든 요청이 익명입니다.
+pub fn authorize(auth: Option<&Authenticator>, route: &Route, headers: &HeaderMap) -> Result<Option<Identity>, GatewayError> {
+    if route.auth == AuthPolicy::None {
+        return Ok(None);
+    }
+
+    let identity = match auth {
+        Some(auth) => auth.authenticate(headers)?,
+        None => None,
+    };
+
+    match identity {
+        None if route.auth == AuthPolicy::Required => {
+            Err(GatewayError::Unauthorized("credentials are required".to_string()))
+        }
+        Some(identity) if !route.roles.is_empty() && !route.roles.iter().any(|role| identity.roles.contains(role)) => {
+            Err(GatewayError::Forbidden(route.name.clone()))
+        }
+        identity => Ok(identity),
+    }
+}
+
+// `https://example.com/roles` 처럼 점이 들어간 클레임 이름을 먼저 찾고, 없으면 점을 중첩으로 보고 따라간다.
+fn claim<'a>(claims: &'a Map<String, Value>, name: &str) -> Option<&'a Value> {
+    claims.get(name).or_else(|| {
+        let (first, rest) = name.split_once('.')?;
+        claim(claims.get(first)?.as_object()?, rest)
+    })
+}
+
+// 역할은 쉼표로 이어 헤더에 담으므로 쉼표가 든 역할은 버린다.
+fn roles(value: Option<&Value>) -> Vec<String> {
+    let roles: Vec<&str> = match value {
+        Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).collect(),
+        // `scope` 처럼 공백으로 구분한 문자열
+        Some(Value::String(value)) => value.split_whitespace().collect(),
+        _ => Vec::new(),
+    };
+
+    roles
+        .into_iter()
+        .filter(|role| !role.is_empty() && !role.contains(','))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
+    use ntex::http::header::HeaderValue;
+    use serde_json::json;
+    use crate::config::Config;
+
+    pub(crate) const JWT_SECRET: &str = "gateway-test-signing-key";
+    // JWT_SECRET 의 base64url
+    const JWKS: &str = r#"{"keys":[{"kty":"oct","kid":"k1","alg":"HS256","k":"Z2F0ZXdheS10ZXN0LXNpZ25pbmcta2V5"}]}"#;
+
+    pub(crate) fn token(claims: Value) -> String {
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some("k1".to_string());
+        encode(&header, &claims, &EncodingKey::from_secret(JWT_SECRET.as_bytes())).unwrap()
+    }
+
+    pub(crate) fn expires() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + 600
+    }
+
+    pub(crate) fn jwks_file(name: &str) -> std::path::PathBuf {
+        let path = env::temp_dir().join(format!("kit-gateway-{}-{}.json", name, std::process::id()));
+        std::fs::write(&path, JWKS).unwrap();
+        path
+    }
+
+    fn authenticator() -> Authenticator {
+        let config = Config::parse(&format!(
+            r#"
+            [auth]
+            identity_secret = "secret"
+            jwks_file = "{}"
+            issuer = "https://issuer.example.com"
+            roles_claim = "realm_access.roles"
+
+            [[auth.api_key]]
+            name = "batch"
+            sha256 = "{}"
+            subject = "service:batch"
+            roles = ["admin"]
+            "#,
+            jwks_file("auth").display(),
+            hex::encode(Sha256::digest(b"batch-key"))
+        ))
+        .unwrap();
+
+        Authenticator::new(config.auth.as_ref().unwrap(), None).unwrap()
+    }
+
+    fn headers(name: HeaderName, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_authenticates_jwt_with_nested_roles() {
+        let token = token(json!({
+            "sub": "42",
+            "exp": expires(),
+            "iss": "https://issuer.example.com",
+            "realm_access": { "roles": ["admin", "a,b"] },
+        }));
+
+        let identity = authenticator()
+            .authenticate(&headers(AUTHORIZATION, &format!("Bearer {}", token)))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(identity.id, "42");
+        assert_eq!(identity.roles, ["admin"]);
+        assert_eq!(identity.method, METHOD_JWT);
+    }
+
+    #[test]
+    fn test_rejects_invalid_credentials() {
+        let authenticator = authenticator();
+        let wrong_issuer = token(json!({ "sub": "42", "exp": expires(), "iss": "https://other.example.com" }));
+        let expired = token(json!({ "sub": "42", "exp": 1, "iss": "https://issuer.example.com" }));
+
+        for headers in [
+            headers(AUTHORIZATION, &format!("Bearer {}", wrong_issuer)),
+            headers(AUTHORIZATION, &format!("Bearer {}", expired)),
+            headers(AUTHORIZATION, "Bearer not-a-token"),
+            headers(HeaderName::from_static("x-api-key"), "wrong-key"),
+        ] {
+            assert!(matches!(authenticator.authenticate(&headers), Err(GatewayError::Unauthorized(_))));
+        }
+        assert!(authenticator.authenticate(&headers(AUTHORIZATION, "Basic dXNlcjpwYXNz")).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_authenticates_api_key() {
+        let identity = authenticator()
+            .authenticate(&headers(HeaderName::from_static("x-api-key"), "batch-key"))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(identity.id, "service:batch");
+        assert_eq!(identity.method, METHOD_API_KEY);
+    }
+}
@@ -0,0 +1,152 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::config::Balance;
+use crate::upstream::Target;
+
+// 대상 하나당 링에 올리는 가상 노드 수. 많을수록 고르게 나뉜다.
+const VIRTUAL_NODES: usize = 160;
+
+/// 사용 가능한 대상 중 하나를 고릅니다.
+#[derive(Debug)]
+pub struct Balancer {
+    balance: Balance,
+    cursor: AtomicUsize,
+    ring: Vec<(u64, usize)>,
+}
+
+impl Balancer {
+    pub fn new(balance: Balance, targets: &[Arc<Target>]) -> Self {
+        let mut ring = Vec::new();
+
+        if balance == Balance::ConsistentHash {
+            for (index, target) in targets.iter().enumerate() {
+                for node in 0..VIRTUAL_NODES {
+                    ring.push((hash(format!("{}#{}", target.addr, node).as_bytes()), index));
+                }
+            }
+            ring.sort_unstable();
+        }
+
+        Self {
+            balance,
+            cursor: AtomicUsize::new(0),
+            ring,
+        }
+    }
+
+    /// 일관된 해시는 `key` 가 없으면 라운드 로빈으로 고릅니다.
+    pub fn pick(&self, targets: &[Arc<Target>], key: Option<&str>) -> Option<Arc<Target>> {
+        if targets.is_empty() {
+            return None;
+        }
+
+        match (self.balance, key) {
+            (Balance::ConsistentHash, Some(key)) => self.by_hash(targets, key),
+            (Balance::LeastConnections, _) => self.least_connections(targets),
+            _ => self.round_robin(targets),
+        }
+    }
+
+    fn round_robin(&self, targets: &[Arc<Target>]) -> Option<Arc<Target>> {
+        let start = self.cursor.fetch_add(1, Ordering::Relaxed);
+
+        (0..targets.len())
+            .map(|offset| &targets[(start + offset) % targets.len()])
+            .find(|target| target.is_available())
+            .cloned()
+    }
+
+    // 연결 수가 같으면 라운드 로빈 순서로 나눈다.
+    fn least_connections(&self, targets: &[Arc<Target>]) -> Option<Arc<Target>> {
+        let start = self.cursor.fetch_add(1, Ordering::Relaxed);
+
+        (0..targets.len())
+            .map(|offset| &targets[(start + offset) % targets.len()])
+            .filter(|target| target.is_available())
+            .min_by_key(|target| target.active())
+            .cloned()
+    }
+
+    // 키의 해시보다 크거나 같은 첫 노드부터 링을 돌며 사용 가능한 대상을 찾는다.
+    fn by_hash(&self, targets: &[Arc<Target>], key: &str) -> Option<Arc<Target>> {
+        let hash = hash(key.as_bytes());
+        let start = self.ring.partition_point(|(node, _)| *node < hash);
+
+        (0..self.ring.len())
+            .map(|offset| &targets[self.ring[(start + offset) % self.ring.len()].1])
+            .find(|target| target.is_available())
+            .cloned()
+    }
+}
+
+// FNV-1a 에 splitmix64 마무리를 더한다. 프로세스가 달라도 같은 키는 같은 대상으로 가야 하므로 고정된 해시를 쓰고,
+// 끝자리만 다른 키(`addr#1`, `addr#2`)도 링에 고르게 퍼지도록 비트를 섞는다.
+pub(crate) fn hash(bytes: &[u8]) -> u64 {
+    let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+
+    let hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    let hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^ (hash >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use crate::config::HealthConfig;
+
+    fn targets(count: usize) -> Vec<Arc<Target>> {
+        (0..count).map(|index| Arc::new(Target::new(format!("10.0.0.{}:80", index)))).collect()
+    }
+
+    fn pick(balancer: &Balancer, targets: &[Arc<Target>], key: Option<&str>) -> String {
+        balancer.pick(targets, key).unwrap().addr.clone()
+    }
+
+    #[test]
+    fn test_round_robin_skips_unavailable() {
+        let targets = targets(3);
+        let balancer = Balancer::new(Balance::RoundRobin, &targets);
+        targets[1].report_check(false, &HealthConfig { unhealthy_threshold: 1, ..Default::default() });
+
+        let picked = (0..4).map(|_| pick(&balancer, &targets, None)).collect::<Vec<_>>();
+
+        assert_eq!(picked, ["10.0.0.0:80", "10.0.0.2:80", "10.0.0.2:80", "10.0.0.0:80"]);
+    }
+
+    #[test]
+    fn test_least_connections_prefers_idle_target() {
+        let targets = targets(2);
+        let balancer = Balancer::new(Balance::LeastConnections, &targets);
+        let _busy = targets[0].begin();
+
+        assert_eq!(pick(&balancer, &targets, None), "10.0.0.1:80");
+        assert_eq!(pick(&balancer, &targets, None), "10.0.0.1:80");
+    }
+
+    #[test]
+    fn test_consistent_hash_is_stable_and_moves_only_removed_keys() {
+        let targets = targets(4);
+        let balancer = Balancer::new(Balance::ConsistentHash, &targets);
+        let keys = (0..200).map(|key| format!("user-{}", key)).collect::<Vec<_>>();
+
+        let before = keys
+            .iter()
+            .map(|key| (key.clone(), pick(&balancer, &targets, Some(key))))
+            .collect::<HashMap<_, _>>();
+        targets[2].report_check(false, &HealthConfig { unhealthy_threshold: 1, ..Default::default() });
+
+        for key in &keys {
+            let after = pick(&balancer, &targets, Some(key));
+
+            if before[key] == "10.0.0.2:80" {
+                assert_ne!(after, "10.0.0.2:80");
+            } else {
+                assert_eq!(after, before[key]);
+            }
+        }
+        assert!(before.values().filter(|addr| *addr == "10.0.0.2:80").count() > 20);
+    }
+}
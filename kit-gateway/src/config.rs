@@ -0,0 +1,397 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use ntex::http::Method;
+use ntex::http::header::{HeaderName, HeaderValue};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("failed to read config: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("failed to parse config: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("invalid config: {0}")]
+    Invalid(String),
+}
+
+/// 게이트웨이 설정 파일 (`gateway.toml`)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub server: ServerConfig,
+    #[serde(default, rename = "upstream")]
+    pub upstreams: Vec<UpstreamConfig>,
+    #[serde(default, rename = "route")]
+    pub routes: Vec<RouteConfig>,
+    pub auth: Option<AuthConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    /// 바인딩 주소. 재시작해야 반영됩니다.
+    pub addr: String,
+    pub max_request_body: usize,
+    pub max_response_body: usize,
+    pub timeout_ms: u64,
+    /// 설정 파일 변경을 확인하는 간격
+    pub reload_interval_ms: u64,
+    /// 게이트웨이 전체에서 동시에 열어 둘 SSE/WebSocket 연결 수
+    pub max_streams: usize,
+    /// SSE/WebSocket 연결에서 이 시간 동안 주고받은 데이터가 없으면 끊습니다.
+    pub idle_timeout_ms: u64,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            addr: "127.0.0.1:8000".to_string(),
+            max_request_body: 1024 * 1024,
+            max_response_body: 10 * 1024 * 1024,
+            timeout_ms: 30_000,
+            reload_interval_ms: 2_000,
+            max_streams: 10_000,
+            idle_timeout_ms: 60_000,
+        }
+    }
+}
+
+/// 엣지 인증 설정. 확인한 신원은 `kit_security::IdentitySigner` 로 서명한 헤더로 업스트림에 전달합니다.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    /// 신원 헤더 서명 키를 읽을 환경 변수. 업스트림 서비스와 같은 값을 써야 합니다.
+    pub identity_secret_env: String,
+    /// 서명 키를 직접 지정합니다. 개발과 테스트 용도이며 `identity_secret_env` 보다 우선합니다.
+    pub identity_secret: Option<String>,
+    /// JWT 서명 키 목록(JWKS) 파일
+    pub jwks_file: Option<PathBuf>,
+    /// JWT 서명 키 목록(JWKS)을 받을 주소
+    pub jwks_url: Option<String>,
+    /// JWKS 를 다시 읽는 간격
+    pub jwks_refresh_ms: u64,
+    pub issuer: Option<String>,
+    pub audience: Option<String>,
+    /// 역할 목록을 담은 클레임. `realm_access.roles` 처럼 점으로 중첩된 클레임을 가리킬 수 있습니다.
+    pub roles_claim: String,
+    pub api_key_header: String,
+    #[serde(rename = "api_key")]
+    pub api_keys: Vec<ApiKeyConfig>,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            identity_secret_env: "IDENTITY_SECRET".to_string(),
+            identity_secret: None,
+            jwks_file: None,
+            jwks_url: None,
+            jwks_refresh_ms: 300_000,
+            issuer: None,
+            audience: None,
+            roles_claim: "roles".to_string(),
+            api_key_header: "x-api-key".to_string(),
+            api_keys: Vec::new(),
+        }
+    }
+}
+
+/// 설정 파일에는 키 대신 키의 SHA-256 을 적습니다.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfig {
+    pub name: String,
+    /// API 키의 SHA-256 (hex)
+    pub sha256: String,
+    /// 업스트림에 전달할 사용자 식별자
+    pub subject: String,
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+/// 라우트의 인증 정책
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthPolicy {
+    /// 자격 증명을 확인하지 않고 익명으로 전달합니다.
+    None,
+    /// 자격 증명이 있으면 확인하고, 없으면 익명으로 전달합니다.
+    #[default]
+    Optional,
+    /// 확인된 자격 증명이 없으면 `401` 로 거절합니다.
+    Required,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Balance {
+    #[default]
+    RoundRobin,
+    LeastConnections,
+    ConsistentHash,
+}
+
+/// 일관된 해시의 키. 키가 없는 요청은 라운드 로빈으로 보냅니다.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HashBy {
+    #[default]
+    Ip,
+    Path,
+    Header(String),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpstreamConfig {
+    pub name: String,
+    /// `host:port` 목록
+    pub targets: Vec<String>,
+    #[serde(default)]
+    pub balance: Balance,
+    #[serde(default)]
+    pub hash_by: HashBy,
+    #[serde(default)]
+    pub health: HealthConfig,
+    /// 이 이름의 쿠키로 처음 고른 대상을 기억해 같은 클라이언트를 같은 대상으로 보냅니다.
+    /// WebSocket 핸드셰이크에는 쿠키를 심을 수 없으므로 앞선 HTTP/SSE 응답에서 받은 쿠키를 따릅니다.
+    pub sticky_cookie: Option<String>,
+}
+
+/// 능동 검사(`path` 가 있을 때 주기적으로 GET)와 수동 검사(프록시 실패 횟수) 설정
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthConfig {
+    pub path: Option<String>,
+    pub interval_ms: u64,
+    pub timeout_ms: u64,
+    /// 능동 검사에서 연속으로 이만큼 성공하면 정상으로 되돌립니다.
+    pub healthy_threshold: u32,
+    /// 능동 검사에서 연속으로 이만큼 실패하면 제외합니다.
+    pub unhealthy_threshold: u32,
+    /// 프록시 요청이 연속으로 이만큼 실패하면 `eject_ms` 동안 제외합니다.
+    pub max_failures: u32,
+    pub eject_ms: u64,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            interval_ms: 5_000,
+            timeout_ms: 2_000,
+            healthy_threshold: 2,
+            unhealthy_threshold: 3,
+            max_failures: 3,
+            eject_ms: 30_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RouteConfig {
+    pub name: String,
+    /// 없으면 모든 호스트. `*.example.com` 처럼 하위 도메인 전체를 지정할 수 있습니다.
+    pub host: Option<String>,
+    /// 경로 접두사. 경로 구분자 단위로 비교합니다.
+    #[serde(default = "root")]
+    pub path: String,
+    /// 비어 있으면 모든 메서드
+    #[serde(default)]
+    pub methods: Vec<String>,
+    pub upstream: String,
+    /// 업스트림으로 보낼 때 `path` 접두사를 뗍니다.
+    #[serde(default)]
+    pub strip_prefix: bool,
+    #[serde(default)]
+    pub request_headers: HeaderRules,
+    #[serde(default)]
+    pub response_headers: HeaderRules,
+    pub max_request_body: Option<usize>,
+    pub max_response_body: Option<usize>,
+    pub timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub auth: AuthPolicy,
+    /// 이 중 하나라도 가진 호출자만 허용합니다. `auth = "required"` 와 함께 씁니다.
+    #[serde(default)]
+    pub roles: Vec<String>,
+    /// 이 라우트에서 동시에 열어 둘 SSE/WebSocket 연결 수
+    pub max_streams: Option<usize>,
+    pub idle_timeout_ms: Option<u64>,
+}
+
+fn root() -> String {
+    "/".to_string()
+}
+
+/// `remove` 를 먼저 적용한 뒤 `set` 으로 덮어씁니다.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HeaderRules {
+    pub set: BTreeMap<String, String>,
+    pub remove: Vec<String>,
+}
+
+impl Config {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn parse(source: &str) -> Result<Self, ConfigError> {
+        let config: Config = toml::from_str(source)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |message: String| Err(ConfigError::Invalid(message));
+
+        let mut upstreams = HashSet::new();
+        for upstream in &self.upstreams {
+            if !upstreams.insert(upstream.name.as_str()) {
+                return invalid(format!("duplicate upstream `{}`", upstream.name));
+            }
+            if upstream.targets.is_empty() {
+                return invalid(format!("upstream `{}` has no targets", upstream.name));
+            }
+            if let Some(cookie) = &upstream.sticky_cookie
+                && (cookie.is_empty() || !cookie.bytes().all(|byte| byte.is_ascii_alphanumeric() || b"-_.".contains(&byte)))
+            {
+                return invalid(format!("upstream `{}` has invalid sticky cookie name `{}`", upstream.name, cookie));
+            }
+        }
+
+        for route in &self.routes {
+            if !upstreams.contains(route.upstream.as_str()) {
+                return invalid(format!("route `{}` refers to unknown upstream `{}`", route.name, route.upstream));
+            }
+            if !route.path.starts_with('/') {
+                return invalid(format!("route `{}` path must start with `/`", route.name));
+            }
+            if let Some(method) = route.methods.iter().find(|method| method.parse::<Method>().is_err()) {
+                return invalid(format!("route `{}` has invalid method `{}`", route.name, method));
+            }
+            if route.auth == AuthPolicy::Required && self.auth.is_none() {
+                return invalid(format!("route `{}` requires auth but `[auth]` is missing", route.name));
+            }
+            if !route.roles.is_empty() && route.auth != AuthPolicy::Required {
+                return invalid(format!("route `{}` has roles but does not require auth", route.name));
+            }
+
+            for rules in [&route.request_headers, &route.response_headers] {
+                for name in rules.set.keys().chain(rules.remove.iter()) {
+                    if HeaderName::try_from(name.as_str()).is_err() {
+                        return invalid(format!("route `{}` has invalid header `{}`", route.name, name));
+                    }
+                }
+                if let Some(value) = rules.set.values().find(|value| HeaderValue::from_str(value).is_err()) {
+                    return invalid(format!("route `{}` has invalid header value `{}`", route.name, value));
+                }
+            }
+        }
+
+        if let Some(auth) = &self.auth {
+            auth.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl AuthConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |message: String| Err(ConfigError::Invalid(message));
+
+        if self.jwks_file.is_some() && self.jwks_url.is_some() {
+            return invalid("`auth.jwks_file` and `auth.jwks_url` are mutually exclusive".to_string());
+        }
+        if HeaderName::try_from(self.api_key_header.as_str()).is_err() {
+            return invalid(format!("invalid api key header `{}`", self.api_key_header));
+        }
+
+        let mut keys = HashSet::new();
+        for key in &self.api_keys {
+            if key.sha256.len() != 64 || !key.sha256.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+                return invalid(format!("api key `{}` must have a hex encoded sha256", key.name));
+            }
+            if !keys.insert(key.sha256.to_ascii_lowercase()) {
+                return invalid(format!("api key `{}` is a duplicate", key.name));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_example_config() {
+        let config = Config::parse(include_str!("../gateway.toml")).unwrap();
+
+        assert_eq!(config.upstreams[0].health.path.as_deref(), Some("/health/ready"));
+        assert_eq!(config.routes[0].response_headers.set["x-served-by"], "kit-gateway");
+        assert!(config.routes[0].strip_prefix);
+        assert!(config.auth.as_ref().is_none_or(|auth| auth.api_keys.is_empty()));
+    }
+
+    #[test]
+    fn test_parse_hash_by_header() {
+        let config = Config::parse(
+            r#"
+            [[upstream]]
+            name = "a"
+            targets = ["127.0.0.1:1"]
+            balance = "consistent_hash"
+            hash_by = { header = "x-user-id" }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.upstreams[0].balance, Balance::ConsistentHash);
+        assert_eq!(config.upstreams[0].hash_by, HashBy::Header("x-user-id".to_string()));
+    }
+
+    #[test]
+    fn test_rejects_unknown_upstream() {
+        let error = Config::parse(
+            r#"
+            [[route]]
+            name = "api"
+            upstream = "missing"
+            "#,
+        )
+        .unwrap_err();
+
+        assert!(matches!(error, ConfigError::Invalid(message) if message.contains("missing")));
+    }
+
+    #[test]
+    fn test_required_auth_needs_auth_section() {
+        let source = r#"
+            [[upstream]]
+            name = "a"
+            targets = ["127.0.0.1:1"]
+
+            [[route]]
+            name = "admin"
+            upstream = "a"
+            auth = "required"
+            roles = ["admin"]
+            "#;
+
+        let error = Config::parse(source).unwrap_err();
+        let config = Config::parse(&format!("{}\n[auth]\njwks_file = \"jwks.json\"", source)).unwrap();
+
+        assert!(matches!(error, ConfigError::Invalid(message) if message.contains("[auth]")));
+        assert_eq!(config.routes[0].auth, AuthPolicy::Required);
+        assert_eq!(config.auth.unwrap().roles_claim, "roles");
+    }
+}
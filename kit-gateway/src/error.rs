@@ -0,0 +1,73 @@
+use ntex::http::StatusCode;
+use ntex::http::header::{CONTENT_TYPE, WWW_AUTHENTICATE};
+use ntex::ws::error::HandshakeError;
+use ntex::web::{DefaultError, HttpRequest, HttpResponse, WebResponseError};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum GatewayError {
+    #[error("no route for {0} {1}")]
+    NoRoute(String, String),
+
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("route `{0}` is not allowed for the caller's roles")]
+    Forbidden(String),
+
+    #[error("no healthy target in upstream `{0}`")]
+    NoTarget(String),
+
+    #[error("request body is larger than {0} bytes")]
+    PayloadTooLarge(usize),
+
+    #[error("failed to read request body: {0}")]
+    Payload(String),
+
+    #[error("upstream `{0}` did not respond in time")]
+    Timeout(String),
+
+    #[error("upstream `{0}` failed: {1}")]
+    Upstream(String, String),
+
+    #[error("upstream `{0}` response is larger than {1} bytes")]
+    ResponseTooLarge(String, usize),
+
+    #[error("route `{0}` has too many open streams")]
+    TooManyStreams(String),
+
+    #[error("websocket handshake failed: {0}")]
+    Handshake(String),
+}
+
+impl From<HandshakeError> for GatewayError {
+    fn from(e: HandshakeError) -> Self {
+        GatewayError::Handshake(e.to_string())
+    }
+}
+
+impl WebResponseError<DefaultError> for GatewayError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            GatewayError::NoRoute(..) => StatusCode::NOT_FOUND,
+            GatewayError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            GatewayError::Forbidden(_) => StatusCode::FORBIDDEN,
+            GatewayError::NoTarget(_) | GatewayError::TooManyStreams(_) => StatusCode::SERVICE_UNAVAILABLE,
+            GatewayError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            GatewayError::Payload(_) | GatewayError::Handshake(_) => StatusCode::BAD_REQUEST,
+            GatewayError::Timeout(_) => StatusCode::GATEWAY_TIMEOUT,
+            GatewayError::Upstream(..) | GatewayError::ResponseTooLarge(..) => StatusCode::BAD_GATEWAY,
+        }
+    }
+
+    fn error_response(&self, _: &HttpRequest) -> HttpResponse {
+        let mut response = HttpResponse::build(WebResponseError::<DefaultError>::status_code(self));
+        response.header(CONTENT_TYPE, "text/plain; charset=utf-8");
+
+        if let GatewayError::Unauthorized(_) = self {
+            response.header(WWW_AUTHENTICATE, "Bearer");
+        }
+
+        response.body(self.to_string())
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use crate::auth::Authenticator;
+use crate::config::{Config, ConfigError, ServerConfig};
+use crate::route::RouteTable;
+use crate::stream::StreamLimits;
+use crate::upstream::Upstream;
+
+/// 한 번에 적용되는 라우팅 설정. 요청은 처리하는 동안 같은 스냅샷을 사용합니다.
+#[derive(Debug)]
+pub struct Snapshot {
+    pub server: ServerConfig,
+    pub routes: RouteTable,
+    pub upstreams: HashMap<String, Arc<Upstream>>,
+    pub auth: Option<Authenticator>,
+}
+
+/// 워커들이 공유하는 게이트웨이 상태
+#[derive(Debug, Clone)]
+pub struct Gateway {
+    snapshot: Arc<RwLock<Arc<Snapshot>>>,
+    streams: Arc<StreamLimits>,
+}
+
+impl Gateway {
+    pub fn new(config: Config) -> Result<Self, ConfigError> {
+        Ok(Self {
+            snapshot: Arc::new(RwLock::new(Arc::new(build(config, None)?))),
+            streams: Arc::default(),
+        })
+    }
+
+    pub fn snapshot(&self) -> Arc<Snapshot> {
+        self.snapshot.read().unwrap().clone()
+    }
+
+    /// 열려 있는 SSE/WebSocket 연결 수. 설정을 다시 읽어도 이어집니다.
+    pub fn streams(&self) -> &StreamLimits {
+        &self.streams
+    }
+
+    /// 새 설정으로 바꿉니다. 주소가 같은 대상은 헬스 상태와 연결 수를, 출처가 같은 JWKS 는 받아 둔 키를 이어받습니다.
+    ///
+    /// 실패하면 기존 설정을 그대로 둡니다.
+    pub fn reload(&self, config: Config) -> Result<(), ConfigError> {
+        let previous = self.snapshot();
+        *self.snapshot.write().unwrap() = Arc::new(build(config, Some(&previous))?);
+        Ok(())
+    }
+}
+
+fn build(config: Config, previous: Option<&Snapshot>) -> Result<Snapshot, ConfigError> {
+    let auth = config
+        .auth
+        .as_ref()
+        .map(|auth| Authenticator::new(auth, previous.and_then(|previous| previous.auth.as_ref())))
+        .transpose()?;
+
+    let upstreams = config
+        .upstreams
+        .iter()
+        .map(|upstream| {
+            let existing = previous.and_then(|previous| previous.upstreams.get(&upstream.name));
+            (upstream.name.clone(), Arc::new(Upstream::new(upstream, existing.map(Arc::as_ref))))
+        })
+        .collect();
+
+    Ok(Snapshot {
+        routes: RouteTable::new(&config.routes, &config.server),
+        server: config.server,
+        upstreams,
+        auth,
+    })
+}
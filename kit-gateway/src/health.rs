@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use futures::future::join_all;
+use ntex::http::client::Client;
+use ntex::time::sleep;
+use crate::gateway::Gateway;
+
+const TICK: Duration = Duration::from_secs(1);
+
+/// 헬스 검사 경로가 있는 업스트림의 대상마다 주기적으로 `GET` 을 보내고, `2xx` 가 아니면 실패로 셉니다.
+///
+/// 매번 현재 스냅샷을 읽으므로 다시 읽은 설정도 그대로 따릅니다.
+pub async fn run(gateway: Gateway) {
+    let client = Client::build().disable_redirects().finish();
+    let mut checked_at: HashMap<String, Instant> = HashMap::new();
+
+    loop {
+        let snapshot = gateway.snapshot();
+        let now = Instant::now();
+        let mut checks = Vec::new();
+
+        for upstream in snapshot.upstreams.values() {
+            let Some(path) = upstream.health.path.as_deref() else {
+                continue;
+            };
+
+            let interval = Duration::from_millis(upstream.health.interval_ms);
+            if checked_at.get(&upstream.name).is_some_and(|at| now.duration_since(*at) < interval) {
+                continue;
+            }
+            checked_at.insert(upstream.name.clone(), now);
+
+            checks.extend(upstream.targets.iter().map(|target| {
+                let request = client
+                    .get(format!("http://{}{}", target.addr, path))
+                    .timeout(Duration::from_millis(upstream.health.timeout_ms));
+
+                async move {
+                    let ok = request.send().await.is_ok_and(|response| response.status().is_success());
+                    let was_healthy = target.is_healthy();
+                    target.report_check(ok, &upstream.health);
+
+                    if was_healthy != target.is_healthy() {
+                        println!(
+                            "[gateway] `{}` in `{}` is now {}",
+                            target.addr,
+                            upstream.name,
+                            if ok { "healthy" } else { "unhealthy" }
+                        );
+                    }
+                }
+            }));
+        }
+
+        // 느린 업스트림이 다른 업스트림의 검사를 늦추지 않도록 모든 대상을 함께 검사한다.
+        join_all(checks).await;
+
+        sleep(TICK).await;
+    }
+}
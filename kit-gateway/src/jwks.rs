@@ -0,0 +1,132 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use jsonwebtoken::jwk::JwkSet;
+use ntex::http::client::Client;
+use ntex::time::sleep;
+use crate::config::ConfigError;
+use crate::gateway::Gateway;
+
+const TICK: Duration = Duration::from_secs(1);
+// 읽지 못했으면 갱신 간격을 기다리지 않고 이 간격으로 다시 시도한다.
+const RETRY: Duration = Duration::from_secs(5);
+const MAX_JWKS_BODY: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JwksSource {
+    File(PathBuf),
+    Url(String),
+}
+
+#[derive(Debug, Clone, Default)]
+struct Refresh {
+    attempted_at: Option<Instant>,
+    failed: bool,
+}
+
+/// JWT 서명 키 목록. [`run`] 이 주기적으로 다시 읽어 키 교체를 따라갑니다.
+#[derive(Debug)]
+pub struct Jwks {
+    source: JwksSource,
+    interval: Duration,
+    keys: RwLock<Arc<JwkSet>>,
+    refresh: Mutex<Refresh>,
+}
+
+impl Jwks {
+    /// 파일은 바로 읽어 실패하면 설정 오류로 돌려주고, 주소는 [`run`] 이 처음 받아올 때까지 비어 있습니다.
+    ///
+    /// 이전 설정과 출처가 같으면 받아 둔 키를 그대로 씁니다.
+    pub fn new(source: JwksSource, interval: Duration, previous: Option<&Jwks>) -> Result<Self, ConfigError> {
+        let jwks = Self {
+            source,
+            interval,
+            keys: RwLock::new(Arc::new(JwkSet { keys: Vec::new() })),
+            refresh: Mutex::new(Refresh::default()),
+        };
+
+        match previous.filter(|previous| previous.source == jwks.source) {
+            Some(previous) => {
+                *jwks.keys.write().unwrap() = previous.keys();
+                *jwks.refresh.lock().unwrap() = previous.refresh.lock().unwrap().clone();
+            }
+            None => {
+                if let JwksSource::File(path) = &jwks.source {
+                    let keys = read_file(path).map_err(ConfigError::Invalid)?;
+                    *jwks.keys.write().unwrap() = Arc::new(keys);
+                    jwks.refresh.lock().unwrap().attempted_at = Some(Instant::now());
+                }
+            }
+        }
+
+        Ok(jwks)
+    }
+
+    pub fn keys(&self) -> Arc<JwkSet> {
+        self.keys.read().unwrap().clone()
+    }
+
+    fn is_due(&self, now: Instant) -> bool {
+        let refresh = self.refresh.lock().unwrap();
+
+        match refresh.attempted_at {
+            None => true,
+            Some(at) if refresh.failed => now.duration_since(at) >= RETRY,
+            Some(at) => now.duration_since(at) >= self.interval,
+        }
+    }
+
+    /// 읽지 못하면 기존 키를 그대로 둡니다.
+    async fn refresh(&self, client: &Client) -> Result<(), String> {
+        self.refresh.lock().unwrap().attempted_at = Some(Instant::now());
+
+        let result = match &self.source {
+            JwksSource::File(path) => read_file(path),
+            JwksSource::Url(url) => fetch(client, url).await,
+        };
+        self.refresh.lock().unwrap().failed = result.is_err();
+
+        *self.keys.write().unwrap() = Arc::new(result?);
+        Ok(())
+    }
+
+    fn describe(&self) -> String {
+        match &self.source {
+            JwksSource::File(path) => path.display().to_string(),
+            JwksSource::Url(url) => url.clone(),
+        }
+    }
+}
+
+/// 현재 설정의 JWKS 를 갱신 간격마다 다시 읽습니다.
+pub async fn run(gateway: Gateway) {
+    let client = Client::build().disable_redirects().finish();
+
+    loop {
+        let snapshot = gateway.snapshot();
+
+        if let Some(jwks) = snapshot.auth.as_ref().and_then(|auth| auth.jwks())
+            && jwks.is_due(Instant::now())
+            && let Err(e) = jwks.refresh(&client).await
+        {
+            eprintln!("[gateway] failed to refresh jwks from {}: {}", jwks.describe(), e);
+        }
+
+        sleep(TICK).await;
+    }
+}
+
+fn read_file(path: &PathBuf) -> Result<JwkSet, String> {
+    let source = std::fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&source).map_err(|e| format!("invalid jwks in {}: {}", path.display(), e))
+}
+
+async fn fetch(client: &Client, url: &str) -> Result<JwkSet, String> {
+    let mut response = client.get(url).send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("unexpected status {}", response.status()));
+    }
+
+    let body = response.body().limit(MAX_JWKS_BODY).await.map_err(|e| e.to_string())?;
+    serde_json::from_slice(&body).map_err(|e| format!("invalid jwks: {}", e))
+}
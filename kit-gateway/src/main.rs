@@ -1,3 +1,45 @@
-fn main() {
-    println!("Hello, world!");
+mod auth;
+mod balancer;
+mod config;
+mod error;
+mod gateway;
+mod health;
+mod jwks;
+mod proxy;
+mod reload;
+mod route;
+mod stream;
+mod upstream;
+mod websocket;
+
+use std::env;
+use std::path::PathBuf;
+use ntex::http::client::Client;
+use ntex::web::{self, App, HttpServer};
+use crate::config::Config;
+use crate::gateway::Gateway;
+
+#[ntex::main]
+async fn main() -> std::io::Result<()> {
+    let path = PathBuf::from(env::var("GATEWAY_CONFIG").unwrap_or_else(|_| "gateway.toml".to_string()));
+    let config = Config::load(&path).map_err(std::io::Error::other)?;
+    let addr = config.server.addr.clone();
+
+    let gateway = Gateway::new(config).map_err(std::io::Error::other)?;
+    ntex::rt::spawn(health::run(gateway.clone()));
+    ntex::rt::spawn(jwks::run(gateway.clone()));
+    ntex::rt::spawn(reload::watch(gateway.clone(), path));
+
+    println!("\nGATEWAY ADDRESS IS: {}", addr);
+
+    HttpServer::new(move || {
+        App::new()
+            .state(gateway.clone())
+            // 리다이렉트는 따라가지 않고 클라이언트에게 그대로 돌려준다.
+            .state(Client::build().disable_redirects().disable_timeout().finish())
+            .default_service(web::to(proxy::forward))
+    })
+        .bind(addr)?
+        .run()
+        .await
 }
@@ -0,0 +1,574 @@
+use std::sync::Arc;
+use futures::StreamExt;
+use kit_security::IDENTITY_HEADERS;
+use ntex::http::client::Client;
+use ntex::http::client::error::SendRequestError;
+use ntex::http::error::PayloadError;
+use ntex::http::header::{self, HeaderMap, HeaderName, HeaderValue};
+use ntex::util::BytesMut;
+use ntex::web::types::{Payload, State};
+use ntex::web::{HttpRequest, HttpResponse};
+use crate::auth::{Authenticator, authorize};
+use crate::config::HashBy;
+use crate::error::GatewayError;
+use crate::gateway::Gateway;
+use crate::route::Route;
+use crate::stream::idle_stream;
+use crate::upstream::{Target, Upstream};
+use crate::websocket;
+
+// 연결 하나에만 의미가 있어 전달하지 않는 헤더 (RFC 9110 7.6.1)
+const HOP_BY_HOP: [HeaderName; 8] = [
+    header::CONNECTION,
+    HeaderName::from_static("keep-alive"),
+    header::PROXY_AUTHENTICATE,
+    header::PROXY_AUTHORIZATION,
+    header::TE,
+    header::TRAILER,
+    header::TRANSFER_ENCODING,
+    header::UPGRADE,
+];
+
+const X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+const X_FORWARDED_HOST: HeaderName = HeaderName::from_static("x-forwarded-host");
+const X_FORWARDED_PROTO: HeaderName = HeaderName::from_static("x-forwarded-proto");
+
+/// 라우팅 테이블에서 라우트를 찾아 업스트림으로 요청을 전달합니다.
+///
+/// 클라이언트가 보낸 신원 헤더는 항상 지우고, 라우트 정책에 따라 확인한 호출자가 있으면 서명한 신원 헤더를 붙입니다.
+///
+/// WebSocket 업그레이드와 `text/event-stream` 응답은 버퍼에 모으지 않고 이어 주며, 연결 수 한도와 유휴 타임아웃을 적용합니다.
+///
+/// 연결 실패, 타임아웃, `502`/`503`/`504` 응답은 대상의 수동 헬스 검사 실패로 셉니다.
+pub async fn forward(
+    req: HttpRequest,
+    payload: Payload,
+    gateway: State<Gateway>,
+    client: State<Client>,
+) -> Result<HttpResponse, GatewayError> {
+    let snapshot = gateway.snapshot();
+    let host = req.connection_info().host().to_string();
+
+    let route = snapshot
+        .routes
+        .find(&host, req.method(), req.path())
+        .ok_or_else(|| GatewayError::NoRoute(req.method().to_string(), req.path().to_string()))?;
+    let identity = authorize(snapshot.auth.as_ref(), &route, req.headers())?;
+    let upstream = snapshot
+        .upstreams
+        .get(&route.upstream)
+        .ok_or_else(|| GatewayError::NoTarget(route.upstream.clone()))?;
+
+    let sticky = sticky_target(&req, upstream);
+    let target = match &sticky {
+        Some(target) => target.clone(),
+        None => upstream
+            .select(hash_key(&req, upstream).as_deref())
+            .ok_or_else(|| GatewayError::NoTarget(upstream.name.clone()))?,
+    };
+
+    let mut headers = HeaderMap::new();
+    forward_headers(&req, &host, snapshot.auth.as_ref(), &mut headers);
+    route.request_headers.apply(&mut headers);
+    if let (Some(auth), Some(identity)) = (&snapshot.auth, &identity) {
+        for (name, value) in auth.sign(identity) {
+            let value = HeaderValue::from_str(&value)
+                .map_err(|_| GatewayError::Unauthorized("identity cannot be forwarded".to_string()))?;
+            headers.insert(HeaderName::from_static(name), value);
+        }
+    }
+
+    let acquire_stream = || {
+        gateway
+            .streams()
+            .acquire(&route, snapshot.server.max_streams)
+            .ok_or_else(|| GatewayError::TooManyStreams(route.name.clone()))
+    };
+
+    if websocket::is_upgrade(&req) {
+        let slot = acquire_stream()?;
+        return websocket::tunnel(req, &route, upstream, target, headers, slot).await;
+    }
+
+    let body = read_body(&req, payload, route.max_request_body).await?;
+
+    let url = format!("http://{}{}", target.addr, route.upstream_path(req.path(), req.uri().query()));
+    let mut request = client
+        .request(req.method().clone(), url)
+        .no_decompress()
+        .timeout(route.timeout);
+    for (name, value) in &headers {
+        request.headers_mut().append(name.clone(), value.clone());
+    }
+
+    let active = target.begin();
+    let mut response = match request.send_body(body).await {
+        Ok(response) => response,
+        Err(e) => {
+            target.report_failure(&upstream.health);
+            eprintln!("[gateway] route `{}` to `{}` failed: {}", route.name, target.addr, e);
+
+            return Err(match e {
+                SendRequestError::Timeout => GatewayError::Timeout(upstream.name.clone()),
+                e => GatewayError::Upstream(upstream.name.clone(), e.to_string()),
+            });
+        }
+    };
+
+    let status = response.status();
+    if matches!(status.as_u16(), 502..=504) {
+        target.report_failure(&upstream.health);
+    } else {
+        target.report_success();
+    }
+
+    let mut built = if is_event_stream(response.headers()) {
+        let slot = acquire_stream()?;
+        let headers = response.headers().clone();
+        let body = idle_stream(Box::pin(response), route.idle_timeout, (slot, active));
+
+        let mut built = HttpResponse::build(status).streaming(Box::pin(body));
+        copy_response_headers(&route, &headers, built.headers_mut());
+        built
+    } else {
+        let body = response
+            .body()
+            .limit(route.max_response_body)
+            .await
+            .map_err(|e| match e {
+                PayloadError::Overflow => GatewayError::ResponseTooLarge(upstream.name.clone(), route.max_response_body),
+                e => GatewayError::Upstream(upstream.name.clone(), e.to_string()),
+            })?;
+
+        let mut built = HttpResponse::build(status).body(body);
+        copy_response_headers(&route, response.headers(), built.headers_mut());
+        built
+    };
+
+    if sticky.is_none()
+        && let Some(cookie) = &upstream.sticky_cookie
+        && let Ok(value) = HeaderValue::from_str(&format!("{}={}; Path=/; HttpOnly; SameSite=Lax", cookie, target.id))
+    {
+        built.headers_mut().append(header::SET_COOKIE, value);
+    }
+
+    Ok(built)
+}
+
+fn is_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.trim_start().to_ascii_lowercase().starts_with("text/event-stream"))
+}
+
+// 고정 라우팅 쿠키가 가리키는 대상. 쿠키가 없거나 대상이 빠졌으면 평소대로 고른다.
+fn sticky_target(req: &HttpRequest, upstream: &Upstream) -> Option<Arc<Target>> {
+    let name = upstream.sticky_cookie.as_deref()?;
+
+    req.headers()
+        .get_all(header::COOKIE)
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .and_then(|(_, id)| upstream.find(id))
+}
+
+fn hash_key(req: &HttpRequest, upstream: &Upstream) -> Option<String> {
+    match &upstream.hash_by {
+        HashBy::Ip => req.peer_addr().map(|addr| addr.ip().to_string()),
+        HashBy::Path => Some(req.path().to_string()),
+        HashBy::Header(name) => req
+            .headers()
+            .get(name.as_str())
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+    }
+}
+
+async fn read_body(req: &HttpRequest, mut payload: Payload, limit: usize) -> Result<ntex::util::Bytes, GatewayError> {
+    let declared = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+    if declared.is_some_and(|length| length > limit) {
+        return Err(GatewayError::PayloadTooLarge(limit));
+    }
+
+    let mut body = BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk.map_err(|e| GatewayError::Payload(e.to_string()))?;
+
+        if body.len() + chunk.len() > limit {
+            return Err(GatewayError::PayloadTooLarge(limit));
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body.freeze())
+}
+
+// 신원 헤더와 게이트웨이에서 확인하는 API 키는 업스트림으로 넘기지 않는다.
+fn forward_headers(req: &HttpRequest, host: &str, auth: Option<&Authenticator>, headers: &mut HeaderMap) {
+    for (name, value) in req.headers() {
+        if *name != header::HOST
+            && !HOP_BY_HOP.contains(name)
+            && !IDENTITY_HEADERS.contains(&name.as_str())
+            && auth.is_none_or(|auth| auth.api_key_header() != name)
+        {
+            headers.append(name.clone(), value.clone());
+        }
+    }
+
+    // 게이트웨이가 엣지이므로 클라이언트가 보낸 값은 버리고 실제 상대 주소만 전달한다.
+    headers.remove(&X_FORWARDED_FOR);
+    if let Some(peer) = req.peer_addr()
+        && let Ok(value) = HeaderValue::from_str(&peer.ip().to_string())
+    {
+        headers.insert(X_FORWARDED_FOR, value);
+    }
+
+    if let Ok(value) = HeaderValue::from_str(host) {
+        headers.insert(X_FORWARDED_HOST, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(req.connection_info().scheme()) {
+        headers.insert(X_FORWARDED_PROTO, value);
+    }
+}
+
+fn copy_response_headers(route: &Route, from: &HeaderMap, to: &mut HeaderMap) {
+    for (name, value) in from {
+        if *name != header::CONTENT_LENGTH && !HOP_BY_HOP.contains(name) {
+            to.append(name.clone(), value.clone());
+        }
+    }
+    route.response_headers.apply(to);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::http::StatusCode;
+    use kit_security::IdentitySigner;
+    use ntex::web::{self, App, test};
+    use serde_json::json;
+    use crate::auth::tests::{expires, jwks_file, token};
+    use crate::config::Config;
+    use futures::stream;
+    use ntex::service::{fn_factory_with_config, fn_service};
+    use ntex::util::{ByteString, Bytes};
+    use ntex::ws::{Frame, Message};
+
+    async fn upstream(name: &'static str) -> test::TestServer {
+        test::server(move || {
+            App::new().default_service(web::to(move |req: HttpRequest, body: ntex::util::Bytes| async move {
+                let forwarded = req
+                    .headers()
+                    .get("x-forwarded-host")
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or("")
+                    .to_string();
+
+                HttpResponse::Ok()
+                    .header("x-upstream", name)
+                    .header("x-internal", "secret")
+                    .body(format!("{} {} {} {}", name, req.uri(), forwarded, body.len()))
+            }))
+        })
+    }
+
+    async fn gateway(config: &str) -> ntex::service::Pipeline<impl ntex::service::Service<ntex::http::Request, Response = web::WebResponse, Error = web::Error>> {
+        let gateway = Gateway::new(Config::parse(config).unwrap()).unwrap();
+
+        test::init_service(
+            App::new()
+                .state(gateway)
+                .state(Client::new())
+                .default_service(web::to(forward)),
+        )
+        .await
+    }
+
+    // `/events` 는 한 번 보내고 멈추는 SSE, 나머지는 텍스트 프레임을 되돌려 주는 WebSocket
+    async fn streaming_upstream() -> test::TestServer {
+        test::server(|| {
+            App::new().default_service(web::to(|req: HttpRequest| async move {
+                if req.path() == "/events" {
+                    let events = stream::iter([Ok::<_, std::io::Error>(Bytes::from_static(b"data: 1\n\n"))]).chain(stream::pending());
+                    return Ok(HttpResponse::Ok().content_type("text/event-stream").streaming(Box::pin(events)));
+                }
+
+                web::ws::start::<_, _, GatewayError>(
+                    req,
+                    fn_factory_with_config(|_| async {
+                        Ok::<_, GatewayError>(fn_service(|frame: Frame| async move {
+                            Ok::<_, GatewayError>(match frame {
+                                Frame::Text(text) => Some(Message::Text(ByteString::try_from(text).unwrap())),
+                                _ => None,
+                            })
+                        }))
+                    }),
+                )
+                .await
+            }))
+        })
+    }
+
+    #[test]
+    fn test_overwrites_client_forwarded_for() {
+        let req = test::TestRequest::default()
+            .header("x-forwarded-for", "203.0.113.7")
+            .to_http_request();
+        let mut headers = HeaderMap::new();
+
+        forward_headers(&req, "gateway.local", None, &mut headers);
+
+        // 테스트 요청에는 소켓이 없으므로 클라이언트가 보낸 값만 지워진다.
+        assert!(headers.get(&X_FORWARDED_FOR).is_none());
+        assert_eq!(headers.get(&X_FORWARDED_HOST).unwrap(), "gateway.local");
+    }
+
+    #[ntex::test]
+    async fn test_forwards_with_rewrites_and_round_robin() {
+        let first = upstream("first").await;
+        let second = upstream("second").await;
+        let config = format!(
+            r#"
+            [[upstream]]
+            name = "api"
+            targets = ["{}", "{}"]
+
+            [[route]]
+            name = "api"
+            path = "/api"
+            strip_prefix = true
+            upstream = "api"
+
+            [route.response_headers]
+            remove = ["x-internal"]
+            set = {{ "x-served-by" = "gateway" }}
+            "#,
+            first.addr(),
+            second.addr()
+        );
+        let app = gateway(&config).await;
+
+        let request = || test::TestRequest::post().uri("/api/users?page=2").header("host", "example.com").set_payload("jeff").to_request();
+        let response = test::call_service(&app, request()).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get("x-served-by").unwrap(), "gateway");
+        assert!(response.headers().get("x-internal").is_none());
+        assert_eq!(test::read_body(response).await, "first /users?page=2 example.com 4");
+
+        let response = test::call_service(&app, request()).await;
+        assert_eq!(response.headers().get("x-upstream").unwrap(), "second");
+    }
+
+    #[ntex::test]
+    async fn test_limits_and_missing_routes() {
+        let server = upstream("only").await;
+        let config = format!(
+            r#"
+            [[upstream]]
+            name = "api"
+            targets = ["{}"]
+
+            [[route]]
+            name = "api"
+            path = "/api"
+            upstream = "api"
+            max_request_body = 4
+            max_response_body = 8
+            "#,
+            server.addr()
+        );
+        let app = gateway(&config).await;
+
+        let too_large = test::TestRequest::post().uri("/api").set_payload("12345").to_request();
+        let response_too_large = test::TestRequest::get().uri("/api/long/path").to_request();
+        let missing = test::TestRequest::get().uri("/other").to_request();
+
+        assert_eq!(test::call_service(&app, too_large).await.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(test::call_service(&app, response_too_large).await.status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(test::call_service(&app, missing).await.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[ntex::test]
+    async fn test_connection_failures_eject_target() {
+        let server = upstream("alive").await;
+        let config = format!(
+            r#"
+            [[upstream]]
+            name = "api"
+            targets = ["127.0.0.1:1", "{}"]
+            health = {{ max_failures = 1 }}
+
+            [[route]]
+            name = "api"
+            upstream = "api"
+            "#,
+            server.addr()
+        );
+        let app = gateway(&config).await;
+
+        let statuses = Arc::new(std::sync::Mutex::new(Vec::new()));
+        for _ in 0..3 {
+            let response = test::call_service(&app, test::TestRequest::get().uri("/").to_request()).await;
+            statuses.lock().unwrap().push(response.status());
+        }
+
+        assert_eq!(*statuses.lock().unwrap(), [StatusCode::BAD_GATEWAY, StatusCode::OK, StatusCode::OK]);
+    }
+
+    #[ntex::test]
+    async fn test_auth_policy_and_signed_identity() {
+        let server = test::server(|| {
+            App::new().default_service(web::to(|req: HttpRequest| async move {
+                let body = match IdentitySigner::new("secret").verify(req.headers()) {
+                    Ok(Some(identity)) => identity.id,
+                    Ok(None) => "anonymous".to_string(),
+                    Err(e) => e.to_string(),
+                };
+                let api_key = req.headers().contains_key("x-api-key");
+
+                HttpResponse::Ok().body(format!("{} {}", body, api_key))
+            }))
+        });
+        let config = format!(
+            r#"
+            [auth]
+            identity_secret = "secret"
+            jwks_file = "{}"
+
+            [[auth.api_key]]
+            name = "batch"
+            sha256 = "{}"
+            subject = "service:batch"
+
+            [[upstream]]
+            name = "api"
+            targets = ["{}"]
+
+            [[route]]
+            name = "admin"
+            path = "/admin"
+            upstream = "api"
+            auth = "required"
+            roles = ["admin"]
+
+            [[route]]
+            name = "public"
+            upstream = "api"
+            "#,
+            jwks_file("proxy").display(),
+            hex::encode(<sha2::Sha256 as sha2::Digest>::digest(b"batch-key")),
+            server.addr()
+        );
+        let app = gateway(&config).await;
+        let bearer = |roles: &[&str]| format!("Bearer {}", token(json!({ "sub": "42", "exp": expires(), "roles": roles })));
+
+        let forged = test::TestRequest::get().uri("/").header("x-user-id", "1").to_request();
+        let response = test::call_service(&app, forged).await;
+        assert_eq!(test::read_body(response).await, "anonymous false");
+
+        let api_key = test::TestRequest::get().uri("/").header("x-api-key", "batch-key").to_request();
+        let response = test::call_service(&app, api_key).await;
+        assert_eq!(test::read_body(response).await, "service:batch false");
+
+        let missing = test::call_service(&app, test::TestRequest::get().uri("/admin").to_request()).await;
+        assert_eq!(missing.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(missing.headers().get(header::WWW_AUTHENTICATE).unwrap(), "Bearer");
+
+        let user = test::TestRequest::get().uri("/admin").header(header::AUTHORIZATION, bearer(&["user"])).to_request();
+        assert_eq!(test::call_service(&app, user).await.status(), StatusCode::FORBIDDEN);
+
+        let admin = test::TestRequest::get().uri("/admin").header(header::AUTHORIZATION, bearer(&["admin"])).to_request();
+        let response = test::call_service(&app, admin).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(test::read_body(response).await, "42 false");
+    }
+
+    #[ntex::test]
+    async fn test_sticky_cookie_pins_target() {
+        let first = upstream("first").await;
+        let second = upstream("second").await;
+        let config = format!(
+            r#"
+            [[upstream]]
+            name = "api"
+            targets = ["{}", "{}"]
+            sticky_cookie = "gw-target"
+
+            [[route]]
+            name = "api"
+            upstream = "api"
+            "#,
+            first.addr(),
+            second.addr()
+        );
+        let app = gateway(&config).await;
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/").to_request()).await;
+        let cookie = response.headers().get(header::SET_COOKIE).unwrap().to_str().unwrap().to_string();
+        assert!(cookie.ends_with("; Path=/; HttpOnly; SameSite=Lax"));
+        let pair = cookie.split(';').next().unwrap().to_string();
+
+        for _ in 0..3 {
+            let request = test::TestRequest::get().uri("/").header(header::COOKIE, format!("theme=dark; {}", pair)).to_request();
+            let response = test::call_service(&app, request).await;
+
+            assert_eq!(response.headers().get("x-upstream").unwrap(), "first");
+            assert!(response.headers().get(header::SET_COOKIE).is_none());
+        }
+
+        let unknown = test::TestRequest::get().uri("/").header(header::COOKIE, "gw-target=gone").to_request();
+        let response = test::call_service(&app, unknown).await;
+        assert!(response.headers().contains_key(header::SET_COOKIE));
+    }
+
+    #[ntex::test]
+    async fn test_streams_websocket_and_sse_within_limits() {
+        let server = streaming_upstream().await;
+        let config = format!(
+            r#"
+            [[upstream]]
+            name = "api"
+            targets = ["{}"]
+
+            [[route]]
+            name = "api"
+            upstream = "api"
+            max_streams = 1
+            idle_timeout_ms = 200
+            "#,
+            server.addr()
+        );
+        let gateway = Gateway::new(Config::parse(&config).unwrap()).unwrap();
+        let app = test::server(move || {
+            App::new()
+                .state(gateway.clone())
+                .state(Client::new())
+                .default_service(web::to(forward))
+        });
+
+        let connection = app.ws_at("/chat").await.unwrap();
+        let sink = connection.sink();
+        let frames = connection.receiver();
+        sink.send(Message::Text("hello".into())).await.unwrap();
+        assert_eq!(frames.recv().await.unwrap().unwrap(), Frame::Text(Bytes::from_static(b"hello")));
+
+        let rejected = app.ws_at("/chat").await.unwrap_err();
+        assert!(matches!(rejected, ntex::ws::error::WsClientError::InvalidResponseStatus(StatusCode::SERVICE_UNAVAILABLE)));
+
+        // 유휴 타임아웃이 지나면 게이트웨이가 닫고 자리를 비운다.
+        assert!(matches!(frames.recv().await, Some(Ok(Frame::Close(_)))));
+        ntex::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        let mut response = app.get("/events").send().await.unwrap();
+        assert_eq!(response.headers().get(header::CONTENT_TYPE).unwrap(), "text/event-stream");
+        assert_eq!(response.body().await.unwrap(), "data: 1\n\n");
+    }
+}
@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+use ntex::time::sleep;
+use crate::config::Config;
+use crate::gateway::Gateway;
+
+/// 설정 파일의 수정 시각이 바뀌면 다시 읽어 적용합니다.
+///
+/// 읽기나 검증에 실패하면 기존 설정을 유지합니다. 서버 주소(`server.addr`)는 재시작해야 바뀝니다.
+pub async fn watch(gateway: Gateway, path: PathBuf) {
+    let mut modified = modified_at(&path);
+
+    loop {
+        sleep(Duration::from_millis(gateway.snapshot().server.reload_interval_ms)).await;
+
+        let current = modified_at(&path);
+        if current == modified {
+            continue;
+        }
+        modified = current;
+
+        match Config::load(&path).and_then(|config| gateway.reload(config)) {
+            Ok(()) => println!("[gateway] reloaded {}", path.display()),
+            Err(e) => eprintln!("[gateway] keeping previous config, {}: {}", path.display(), e),
+        }
+    }
+}
+
+fn modified_at(path: &PathBuf) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
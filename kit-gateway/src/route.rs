@@ -0,0 +1,203 @@
+use std::sync::Arc;
+use std::time::Duration;
+use ntex::http::header::{HeaderMap, HeaderName, HeaderValue};
+use ntex::http::Method;
+use crate::config::{AuthPolicy, HeaderRules, RouteConfig, ServerConfig};
+
+/// 설정의 헤더 규칙을 미리 파싱해 둔 것
+#[derive(Debug, Clone, Default)]
+pub struct HeaderRewrite {
+    set: Vec<(HeaderName, HeaderValue)>,
+    remove: Vec<HeaderName>,
+}
+
+impl HeaderRewrite {
+    // 설정 검증을 통과한 규칙이므로 파싱은 실패하지 않는다.
+    fn new(rules: &HeaderRules) -> Self {
+        Self {
+            set: rules
+                .set
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((HeaderName::try_from(name.as_str()).ok()?, HeaderValue::from_str(value).ok()?))
+                })
+                .collect(),
+            remove: rules
+                .remove
+                .iter()
+                .filter_map(|name| HeaderName::try_from(name.as_str()).ok())
+                .collect(),
+        }
+    }
+
+    pub fn apply(&self, headers: &mut HeaderMap) {
+        for name in &self.remove {
+            headers.remove(name);
+        }
+        for (name, value) in &self.set {
+            headers.insert(name.clone(), value.clone());
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Route {
+    pub name: String,
+    host: Option<String>,
+    prefix: String,
+    methods: Vec<Method>,
+    strip_prefix: bool,
+    pub upstream: String,
+    pub request_headers: HeaderRewrite,
+    pub response_headers: HeaderRewrite,
+    pub max_request_body: usize,
+    pub max_response_body: usize,
+    pub timeout: Duration,
+    pub auth: AuthPolicy,
+    pub roles: Vec<String>,
+    pub max_streams: Option<usize>,
+    pub idle_timeout: Duration,
+}
+
+impl Route {
+    fn new(config: &RouteConfig, server: &ServerConfig) -> Self {
+        Self {
+            name: config.name.clone(),
+            host: config.host.as_ref().map(|host| host.to_ascii_lowercase()),
+            prefix: config.path.trim_end_matches('/').to_string(),
+            methods: config.methods.iter().filter_map(|method| method.parse().ok()).collect(),
+            strip_prefix: config.strip_prefix,
+            upstream: config.upstream.clone(),
+            request_headers: HeaderRewrite::new(&config.request_headers),
+            response_headers: HeaderRewrite::new(&config.response_headers),
+            max_request_body: config.max_request_body.unwrap_or(server.max_request_body),
+            max_response_body: config.max_response_body.unwrap_or(server.max_response_body),
+            timeout: Duration::from_millis(config.timeout_ms.unwrap_or(server.timeout_ms)),
+            auth: config.auth,
+            roles: config.roles.clone(),
+            max_streams: config.max_streams,
+            idle_timeout: Duration::from_millis(config.idle_timeout_ms.unwrap_or(server.idle_timeout_ms)),
+        }
+    }
+
+    fn matches(&self, host: &str, method: &Method, path: &str) -> bool {
+        let host_matches = match self.host.as_deref() {
+            None => true,
+            Some(pattern) => match pattern.strip_prefix("*.") {
+                Some(domain) => host.strip_suffix(domain).is_some_and(|sub| sub.ends_with('.')),
+                None => host == pattern,
+            },
+        };
+
+        host_matches
+            && (self.methods.is_empty() || self.methods.contains(method))
+            && (self.prefix.is_empty()
+                || path == self.prefix
+                || path.strip_prefix(&self.prefix).is_some_and(|rest| rest.starts_with('/')))
+    }
+
+    /// 업스트림으로 보낼 경로와 쿼리
+    pub fn upstream_path(&self, path: &str, query: Option<&str>) -> String {
+        let path = match self.strip_prefix {
+            true => path.strip_prefix(&self.prefix).unwrap_or(path),
+            false => path,
+        };
+        let path = if path.is_empty() { "/" } else { path };
+
+        match query {
+            Some(query) => format!("{}?{}", path, query),
+            None => path.to_string(),
+        }
+    }
+}
+
+/// 호스트가 지정된 라우트, 긴 접두사 순으로 먼저 비교합니다. 같으면 설정 파일의 순서를 따릅니다.
+#[derive(Debug, Clone, Default)]
+pub struct RouteTable {
+    routes: Vec<Arc<Route>>,
+}
+
+impl RouteTable {
+    pub fn new(routes: &[RouteConfig], server: &ServerConfig) -> Self {
+        let mut routes = routes
+            .iter()
+            .map(|config| Arc::new(Route::new(config, server)))
+            .collect::<Vec<_>>();
+        routes.sort_by_key(|route| (route.host.is_none(), std::cmp::Reverse(route.prefix.len())));
+
+        Self { routes }
+    }
+
+    /// `host` 는 포트를 뗀 소문자로 비교합니다.
+    pub fn find(&self, host: &str, method: &Method, path: &str) -> Option<Arc<Route>> {
+        let host = host.rsplit_once(':').map_or(host, |(host, _)| host).to_ascii_lowercase();
+
+        self.routes
+            .iter()
+            .find(|route| route.matches(&host, method, path))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn table() -> RouteTable {
+        let config = Config::parse(
+            r#"
+            [[upstream]]
+            name = "a"
+            targets = ["127.0.0.1:1"]
+
+            [[route]]
+            name = "fallback"
+            upstream = "a"
+
+            [[route]]
+            name = "api"
+            path = "/api"
+            strip_prefix = true
+            upstream = "a"
+
+            [[route]]
+            name = "users"
+            path = "/api/users/"
+            methods = ["GET"]
+            upstream = "a"
+
+            [[route]]
+            name = "admin"
+            host = "*.admin.example.com"
+            upstream = "a"
+            "#,
+        )
+        .unwrap();
+
+        RouteTable::new(&config.routes, &config.server)
+    }
+
+    fn find(table: &RouteTable, host: &str, method: Method, path: &str) -> String {
+        table.find(host, &method, path).unwrap().name.clone()
+    }
+
+    #[test]
+    fn test_find_prefers_host_then_longest_prefix() {
+        let table = table();
+
+        assert_eq!(find(&table, "eu.admin.example.com:8000", Method::GET, "/api/users"), "admin");
+        assert_eq!(find(&table, "admin.example.com", Method::GET, "/api/users"), "users");
+        assert_eq!(find(&table, "localhost", Method::POST, "/api/users"), "api");
+        assert_eq!(find(&table, "localhost", Method::GET, "/apis"), "fallback");
+    }
+
+    #[test]
+    fn test_upstream_path_strips_prefix() {
+        let table = table();
+        let api = table.find("localhost", &Method::POST, "/api/users").unwrap();
+
+        assert_eq!(api.upstream_path("/api/users", Some("page=2")), "/users?page=2");
+        assert_eq!(api.upstream_path("/api", None), "/");
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use futures::{Stream, StreamExt, stream};
+use ntex::util::Bytes;
+use crate::route::Route;
+
+/// 열려 있는 SSE/WebSocket 연결 수
+///
+/// 설정을 다시 읽어도 이미 열린 연결은 그대로이므로 스냅샷과 따로 둡니다. 라우트별 수는 라우트 이름으로 셉니다.
+#[derive(Debug, Default)]
+pub struct StreamLimits {
+    total: Arc<AtomicUsize>,
+    routes: Mutex<HashMap<String, Arc<AtomicUsize>>>,
+}
+
+impl StreamLimits {
+    /// 전체와 라우트의 한도 안에 자리가 있으면 연결 하나를 차지합니다. 돌려받은 값을 버리면 자리를 비웁니다.
+    pub fn acquire(&self, route: &Route, max_total: usize) -> Option<StreamSlot> {
+        let counter = self
+            .routes
+            .lock()
+            .unwrap()
+            .entry(route.name.clone())
+            .or_default()
+            .clone();
+
+        if !increment(&self.total, max_total) {
+            return None;
+        }
+        if !increment(&counter, route.max_streams.unwrap_or(usize::MAX)) {
+            self.total.fetch_sub(1, Ordering::AcqRel);
+            return None;
+        }
+
+        Some(StreamSlot {
+            counters: [self.total.clone(), counter],
+        })
+    }
+}
+
+fn increment(counter: &AtomicUsize, max: usize) -> bool {
+    counter
+        .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| (count < max).then_some(count + 1))
+        .is_ok()
+}
+
+/// 연결 하나가 차지한 자리
+#[derive(Debug)]
+pub struct StreamSlot {
+    counters: [Arc<AtomicUsize>; 2],
+}
+
+impl Drop for StreamSlot {
+    fn drop(&mut self) {
+        for counter in &self.counters {
+            counter.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+/// 업스트림 응답 본문을 그대로 흘려보냅니다. `idle` 동안 다음 조각이 오지 않으면 스트림을 끝냅니다.
+///
+/// 스트림이 끝나거나 클라이언트가 끊어 버려질 때 `guard` 도 함께 버려집니다.
+pub fn idle_stream<S, E, G>(body: S, idle: Duration, guard: G) -> impl Stream<Item = Result<Bytes, E>>
+where
+    S: Stream<Item = Result<Bytes, E>> + Unpin,
+{
+    stream::unfold((body, guard), move |(mut body, guard)| async move {
+        match ntex::time::timeout(idle, body.next()).await {
+            Ok(Some(chunk)) => Some((chunk, (body, guard))),
+            Ok(None) | Err(()) => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::http::Method;
+    use crate::config::{RouteConfig, ServerConfig};
+    use crate::route::RouteTable;
+
+    fn route(name: &str, max_streams: Option<usize>) -> Route {
+        let config: RouteConfig = toml::from_str(&format!("name = \"{}\"\nupstream = \"api\"", name)).unwrap();
+        let table = RouteTable::new(&[RouteConfig { max_streams, ..config }], &ServerConfig::default());
+
+        table.find("localhost", &Method::GET, "/").unwrap().as_ref().clone()
+    }
+
+    #[test]
+    fn test_acquire_respects_route_and_total_limits() {
+        let limits = StreamLimits::default();
+        let limited = route("events", Some(1));
+
+        let first = limits.acquire(&limited, 2).unwrap();
+        assert!(limits.acquire(&limited, 2).is_none());
+
+        let other = route("other", None);
+        let _second = limits.acquire(&other, 2).unwrap();
+        assert!(limits.acquire(&other, 2).is_none());
+
+        drop(first);
+        assert!(limits.acquire(&limited, 2).is_some());
+    }
+
+    #[ntex::test]
+    async fn test_idle_stream_ends_after_silence() {
+        let body = stream::iter([Ok::<_, ()>(Bytes::from_static(b"data: 1\n\n"))]).chain(stream::pending());
+        let slot = Arc::new(());
+
+        let chunks = idle_stream(Box::pin(body), Duration::from_millis(50), slot.clone())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(chunks, [Ok(Bytes::from_static(b"data: 1\n\n"))]);
+        assert_eq!(Arc::strong_count(&slot), 1);
+    }
+}
@@ -0,0 +1,189 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use crate::balancer::{self, Balancer};
+use crate::config::{HashBy, HealthConfig, UpstreamConfig};
+
+/// 업스트림 서버 하나와 그 상태
+///
+/// 능동 검사로 비정상 판정을 받았거나, 프록시 요청이 연속으로 실패해 잠시 제외된 대상은 고르지 않습니다.
+#[derive(Debug)]
+pub struct Target {
+    pub addr: String,
+    /// 고정 라우팅 쿠키에 담는 식별자. 내부 주소를 드러내지 않도록 주소의 해시를 씁니다.
+    pub id: String,
+    active: AtomicUsize,
+    healthy: AtomicBool,
+    // 능동 검사의 연속 성공(양수) 또는 실패(음수) 횟수
+    streak: Mutex<i64>,
+    failures: AtomicU32,
+    ejected_until: Mutex<Option<Instant>>,
+}
+
+impl Target {
+    pub fn new(addr: impl Into<String>) -> Self {
+        let addr = addr.into();
+
+        Self {
+            id: format!("{:016x}", balancer::hash(addr.as_bytes())),
+            addr,
+            active: AtomicUsize::new(0),
+            healthy: AtomicBool::new(true),
+            streak: Mutex::new(0),
+            failures: AtomicU32::new(0),
+            ejected_until: Mutex::new(None),
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+            && self
+                .ejected_until
+                .lock()
+                .unwrap()
+                .is_none_or(|until| until <= Instant::now())
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    /// 처리 중인 요청 수
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// 요청을 보내는 동안 쥐고 있으면 처리 중인 요청 수에 포함됩니다.
+    pub fn begin(self: &Arc<Self>) -> ActiveRequest {
+        self.active.fetch_add(1, Ordering::Relaxed);
+        ActiveRequest(self.clone())
+    }
+
+    /// 능동 검사 결과. 기준 횟수만큼 연속되어야 상태가 바뀝니다.
+    pub fn report_check(&self, ok: bool, health: &HealthConfig) {
+        let mut streak = self.streak.lock().unwrap();
+        *streak = match (ok, *streak) {
+            (true, streak) if streak > 0 => streak + 1,
+            (true, _) => 1,
+            (false, streak) if streak < 0 => streak - 1,
+            (false, _) => -1,
+        };
+
+        if ok && *streak >= health.healthy_threshold.max(1) as i64 {
+            self.healthy.store(true, Ordering::Relaxed);
+        } else if !ok && -*streak >= health.unhealthy_threshold.max(1) as i64 {
+            self.healthy.store(false, Ordering::Relaxed);
+        }
+    }
+
+    pub fn report_success(&self) {
+        self.failures.store(0, Ordering::Relaxed);
+    }
+
+    /// 프록시 요청 실패. 연속 실패가 `max_failures` 에 이르면 `eject_ms` 동안 제외합니다.
+    pub fn report_failure(&self, health: &HealthConfig) {
+        let failures = self.failures.fetch_add(1, Ordering::Relaxed) + 1;
+
+        if failures >= health.max_failures.max(1) {
+            self.failures.store(0, Ordering::Relaxed);
+            *self.ejected_until.lock().unwrap() = Some(Instant::now() + Duration::from_millis(health.eject_ms));
+            eprintln!("[gateway] ejected `{}` after {} failures", self.addr, failures);
+        }
+    }
+}
+
+pub struct ActiveRequest(Arc<Target>);
+
+impl Drop for ActiveRequest {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug)]
+pub struct Upstream {
+    pub name: String,
+    pub targets: Vec<Arc<Target>>,
+    pub hash_by: HashBy,
+    pub health: HealthConfig,
+    pub sticky_cookie: Option<String>,
+    balancer: Balancer,
+}
+
+impl Upstream {
+    /// 설정을 다시 읽을 때 `previous` 에 같은 주소의 대상이 있으면 상태를 이어받습니다.
+    pub fn new(config: &UpstreamConfig, previous: Option<&Upstream>) -> Self {
+        let targets = config
+            .targets
+            .iter()
+            .map(|addr| {
+                previous
+                    .and_then(|previous| previous.targets.iter().find(|target| &target.addr == addr))
+                    .cloned()
+                    .unwrap_or_else(|| Arc::new(Target::new(addr.clone())))
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            name: config.name.clone(),
+            balancer: Balancer::new(config.balance, &targets),
+            targets,
+            hash_by: config.hash_by.clone(),
+            health: config.health.clone(),
+            sticky_cookie: config.sticky_cookie.clone(),
+        }
+    }
+
+    pub fn select(&self, key: Option<&str>) -> Option<Arc<Target>> {
+        self.balancer.pick(&self.targets, key)
+    }
+
+    /// 고정 라우팅 쿠키의 대상. 사용할 수 없는 대상이면 `None` 입니다.
+    pub fn find(&self, id: &str) -> Option<Arc<Target>> {
+        self.targets
+            .iter()
+            .find(|target| target.id == id && target.is_available())
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passive_failures_eject_target_temporarily() {
+        let target = Target::new("10.0.0.1:80");
+        let health = HealthConfig { max_failures: 2, eject_ms: 30, ..Default::default() };
+
+        target.report_failure(&health);
+        target.report_success();
+        target.report_failure(&health);
+        assert!(target.is_available());
+
+        target.report_failure(&health);
+        assert!(!target.is_available());
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(target.is_available());
+    }
+
+    #[test]
+    fn test_active_checks_need_consecutive_results() {
+        let target = Target::new("10.0.0.1:80");
+        let health = HealthConfig { healthy_threshold: 2, unhealthy_threshold: 2, ..Default::default() };
+
+        target.report_check(false, &health);
+        target.report_check(true, &health);
+        target.report_check(false, &health);
+        assert!(target.is_healthy());
+
+        target.report_check(false, &health);
+        assert!(!target.is_healthy());
+
+        target.report_check(true, &health);
+        assert!(!target.is_healthy());
+        target.report_check(true, &health);
+        assert!(target.is_healthy());
+    }
+}
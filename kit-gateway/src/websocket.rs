@@ -0,0 +1,179 @@
+use std::cell::Cell;
+use std::rc::{Rc, Weak};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use ntex::channel::mpsc::Receiver;
+use ntex::http::header::{self, HeaderMap};
+use ntex::service::{fn_factory_with_config, fn_service};
+use ntex::time::Seconds;
+use ntex::util::ByteString;
+use ntex::web::{self, HttpRequest, HttpResponse};
+use ntex::ws::error::{WsClientError, WsError};
+use ntex::ws::{CloseCode, Frame, Message, WsClient, WsSink};
+use crate::error::GatewayError;
+use crate::route::Route;
+use crate::stream::StreamSlot;
+use crate::upstream::{ActiveRequest, Target, Upstream};
+
+/// `Upgrade: websocket` 요청인지 확인합니다.
+pub fn is_upgrade(req: &HttpRequest) -> bool {
+    req.headers()
+        .get(header::UPGRADE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.split(',').any(|protocol| protocol.trim().eq_ignore_ascii_case("websocket")))
+}
+
+/// 업스트림과 WebSocket 연결을 맺은 뒤 클라이언트의 핸드셰이크에 응답하고, 두 연결 사이에서 프레임을 그대로 전달합니다.
+///
+/// 업스트림이 업그레이드를 거절하거나 응답하지 않으면 클라이언트는 `502`/`504` 를 받습니다.
+/// 어느 쪽이든 `route.idle_timeout` 동안 프레임이 없으면 두 연결을 모두 닫습니다.
+///
+/// 서브프로토콜(`Sec-WebSocket-Protocol`)과 확장(`Sec-WebSocket-Extensions`) 협상은 지원하지 않습니다.
+pub async fn tunnel(
+    req: HttpRequest,
+    route: &Route,
+    upstream: &Upstream,
+    target: Arc<Target>,
+    headers: HeaderMap,
+    slot: StreamSlot,
+) -> Result<HttpResponse, GatewayError> {
+    // 잘못된 핸드셰이크로 업스트림 연결을 맺지 않도록 먼저 확인한다.
+    ntex::ws::handshake(req.head())?;
+
+    let url = format!("ws://{}{}", target.addr, route.upstream_path(req.path(), req.uri().query()));
+    let mut builder = WsClient::build(url);
+    builder.timeout(route.timeout).keepalive_timeout(Seconds::ZERO);
+    for (name, value) in &headers {
+        if !name.as_str().starts_with("sec-websocket-") {
+            builder.header(name.clone(), value.clone());
+        }
+    }
+    let client = builder
+        .finish()
+        .map_err(|e| GatewayError::Upstream(upstream.name.clone(), e.to_string()))?;
+
+    let active = target.begin();
+    let connection = match client.connect().await {
+        Ok(connection) => {
+            target.report_success();
+            connection
+        }
+        Err(e) => {
+            match &e {
+                WsClientError::InvalidResponseStatus(status) if !matches!(status.as_u16(), 502..=504) => target.report_success(),
+                _ => target.report_failure(&upstream.health),
+            }
+            eprintln!("[gateway] route `{}` to `{}` failed: {}", route.name, target.addr, e);
+
+            return Err(match e {
+                WsClientError::Timeout => GatewayError::Timeout(upstream.name.clone()),
+                WsClientError::InvalidResponseStatus(status) => {
+                    GatewayError::Upstream(upstream.name.clone(), format!("upgrade rejected with {}", status))
+                }
+                e => GatewayError::Upstream(upstream.name.clone(), e.to_string()),
+            });
+        }
+    };
+
+    let connection = connection.seal();
+    let tunnel = Rc::new(Tunnel {
+        upstream: connection.sink(),
+        activity: Cell::new(Instant::now()),
+        _slot: slot,
+        _active: active,
+    });
+    let frames = connection.receiver();
+    ntex::rt::spawn(watch_idle(Rc::downgrade(&tunnel), route.idle_timeout));
+
+    // 팩토리는 한 번만 불린다. 핸드셰이크 도중 실패해 불리지 않으면 함께 버려지며 업스트림 연결을 닫는다.
+    let state = Cell::new(Some((tunnel, frames)));
+
+    web::ws::start::<_, _, GatewayError>(
+        req,
+        fn_factory_with_config(move |client: WsSink| {
+            let state = state.take();
+
+            async move {
+                let (tunnel, frames) = state.ok_or_else(|| GatewayError::Handshake("websocket is already started".to_string()))?;
+                ntex::rt::spawn(pump(frames, client, Rc::downgrade(&tunnel)));
+
+                Ok::<_, GatewayError>(fn_service(move |frame: Frame| {
+                    let tunnel = tunnel.clone();
+
+                    async move {
+                        tunnel.activity.set(Instant::now());
+                        let message = message(frame).ok_or("invalid text frame")?;
+                        tunnel.upstream.send(message).await.map_err(|_| "upstream is closed")?;
+
+                        Ok::<_, &'static str>(None)
+                    }
+                }))
+            }
+        }),
+    )
+    .await
+}
+
+// 클라이언트 쪽 연결이 살아 있는 동안 유지된다. 버려지면 업스트림 연결을 닫는다.
+struct Tunnel {
+    upstream: WsSink,
+    activity: Cell<Instant>,
+    _slot: StreamSlot,
+    _active: ActiveRequest,
+}
+
+impl Drop for Tunnel {
+    fn drop(&mut self) {
+        self.upstream.io().close();
+    }
+}
+
+// 업스트림에서 받은 프레임을 클라이언트로 보낸다. 업스트림 연결이 끝나면 클라이언트 연결도 닫는다.
+async fn pump(frames: Receiver<Result<Frame, WsError<()>>>, client: WsSink, tunnel: Weak<Tunnel>) {
+    let mut closed = false;
+
+    while let Some(Ok(frame)) = frames.recv().await {
+        match tunnel.upgrade() {
+            Some(tunnel) => tunnel.activity.set(Instant::now()),
+            None => break,
+        }
+
+        closed = matches!(frame, Frame::Close(_));
+        let Some(message) = message(frame) else { break };
+        if client.send(message).await.is_err() || closed {
+            break;
+        }
+    }
+
+    if !closed {
+        let _ = client.send(Message::Close(Some(CloseCode::Away.into()))).await;
+    }
+    client.io().close();
+}
+
+// 업스트림을 닫으면 `pump` 가 클라이언트까지 정리한다.
+async fn watch_idle(tunnel: Weak<Tunnel>, idle: Duration) {
+    loop {
+        let wait = match tunnel.upgrade() {
+            Some(tunnel) => match idle.checked_sub(tunnel.activity.get().elapsed()) {
+                Some(wait) if !wait.is_zero() => wait,
+                _ => return tunnel.upstream.io().close(),
+            },
+            None => return,
+        };
+
+        ntex::time::sleep(wait).await;
+    }
+}
+
+// 받은 프레임을 그대로 다시 보낼 메시지로 바꾼다. UTF-8 이 아닌 텍스트 프레임은 전달하지 않는다.
+fn message(frame: Frame) -> Option<Message> {
+    Some(match frame {
+        Frame::Text(text) => Message::Text(ByteString::try_from(text).ok()?),
+        Frame::Binary(data) => Message::Binary(data),
+        Frame::Continuation(item) => Message::Continuation(item),
+        Frame::Ping(data) => Message::Ping(data),
+        Frame::Pong(data) => Message::Pong(data),
+        Frame::Close(reason) => Message::Close(reason),
+    })
+}
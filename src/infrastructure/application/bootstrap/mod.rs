@@ -1 +1,2 @@
-pub mod setting;
\ No newline at end of file
+pub mod setting;
+pub mod retry;
\ No newline at end of file
@@ -0,0 +1,128 @@
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+
+/// 지수 백오프 정책
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+    pub multiplier: f64,
+    pub max_attempts: usize,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(500),
+            max: Duration::from_secs(30),
+            multiplier: 2.0,
+            max_attempts: 10,
+        }
+    }
+}
+
+impl Backoff {
+    /// `attempt` 번째(1부터 시작) 실패 후 기다릴 시간
+    pub fn delay(&self, attempt: usize) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(32) as i32;
+        let delay = self.initial.as_secs_f64() * self.multiplier.powi(exponent);
+
+        Duration::from_secs_f64(delay.min(self.max.as_secs_f64()))
+    }
+}
+
+/// 의존성(DB, NATS 등)이 준비될 때까지 백오프를 두고 재시도합니다.
+///
+/// `max_attempts` 를 모두 소진하면 마지막 에러를 그대로 반환하므로,
+/// 호출하는 쪽에서 panic 대신 정상적인 종료 경로를 선택할 수 있습니다.
+///
+/// # 예시
+///
+/// ```
+/// let pool = retry("database", &Backoff::default(), || {
+///     PgPoolOptions::new().connect(&database_url)
+/// }).await?;
+/// ```
+pub async fn retry<T, E, F, Fut>(name: &str, backoff: &Backoff, mut operation: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: Display,
+{
+    let mut attempt = 1;
+
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < backoff.max_attempts => {
+                let delay = backoff.delay(attempt);
+                println!(
+                    "[bootstrap] {} is not ready (attempt {}/{}): {}. retrying in {:?}",
+                    name, attempt, backoff.max_attempts, e, delay
+                );
+
+                ntex::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                println!("[bootstrap] {} is unavailable after {} attempts: {}", name, attempt, e);
+                return Err(e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_backoff_delay_is_capped() {
+        let backoff = Backoff {
+            initial: Duration::from_millis(100),
+            max: Duration::from_millis(500),
+            multiplier: 2.0,
+            max_attempts: 10,
+        };
+
+        assert_eq!(backoff.delay(1), Duration::from_millis(100));
+        assert_eq!(backoff.delay(3), Duration::from_millis(400));
+        assert_eq!(backoff.delay(4), Duration::from_millis(500));
+    }
+
+    #[ntex::test]
+    async fn test_retry_until_success() {
+        let backoff = Backoff {
+            initial: Duration::from_millis(1),
+            max: Duration::from_millis(1),
+            multiplier: 1.0,
+            max_attempts: 5,
+        };
+        let calls = Cell::new(0);
+
+        let result: Result<usize, String> = retry("test", &backoff, || {
+            calls.set(calls.get() + 1);
+            let attempt = calls.get();
+            async move { if attempt < 3 { Err("not yet".to_string()) } else { Ok(attempt) } }
+        })
+        .await;
+
+        assert_eq!(result, Ok(3));
+    }
+
+    #[ntex::test]
+    async fn test_retry_gives_up() {
+        let backoff = Backoff {
+            initial: Duration::from_millis(1),
+            max: Duration::from_millis(1),
+            multiplier: 1.0,
+            max_attempts: 2,
+        };
+
+        let result: Result<(), String> = retry("test", &backoff, || async { Err("down".to_string()) }).await;
+
+        assert_eq!(result, Err("down".to_string()));
+    }
+}
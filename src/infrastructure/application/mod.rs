@@ -1,2 +1,3 @@
 pub mod states;
-pub mod bootstrap;
\ No newline at end of file
+pub mod bootstrap;
+pub mod shutdown;
\ No newline at end of file
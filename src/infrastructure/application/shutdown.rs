@@ -0,0 +1,156 @@
+use std::future::Future;
+use std::io;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use futures::future::{LocalBoxFuture, select};
+use tokio::signal::unix::{SignalKind, signal};
+use tokio::sync::watch;
+
+type Hook = Box<dyn FnOnce() -> LocalBoxFuture<'static, ()> + Send>;
+
+/// 종료가 시작되었는지 확인하거나 기다릴 수 있는 신호
+///
+/// 백그라운드 워커는 이 신호를 받으면 새 작업을 받지 않고 루프를 빠져나와야 합니다.
+#[derive(Clone)]
+pub struct ShutdownSignal {
+    receiver: watch::Receiver<bool>,
+}
+
+impl ShutdownSignal {
+    pub fn is_shutdown(&self) -> bool {
+        *self.receiver.borrow()
+    }
+
+    pub async fn wait(&mut self) {
+        let _ = self.receiver.wait_for(|shutdown| *shutdown).await;
+    }
+}
+
+/// 프로세스 종료 절차를 조율합니다.
+///
+/// HTTP 서버가 in-flight 요청을 모두 처리하고 멈춘 뒤 [`Shutdown::run`] 을 호출하면,
+/// 먼저 백그라운드 워커에 종료 신호를 보내고 등록된 훅을 등록 순서대로 실행합니다.
+/// 모든 훅은 하나의 데드라인을 공유하며, 데드라인을 넘긴 훅은 건너뜁니다.
+pub struct Shutdown {
+    sender: watch::Sender<bool>,
+    hooks: Mutex<Vec<(String, Hook)>>,
+    timeout: Duration,
+}
+
+impl Shutdown {
+    pub fn new(timeout: Duration) -> Self {
+        let (sender, _) = watch::channel(false);
+
+        Self {
+            sender,
+            hooks: Mutex::new(Vec::new()),
+            timeout,
+        }
+    }
+
+    pub fn signal(&self) -> ShutdownSignal {
+        ShutdownSignal {
+            receiver: self.sender.subscribe(),
+        }
+    }
+
+    /// 종료 시 실행할 훅을 등록합니다.
+    ///
+    /// # 예시
+    ///
+    /// ```
+    /// shutdown.on_shutdown("database", move || async move { pool.close().await });
+    /// ```
+    pub fn on_shutdown<F, Fut>(&self, name: impl Into<String>, hook: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + 'static,
+    {
+        let hook: Hook = Box::new(move || Box::pin(hook()));
+
+        if let Ok(mut hooks) = self.hooks.lock() {
+            hooks.push((name.into(), hook));
+        }
+    }
+
+    pub async fn run(&self) {
+        let _ = self.sender.send(true);
+
+        let hooks = self
+            .hooks
+            .lock()
+            .map(|mut hooks| std::mem::take(&mut *hooks))
+            .unwrap_or_default();
+
+        let deadline = Instant::now() + self.timeout;
+
+        for (name, hook) in hooks {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            if remaining.is_zero() {
+                println!("[shutdown] skipped {}: deadline exceeded", name);
+                continue;
+            }
+
+            match ntex::time::timeout(remaining, hook()).await {
+                Ok(()) => println!("[shutdown] {} completed", name),
+                Err(()) => println!("[shutdown] {} did not complete within {:?}", name, remaining),
+            }
+        }
+    }
+}
+
+/// SIGTERM 또는 SIGINT 를 기다리고, 받은 신호의 이름을 돌려줍니다.
+pub async fn wait_for_signal() -> io::Result<&'static str> {
+    let mut terminate = signal(SignalKind::terminate())?;
+    let mut interrupt = signal(SignalKind::interrupt())?;
+
+    let received = select(Box::pin(terminate.recv()), Box::pin(interrupt.recv())).await;
+
+    Ok(match received {
+        futures::future::Either::Left(_) => "SIGTERM",
+        futures::future::Either::Right(_) => "SIGINT",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[ntex::test]
+    async fn test_hooks_run_in_order_after_signal() {
+        let shutdown = Shutdown::new(Duration::from_secs(1));
+        let mut signal = shutdown.signal();
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        for name in ["nats", "database"] {
+            let order = order.clone();
+            shutdown.on_shutdown(name, move || async move {
+                order.lock().unwrap().push(name);
+            });
+        }
+
+        assert!(!signal.is_shutdown());
+
+        shutdown.run().await;
+        signal.wait().await;
+
+        assert!(signal.is_shutdown());
+        assert_eq!(*order.lock().unwrap(), vec!["nats", "database"]);
+    }
+
+    #[ntex::test]
+    async fn test_slow_hook_does_not_block_shutdown() {
+        let shutdown = Shutdown::new(Duration::from_millis(50));
+
+        shutdown.on_shutdown("slow", || async {
+            ntex::time::sleep(Duration::from_secs(5)).await;
+        });
+
+        let started_at = Instant::now();
+        shutdown.run().await;
+
+        assert!(started_at.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,106 @@
+use std::time::Duration;
+use futures::future::BoxFuture;
+use kit_event::{BusError, IdempotencyClaim, IdempotencyStore};
+use serde_json::Value;
+use sqlx::PgPool;
+
+/// `command_results` 테이블에 커맨드 결과를 저장합니다.
+///
+/// 여러 인스턴스가 같은 키의 커맨드를 동시에 받아도 기본 키 충돌로 한 커맨드만 선점합니다.
+/// 만료된 키는 같은 키로 다시 선점할 때 덮어쓰고, 나머지는 [`PgIdempotencyStore::purge_expired`] 가 주기적으로 지웁니다.
+/// 테이블은 `platform` 모듈의 마이그레이션이 만듭니다.
+#[derive(Debug, Clone)]
+pub struct PgIdempotencyStore {
+    pool: PgPool,
+}
+
+impl PgIdempotencyStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 만료된 키를 `interval` 마다 지웁니다. 끝나지 않습니다.
+    pub async fn purge_expired(&self, interval: Duration) {
+        loop {
+            ntex::time::sleep(interval).await;
+
+            if let Err(e) = sqlx::query("DELETE FROM command_results WHERE expires_at <= CURRENT_TIMESTAMP")
+                .execute(&self.pool)
+                .await
+            {
+                eprintln!("[command] failed to purge expired idempotency keys: {}", e);
+            }
+        }
+    }
+
+    async fn try_claim(&self, key: &str, lock_timeout: Duration) -> Result<IdempotencyClaim, sqlx::Error> {
+        // 같은 키가 만료됐을 때만 새 커맨드로 덮어쓴다.
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO command_results (key, expires_at)
+            VALUES ($1, CURRENT_TIMESTAMP + make_interval(secs => $2))
+            ON CONFLICT (key) DO UPDATE
+            SET result = NULL, expires_at = EXCLUDED.expires_at, created_at = CURRENT_TIMESTAMP
+            WHERE command_results.expires_at <= CURRENT_TIMESTAMP
+            "#
+        )
+            .bind(key)
+            .bind(lock_timeout.as_secs_f64())
+            .execute(&self.pool)
+            .await?;
+
+        if inserted.rows_affected() == 1 {
+            return Ok(IdempotencyClaim::Acquired);
+        }
+
+        let result = sqlx::query_scalar::<_, Option<String>>("SELECT result::text FROM command_results WHERE key = $1")
+            .bind(key)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(match result {
+            Some(result) => IdempotencyClaim::Completed(
+                serde_json::from_str::<Value>(&result).map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            ),
+            None => IdempotencyClaim::InProgress,
+        })
+    }
+}
+
+impl IdempotencyStore for PgIdempotencyStore {
+    fn claim<'a>(&'a self, key: &'a str, lock_timeout: Duration) -> BoxFuture<'a, Result<IdempotencyClaim, BusError>> {
+        Box::pin(async move { self.try_claim(key, lock_timeout).await.map_err(BusError::handler) })
+    }
+
+    fn complete<'a>(&'a self, key: &'a str, result: Value, ttl: Duration) -> BoxFuture<'a, Result<(), BusError>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                UPDATE command_results
+                SET result = $2::jsonb, expires_at = CURRENT_TIMESTAMP + make_interval(secs => $3)
+                WHERE key = $1
+                "#
+            )
+                .bind(key)
+                .bind(result.to_string())
+                .bind(ttl.as_secs_f64())
+                .execute(&self.pool)
+                .await
+                .map_err(BusError::handler)?;
+
+            Ok(())
+        })
+    }
+
+    fn release<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<(), BusError>> {
+        Box::pin(async move {
+            sqlx::query("DELETE FROM command_results WHERE key = $1 AND result IS NULL")
+                .bind(key)
+                .execute(&self.pool)
+                .await
+                .map_err(BusError::handler)?;
+
+            Ok(())
+        })
+    }
+}
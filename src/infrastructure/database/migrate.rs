@@ -0,0 +1,60 @@
+use core_module::Migration;
+use sqlx::PgPool;
+
+// 여러 인스턴스가 동시에 부팅해도 마이그레이션은 한 곳에서만 실행되도록 advisory lock 을 건다.
+const MIGRATION_LOCK_KEY: i64 = 0x6b69_745f_6d69_6772;
+
+/// 아직 적용되지 않은 모듈 마이그레이션을 순서대로 적용합니다.
+///
+/// 각 마이그레이션은 하나의 트랜잭션 안에서 실행되고, 적용 기록은 `schema_migrations` 에 남습니다.
+pub async fn run(pool: &PgPool, migrations: &[Migration]) -> Result<(), sqlx::Error> {
+    sqlx::raw_sql(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            module VARCHAR(50) NOT NULL,
+            version BIGINT NOT NULL,
+            name VARCHAR(100) NOT NULL,
+            applied_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY (module, version)
+        )
+        "#
+    )
+        .execute(pool)
+        .await?;
+
+    for migration in migrations {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(MIGRATION_LOCK_KEY)
+            .execute(&mut *tx)
+            .await?;
+
+        let applied = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM schema_migrations WHERE module = $1 AND version = $2)"
+        )
+            .bind(migration.module)
+            .bind(migration.version)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        if applied {
+            continue;
+        }
+
+        sqlx::raw_sql(migration.sql).execute(&mut *tx).await?;
+
+        sqlx::query("INSERT INTO schema_migrations (module, version, name) VALUES ($1, $2, $3)")
+            .bind(migration.module)
+            .bind(migration.version)
+            .bind(migration.name)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        println!("[migration] applied {} v{} ({})", migration.module, migration.version, migration.name);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,4 @@
+pub mod migrate;
+pub mod projection;
+pub mod transaction;
+pub mod idempotency_store;
\ No newline at end of file
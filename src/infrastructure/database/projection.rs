@@ -0,0 +1,30 @@
+use std::sync::Arc;
+use core_module::Projection;
+
+/// 모듈이 등록한 프로젝션을 이벤트 로그로부터 다시 만듭니다.
+///
+/// `names` 가 비어 있으면 전체를, 아니면 이름이 일치하는 프로젝션만 재생성합니다.
+/// `cargo run -- rebuild-projections [name...]` 으로 실행합니다.
+pub async fn rebuild(projections: &[Arc<dyn Projection>], names: &[String]) -> Result<(), String> {
+    if let Some(unknown) = names
+        .iter()
+        .find(|name| !projections.iter().any(|projection| projection.name() == name.as_str()))
+    {
+        return Err(format!("unknown projection `{}`", unknown));
+    }
+
+    for projection in projections {
+        if !names.is_empty() && !names.iter().any(|name| name == projection.name()) {
+            continue;
+        }
+
+        let replayed = projection
+            .rebuild()
+            .await
+            .map_err(|e| format!("failed to rebuild `{}`: {}", projection.name(), e))?;
+
+        println!("[projection] `{}` rebuilt from {} events", projection.name(), replayed);
+    }
+
+    Ok(())
+}
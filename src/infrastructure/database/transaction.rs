@@ -0,0 +1,138 @@
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use futures::future::BoxFuture;
+use kit_event::{BusError, CommandMiddleware, Next};
+use serde_json::Value;
+use sqlx::pool::PoolConnection;
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+use tokio::sync::{Mutex, OwnedMappedMutexGuard, OwnedMutexGuard};
+
+type SharedTransaction = Arc<Mutex<Option<Transaction<'static, Postgres>>>>;
+
+struct Current {
+    transaction: SharedTransaction,
+    after_commit: Mutex<Vec<BoxFuture<'static, ()>>>,
+}
+
+tokio::task_local! {
+    static CURRENT: Arc<Current>;
+}
+
+/// 커맨드 하나를 하나의 트랜잭션으로 묶는 커맨드 미들웨어
+///
+/// 핸들러가 성공하면 커밋하고 실패하면 롤백합니다. 리포지토리는 [`acquire`] 로 커넥션을 얻어야 트랜잭션에 참여합니다.
+/// 커맨드 안에서 다른 커맨드를 보내면 바깥 트랜잭션을 그대로 사용합니다.
+/// 메시지 발행처럼 커밋된 뒤에만 해야 하는 일은 [`after_commit`] 으로 미룹니다.
+pub struct Transactional {
+    pool: PgPool,
+}
+
+impl Transactional {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl CommandMiddleware for Transactional {
+    fn handle<'a>(&'a self, next: Next<'a>) -> BoxFuture<'a, Result<Value, BusError>> {
+        Box::pin(async move {
+            if CURRENT.try_with(|_| ()).is_ok() {
+                return next.run().await;
+            }
+
+            let transaction = self.pool.begin().await.map_err(BusError::handler)?;
+            let current = Arc::new(Current {
+                transaction: Arc::new(Mutex::new(Some(transaction))),
+                after_commit: Mutex::default(),
+            });
+
+            let result = CURRENT.scope(current.clone(), next.run()).await;
+
+            let Some(transaction) = current.transaction.lock().await.take() else {
+                return Err(BusError::handler("command transaction already finished"));
+            };
+
+            match result {
+                Ok(value) => {
+                    transaction.commit().await.map_err(BusError::handler)?;
+
+                    for task in current.after_commit.lock().await.drain(..) {
+                        task.await;
+                    }
+                    Ok(value)
+                }
+                Err(e) => {
+                    if let Err(rollback) = transaction.rollback().await {
+                        eprintln!("[transaction] failed to roll back: {}", rollback);
+                    }
+                    Err(e)
+                }
+            }
+        })
+    }
+}
+
+/// 현재 커맨드의 트랜잭션 커넥션이나 풀에서 꺼낸 커넥션
+///
+/// 트랜잭션 커넥션은 하나뿐이므로, 동시에 실행한 쿼리들(`try_join!` 등)은 순서대로 실행됩니다.
+pub enum Connection {
+    Pool(PoolConnection<Postgres>),
+    Transaction(OwnedMappedMutexGuard<Option<Transaction<'static, Postgres>>, PgConnection>),
+}
+
+impl Deref for Connection {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &PgConnection {
+        match self {
+            Connection::Pool(connection) => connection,
+            Connection::Transaction(connection) => connection,
+        }
+    }
+}
+
+impl DerefMut for Connection {
+    fn deref_mut(&mut self) -> &mut PgConnection {
+        match self {
+            Connection::Pool(connection) => connection,
+            Connection::Transaction(connection) => connection,
+        }
+    }
+}
+
+/// 커맨드 트랜잭션 안이면 그 커넥션을, 아니면 풀에서 새 커넥션을 꺼냅니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// let mut connection = transaction::acquire(&self.pool).await?;
+/// query("...").execute(&mut *connection).await?;
+/// ```
+pub async fn acquire(pool: &PgPool) -> Result<Connection, sqlx::Error> {
+    let Ok(current) = CURRENT.try_with(Arc::clone) else {
+        return pool.acquire().await.map(Connection::Pool);
+    };
+
+    OwnedMutexGuard::try_map(current.transaction.clone().lock_owned().await, |transaction| transaction.as_deref_mut())
+        .map(Connection::Transaction)
+        .map_err(|_| sqlx::Error::Protocol("command transaction already finished".to_string()))
+}
+
+/// 현재 커맨드의 트랜잭션이 커밋된 뒤에 `task` 를 실행합니다. 롤백되면 실행하지 않습니다.
+///
+/// 트랜잭션 밖에서 호출하면 바로 실행합니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// transaction::after_commit(async move {
+///     if let Err(e) = publisher::publish(&client, subject, payload).await { ... }
+/// })
+/// .await;
+/// ```
+pub async fn after_commit(task: impl Future<Output = ()> + Send + 'static) {
+    match CURRENT.try_with(Arc::clone) {
+        Ok(current) => current.after_commit.lock().await.push(Box::pin(task)),
+        Err(_) => task.await,
+    }
+}
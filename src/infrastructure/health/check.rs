@@ -0,0 +1,65 @@
+use async_nats::connection::State as ConnectionState;
+use futures::future::BoxFuture;
+use serde_json::{Value, json};
+use sqlx::PgPool;
+
+pub use core_module::HealthCheck;
+
+/// `SELECT 1` 로 Postgres 연결을 확인합니다.
+pub struct DatabaseCheck {
+    pool: PgPool,
+}
+
+impl DatabaseCheck {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl HealthCheck for DatabaseCheck {
+    fn name(&self) -> &str {
+        "database"
+    }
+
+    fn check(&self) -> BoxFuture<'_, Result<Option<Value>, String>> {
+        Box::pin(async move {
+            sqlx::query("SELECT 1")
+                .execute(&self.pool)
+                .await
+                .map_err(|e| e.to_string())?;
+
+            Ok(Some(json!({
+                "pool_size": self.pool.size(),
+                "idle": self.pool.num_idle(),
+            })))
+        })
+    }
+}
+
+/// NATS 클라이언트의 연결 상태를 확인합니다.
+pub struct NatsCheck {
+    client: async_nats::Client,
+}
+
+impl NatsCheck {
+    pub fn new(client: async_nats::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl HealthCheck for NatsCheck {
+    fn name(&self) -> &str {
+        "nats"
+    }
+
+    fn check(&self) -> BoxFuture<'_, Result<Option<Value>, String>> {
+        Box::pin(async move {
+            match self.client.connection_state() {
+                ConnectionState::Connected => Ok(Some(json!({
+                    "server": self.client.server_info().server_name,
+                }))),
+                state => Err(format!("connection is {}", state)),
+            }
+        })
+    }
+}
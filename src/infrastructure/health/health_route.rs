@@ -0,0 +1,21 @@
+use ntex::web::*;
+use ntex::web::types::State;
+use serde_json::json;
+use crate::infrastructure::health::registry::{HealthRegistry, HealthStatus};
+
+/// 프로세스가 요청을 처리할 수 있는 상태인지만 확인합니다. 의존성은 검사하지 않습니다.
+#[get("/health/live")]
+async fn liveness() -> HttpResponse {
+    HttpResponse::Ok().json(&json!({ "status": HealthStatus::Up }))
+}
+
+/// 등록된 모든 의존성 검사를 실행하고, 하나라도 실패하면 503 을 반환합니다.
+#[get("/health/ready")]
+async fn readiness(registry: State<HealthRegistry>) -> HttpResponse {
+    let report = registry.run().await;
+
+    match report.status {
+        HealthStatus::Up => HttpResponse::Ok().json(&report),
+        HealthStatus::Down => HttpResponse::ServiceUnavailable().json(&report),
+    }
+}
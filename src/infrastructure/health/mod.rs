@@ -0,0 +1,3 @@
+pub mod check;
+pub mod registry;
+pub mod health_route;
\ No newline at end of file
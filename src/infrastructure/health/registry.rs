@@ -0,0 +1,197 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use futures::future::join_all;
+use kit_msa::{Health, Registration};
+use serde::Serialize;
+use serde_json::Value;
+use crate::infrastructure::health::check::HealthCheck;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckReport {
+    pub name: String,
+    pub status: HealthStatus,
+    pub duration_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub checks: Vec<CheckReport>,
+}
+
+#[derive(Clone)]
+struct RegisteredCheck {
+    check: Arc<dyn HealthCheck>,
+    timeout: Duration,
+}
+
+/// readiness 판단에 사용하는 검사 목록
+///
+/// 모든 검사는 동시에 실행되며, 검사별 타임아웃을 넘기면 실패로 처리합니다.
+#[derive(Clone, Default)]
+pub struct HealthRegistry {
+    checks: Vec<RegisteredCheck>,
+}
+
+impl HealthRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_check(self, check: impl HealthCheck + 'static) -> Self {
+        self.with_check_timeout(check, DEFAULT_TIMEOUT)
+    }
+
+    pub fn with_check_timeout(mut self, check: impl HealthCheck + 'static, timeout: Duration) -> Self {
+        self.checks.push(RegisteredCheck {
+            check: Arc::new(check),
+            timeout,
+        });
+        self
+    }
+
+    /// 모듈이 선언한 검사처럼 이미 공유 중인 검사를 등록합니다.
+    pub fn with_shared_check(mut self, check: Arc<dyn HealthCheck>) -> Self {
+        self.checks.push(RegisteredCheck {
+            check,
+            timeout: DEFAULT_TIMEOUT,
+        });
+        self
+    }
+
+    pub async fn run(&self) -> HealthReport {
+        let checks = join_all(self.checks.iter().map(Self::run_check)).await;
+
+        let status = if checks.iter().all(|check| check.status == HealthStatus::Up) {
+            HealthStatus::Up
+        } else {
+            HealthStatus::Down
+        };
+
+        HealthReport { status, checks }
+    }
+
+    /// 검사 결과를 `interval` 마다 레지스트리에 등록한 인스턴스의 상태로 반영합니다. 끝나지 않습니다.
+    ///
+    /// readiness 가 실패하면 `Critical` 로 바뀌어 디스커버리 후보에서 빠집니다.
+    pub async fn report_to(&self, registration: &Registration, interval: Duration) -> Infallible {
+        loop {
+            let health = match self.run().await.status {
+                HealthStatus::Up => Health::Passing,
+                HealthStatus::Down => Health::Critical,
+            };
+            registration.set_health(health);
+
+            ntex::time::sleep(interval).await;
+        }
+    }
+
+    async fn run_check(registered: &RegisteredCheck) -> CheckReport {
+        let started_at = Instant::now();
+        let result = ntex::time::timeout(registered.timeout, registered.check.check()).await;
+        let duration_ms = started_at.elapsed().as_millis();
+
+        let (status, details, error) = match result {
+            Ok(Ok(details)) => (HealthStatus::Up, details, None),
+            Ok(Err(e)) => (HealthStatus::Down, None, Some(e)),
+            Err(()) => (
+                HealthStatus::Down,
+                None,
+                Some(format!("timed out after {}ms", registered.timeout.as_millis())),
+            ),
+        };
+
+        CheckReport {
+            name: registered.check.name().to_string(),
+            status,
+            duration_ms,
+            details,
+            error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future::BoxFuture;
+
+    struct StaticCheck {
+        name: &'static str,
+        result: Result<(), &'static str>,
+        delay: Duration,
+    }
+
+    impl HealthCheck for StaticCheck {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn check(&self) -> BoxFuture<'_, Result<Option<Value>, String>> {
+            Box::pin(async move {
+                ntex::time::sleep(self.delay).await;
+                self.result.map(|_| None).map_err(str::to_string)
+            })
+        }
+    }
+
+    #[ntex::test]
+    async fn test_all_checks_up() {
+        let registry = HealthRegistry::new()
+            .with_check(StaticCheck { name: "a", result: Ok(()), delay: Duration::ZERO })
+            .with_check(StaticCheck { name: "b", result: Ok(()), delay: Duration::ZERO });
+
+        let report = registry.run().await;
+
+        assert_eq!(report.status, HealthStatus::Up);
+        assert_eq!(report.checks.len(), 2);
+    }
+
+    #[ntex::test]
+    async fn test_failed_and_timed_out_checks() {
+        let registry = HealthRegistry::new()
+            .with_check(StaticCheck { name: "failing", result: Err("boom"), delay: Duration::ZERO })
+            .with_check_timeout(
+                StaticCheck { name: "slow", result: Ok(()), delay: Duration::from_millis(200) },
+                Duration::from_millis(20),
+            );
+
+        let report = registry.run().await;
+
+        assert_eq!(report.status, HealthStatus::Down);
+        assert_eq!(report.checks[0].error.as_deref(), Some("boom"));
+        assert!(report.checks[1].error.as_deref().unwrap().starts_with("timed out"));
+    }
+
+    #[ntex::test]
+    async fn test_reports_failed_readiness_to_registration() {
+        let registration = kit_msa::ServiceRegistry::new(kit_msa::MemoryRegistry::new())
+            .register(kit_msa::ServiceInstance::new("user-service", "10.0.0.7:8080"))
+            .await
+            .unwrap();
+        let registry = HealthRegistry::new()
+            .with_check(StaticCheck { name: "failing", result: Err("boom"), delay: Duration::ZERO });
+
+        let _ = ntex::util::select(
+            registry.report_to(&registration, Duration::from_secs(60)),
+            ntex::time::sleep(Duration::from_millis(20)),
+        )
+        .await;
+
+        assert_eq!(registration.instance().health, Health::Critical);
+    }
+}
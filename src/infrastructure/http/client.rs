@@ -1,12 +1,26 @@
-use reqwest::{Client, Error};
-use reqwest::header::HeaderMap;
+use kit_context::RequestContext;
+use kit_msa::{Discovery, RegistryError};
+use reqwest::{Client, Method, RequestBuilder, Response};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use thiserror::Error;
+use crate::infrastructure::trace::propagation;
+
+#[derive(Debug, Error)]
+pub enum HttpClientError {
+    #[error("failed to discover service: {0}")]
+    Discovery(#[from] RegistryError),
+
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+}
 
 #[derive(Debug)]
 pub struct HttpClient {
     pub client: Client,
     pub base_url: String,
+    discovery: Option<Discovery>,
 }
 
 impl HttpClient {
@@ -35,8 +49,103 @@ impl HttpClient {
     pub fn new(base_url: impl Into<String>) -> Self {
         HttpClient {
             client: Client::new(),
-            base_url: base_url.into()
+            base_url: base_url.into(),
+            discovery: None,
+        }
+    }
+
+    /// 서비스 레지스트리에서 호출할 인스턴스를 찾는 HTTP 클라이언트를 생성합니다.
+    ///
+    /// `base_url` 이 `service://<서비스 이름>` 으로 시작하면 요청마다 [`Discovery`] 로 인스턴스를 골라 `http://<주소>` 로 보냅니다.
+    /// 연결하지 못하면 다음 후보 인스턴스로 다시 보내고, 인스턴스를 찾지 못하면 [`HttpClientError::Discovery`] 를 돌려줍니다.
+    /// 그 외의 URL 은 [`HttpClient::new`] 와 같습니다.
+    ///
+    /// # 예시
+    ///
+    /// ```
+    /// let client = HttpClient::with_discovery("service://user-service/api", discovery);
+    /// let user: User = client.get("/users/1", None).await?;
+    /// ```
+    pub fn with_discovery(base_url: impl Into<String>, discovery: Discovery) -> Self {
+        HttpClient {
+            client: Client::new(),
+            base_url: base_url.into(),
+            discovery: Some(discovery),
+        }
+    }
+
+    /// 요청을 보낼 URL 후보. `service://` 주소는 이번 요청에 쓸 인스턴스들의 주소로 바꿉니다.
+    async fn urls(&self, endpoint: &str) -> Result<Vec<String>, RegistryError> {
+        let (Some(discovery), Some(rest)) = (&self.discovery, self.base_url.strip_prefix("service://")) else {
+            return Ok(vec![format!("{}{}", self.base_url, endpoint)]);
+        };
+        let (service, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+
+        let urls = discovery
+            .candidates(service)
+            .await?
+            .into_iter()
+            .map(|instance| format!("http://{}{}{}", instance.address, path, endpoint))
+            .collect::<Vec<_>>();
+
+        if urls.is_empty() {
+            return Err(RegistryError::NoInstance(service.to_string()));
+        }
+        Ok(urls)
+    }
+
+    /// 요청 헤더를 준비합니다.
+    ///
+    /// 헤더가 주어지지 않으면 `Accept: application/json` 을 기본으로 사용하며,
+    /// 현재 스팬이 있으면 `traceparent`/`tracestate` 를 주입해 트레이스를 이어갑니다.
+    /// 요청 컨텍스트 안이라면 요청 ID 와 남은 처리 시간도 함께 전달합니다.
+    fn request_headers(headers: Option<HeaderMap>) -> HeaderMap {
+        let mut request_headers = headers.unwrap_or_else(|| {
+            let mut headers = HeaderMap::new();
+            headers.insert("Accept", "application/json".parse().unwrap());
+            headers
+        });
+
+        propagation::inject(&mut request_headers);
+
+        if let Some(context) = RequestContext::current() {
+            for (name, value) in context.propagation_headers() {
+                if let Ok(value) = HeaderValue::from_str(&value) {
+                    request_headers.insert(HeaderName::from_static(name), value);
+                }
+            }
+        }
+
+        request_headers
+    }
+
+    /// 요청을 보냅니다. 요청 컨텍스트에 마감 시간이 있으면 남은 시간을 타임아웃으로 사용합니다.
+    ///
+    /// 후보 인스턴스에 연결하지 못하면 다음 후보로 다시 보냅니다. 연결된 뒤의 실패는 요청이 처리됐을 수 있으므로 다시 보내지 않습니다.
+    async fn send(
+        &self,
+        method: Method,
+        endpoint: &str,
+        headers: Option<HeaderMap>,
+        body: impl Fn(RequestBuilder) -> RequestBuilder,
+    ) -> Result<Response, HttpClientError> {
+        let headers = Self::request_headers(headers);
+        let mut error = None;
+
+        for url in self.urls(endpoint).await? {
+            let request = body(self.client.request(method.clone(), &url).headers(headers.clone()));
+            let request = match RequestContext::current().and_then(|context| context.remaining()) {
+                Some(remaining) => request.timeout(remaining),
+                None => request,
+            };
+
+            match request.send().await {
+                Err(e) if e.is_connect() => error = Some(e),
+                result => return Ok(result?),
+            }
         }
+
+        Err(error.map_or_else(|| RegistryError::NoInstance(self.base_url.clone()).into(), Into::into))
     }
 
     /// GET 요청을 보내고 응답을 지정된 타입으로 변환합니다.
@@ -52,7 +161,7 @@ impl HttpClient {
     ///
     /// # 반환값
     ///
-    /// * `Result<T, reqwest::Error>` - 성공 시 응답 데이터(T 타입), 실패 시 reqwest 에러
+    /// * `Result<T, HttpClientError>` - 성공 시 응답 데이터(T 타입), 실패 시 디스커버리 또는 reqwest 에러
     ///
     /// # 예시
     ///
@@ -63,22 +172,12 @@ impl HttpClient {
         &self,
         endpoint: &str,
         headers: Option<HeaderMap>
-    ) -> Result<T, reqwest::Error>
+    ) -> Result<T, HttpClientError>
     where
         T: DeserializeOwned + Send,
     {
-        let url = format!("{}{}", self.base_url, endpoint);
-
-        let request_headers = headers.unwrap_or_else(|| {
-            let mut headers = HeaderMap::new();
-            headers.insert("Accept", "application/json".parse().unwrap());
-            headers
-        });
-
-        let response = self.client
-            .get(&url)
-            .headers(request_headers)
-            .send()
+        let response = self
+            .send(Method::GET, endpoint, headers, |request| request)
             .await?
             .json::<T>()
             .await?;
@@ -101,7 +200,7 @@ impl HttpClient {
     ///
     /// # 반환값
     ///
-    /// * `Result<T, reqwest::Error>` - 성공 시 응답 데이터(T 타입), 실패 시 reqwest 에러
+    /// * `Result<T, HttpClientError>` - 성공 시 응답 데이터(T 타입), 실패 시 디스커버리 또는 reqwest 에러
     /// * `T` - 응답 데이터 타입 (DeserializeOwned + Send 트레이트 구현한 Projection 타입)
     ///
     /// # 예시
@@ -115,24 +214,13 @@ impl HttpClient {
         endpoint: &str,
         body: &B,
         headers: Option<HeaderMap>
-    ) -> Result<T, Error>
+    ) -> Result<T, HttpClientError>
     where
         T: DeserializeOwned + Send,
         B: Serialize + Send,
     {
-        let url = format!("{}{}", self.base_url, endpoint);
-
-        let request_headers = headers.unwrap_or_else(|| {
-            let mut headers = HeaderMap::new();
-            headers.insert("Accept", "application/json".parse().unwrap());
-            headers
-        });
-
-        let response = self.client
-            .post(&url)
-            .headers(request_headers)
-            .json(body)
-            .send()
+        let response = self
+            .send(Method::POST, endpoint, headers, |request| request.json(body))
             .await?
             .json::<T>()
             .await?;
@@ -155,7 +243,7 @@ impl HttpClient {
     ///
     /// # 반환값
     ///
-    /// * `Result<T, reqwest::Error>` - 성공 시 응답 데이터(T 타입), 실패 시 reqwest 에러
+    /// * `Result<T, HttpClientError>` - 성공 시 응답 데이터(T 타입), 실패 시 디스커버리 또는 reqwest 에러
     ///
     /// # 예시
     ///
@@ -168,24 +256,13 @@ impl HttpClient {
         endpoint: &str,
         body: &B,
         headers: Option<HeaderMap>
-    ) -> Result<T, Error>
+    ) -> Result<T, HttpClientError>
     where
         T: DeserializeOwned + Send,
         B: Serialize + Send,
     {
-        let url = format!("{}{}", self.base_url, endpoint);
-
-        let request_headers = headers.unwrap_or_else(|| {
-            let mut headers = HeaderMap::new();
-            headers.insert("Accept", "application/json".parse().unwrap());
-            headers
-        });
-
-        let response = self.client
-            .put(&url)
-            .headers(request_headers)
-            .json(body)
-            .send()
+        let response = self
+            .send(Method::PUT, endpoint, headers, |request| request.json(body))
             .await?
             .json::<T>()
             .await?;
@@ -206,7 +283,7 @@ impl HttpClient {
     ///
     /// # 반환값
     ///
-    /// * `Result<T, reqwest::Error>` - 성공 시 응답 데이터(T 타입), 실패 시 reqwest 에러
+    /// * `Result<T, HttpClientError>` - 성공 시 응답 데이터(T 타입), 실패 시 디스커버리 또는 reqwest 에러
     ///
     /// # 예시
     ///
@@ -227,22 +304,12 @@ impl HttpClient {
         &self,
         endpoint: &str,
         headers: Option<HeaderMap>
-    ) -> Result<T, Error>
+    ) -> Result<T, HttpClientError>
     where
         T: DeserializeOwned + Send,
     {
-        let url = format!("{}{}", self.base_url, endpoint);
-
-        let request_headers = headers.unwrap_or_else(|| {
-            let mut headers = HeaderMap::new();
-            headers.insert("Accept", "application/json".parse().unwrap());
-            headers
-        });
-
-        let response = self.client
-            .delete(&url)
-            .headers(request_headers)
-            .send()
+        let response = self
+            .send(Method::DELETE, endpoint, headers, |request| request)
             .await?
             .json::<T>()
             .await?;
@@ -265,7 +332,7 @@ impl HttpClient {
     ///
     /// # 반환값
     ///
-    /// * `Result<T, reqwest::Error>` - 성공 시 응답 데이터(T 타입), 실패 시 reqwest 에러
+    /// * `Result<T, HttpClientError>` - 성공 시 응답 데이터(T 타입), 실패 시 디스커버리 또는 reqwest 에러
     ///
     /// # 예시
     ///
@@ -288,24 +355,13 @@ impl HttpClient {
         endpoint: &str,
         body: &B,
         headers: Option<HeaderMap>
-    ) -> Result<T, Error>
+    ) -> Result<T, HttpClientError>
     where
         T: DeserializeOwned + Send,
         B: Serialize + Send,
     {
-        let url = format!("{}{}", self.base_url, endpoint);
-
-        let request_headers = headers.unwrap_or_else(|| {
-            let mut headers = HeaderMap::new();
-            headers.insert("Accept", "application/json".parse().unwrap());
-            headers
-        });
-
-        let response = self.client
-            .patch(&url)
-            .headers(request_headers)
-            .json(body)
-            .send()
+        let response = self
+            .send(Method::PATCH, endpoint, headers, |request| request.json(body))
             .await?
             .json::<T>()
             .await?;
@@ -317,7 +373,9 @@ impl HttpClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use kit_msa::{MemoryRegistry, RegistryBackend, ServiceInstance};
     use serde::{Deserialize, Serialize};
+    use std::time::Duration;
 
     #[derive(Serialize, Deserialize, Debug, PartialEq)]
     struct Post {
@@ -403,4 +461,67 @@ mod tests {
         assert_eq!(response.id, 1);
         assert_eq!(response.title, patch_data.title);
     }
+
+    #[tokio::test]
+    async fn test_service_url_resolves_registered_instances() {
+        let mut first = mockito::Server::new_async().await;
+        let mut second = mockito::Server::new_async().await;
+        let first_mock = first.mock("GET", "/api/ping").with_body(r#"{"from":"first"}"#).create_async().await;
+        let second_mock = second.mock("GET", "/api/ping").with_body(r#"{"from":"second"}"#).create_async().await;
+
+        let registry = MemoryRegistry::new();
+        let ttl = Duration::from_secs(5);
+        registry.register(&ServiceInstance::new("echo-service", first.host_with_port()), ttl).await.unwrap();
+        registry.register(&ServiceInstance::new("echo-service", second.host_with_port()), ttl).await.unwrap();
+
+        #[derive(Deserialize)]
+        struct Pong {
+            from: String,
+        }
+
+        let client = HttpClient::with_discovery("service://echo-service/api", Discovery::new(registry));
+        let mut replies = Vec::new();
+        for _ in 0..2 {
+            let pong: Pong = client.get("/ping", None).await.unwrap();
+            replies.push(pong.from);
+        }
+        replies.sort();
+
+        assert_eq!(replies, ["first", "second"]);
+        first_mock.assert_async().await;
+        second_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_service_url_fails_over_to_next_candidate() {
+        let mut live = mockito::Server::new_async().await;
+        let mock = live.mock("GET", "/ping").with_body("{}").expect(2).create_async().await;
+
+        // 바인딩했다가 닫은 포트는 연결이 거부된다.
+        let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+
+        let registry = MemoryRegistry::new();
+        let ttl = Duration::from_secs(5);
+        registry.register(&ServiceInstance::new("echo-service", closed.to_string()), ttl).await.unwrap();
+        registry.register(&ServiceInstance::new("echo-service", live.host_with_port()), ttl).await.unwrap();
+
+        #[derive(Deserialize)]
+        struct Empty {}
+
+        let client = HttpClient::with_discovery("service://echo-service", Discovery::new(registry));
+        for _ in 0..2 {
+            let _: Empty = client.get("/ping", None).await.unwrap();
+        }
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_service_url_without_instances_is_an_error() {
+        let client = HttpClient::with_discovery("service://missing-service", Discovery::new(MemoryRegistry::new()));
+
+        let result = client.get::<serde_json::Value>("/ping", None).await;
+
+        assert!(matches!(result, Err(HttpClientError::Discovery(RegistryError::NoInstance(_)))));
+    }
 }
@@ -0,0 +1,121 @@
+use async_nats::PublishError;
+use core_filter::{ErrorResponse, ExceptionFilters, catch};
+use kit_event::CommandInProgress;
+use kit_security::SecurityError;
+use ntex::http::StatusCode;
+use serde_json::json;
+use crate::infrastructure::http::client::HttpClientError;
+use crate::infrastructure::mq::fanout::FanoutError;
+
+/// 앱 전체에 적용되는 에러 필터
+///
+/// 모듈은 스코프에 `filters.extend()` 로 필터를 덧붙여 특정 에러의 응답을 바꿀 수 있습니다.
+pub fn global() -> ExceptionFilters {
+    ExceptionFilters::new()
+        .with_filter(catch(database_error))
+        .with_filter(catch(upstream_error))
+        .with_filter(catch(|_: &PublishError| {
+            ErrorResponse::new(StatusCode::SERVICE_UNAVAILABLE, "messaging_unavailable", "Message broker is unavailable")
+        }))
+        .with_filter(catch(fanout_error))
+        .with_filter(catch(security_error))
+        .with_filter(catch(|_: &CommandInProgress| {
+            ErrorResponse::new(StatusCode::CONFLICT, "command_in_progress", "A request with this idempotency key is still being processed")
+        }))
+}
+
+fn database_error(error: &sqlx::Error) -> ErrorResponse {
+    match error {
+        sqlx::Error::RowNotFound => ErrorResponse::from_status(StatusCode::NOT_FOUND),
+        sqlx::Error::Database(e) if e.is_unique_violation() => {
+            ErrorResponse::new(StatusCode::CONFLICT, "conflict", "Resource already exists")
+                .with_details(json!({ "constraint": e.constraint() }))
+        }
+        sqlx::Error::Database(e) if e.is_foreign_key_violation() || e.is_check_violation() => {
+            ErrorResponse::new(StatusCode::UNPROCESSABLE_ENTITY, "constraint_violation", "Request violates a data constraint")
+                .with_details(json!({ "constraint": e.constraint() }))
+        }
+        sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed => ErrorResponse::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "database_unavailable",
+            "Database is unavailable",
+        ),
+        _ => ErrorResponse::internal(),
+    }
+}
+
+fn fanout_error(error: &FanoutError) -> ErrorResponse {
+    match error {
+        FanoutError::Subscribe(_) => {
+            ErrorResponse::new(StatusCode::SERVICE_UNAVAILABLE, "messaging_unavailable", "Message broker is unavailable")
+        }
+        FanoutError::Handshake(_) => {
+            ErrorResponse::new(StatusCode::BAD_REQUEST, "websocket_handshake_failed", "WebSocket upgrade request is invalid")
+        }
+    }
+}
+
+fn security_error(error: &SecurityError) -> ErrorResponse {
+    match error {
+        SecurityError::Forbidden(role) => ErrorResponse::new(StatusCode::FORBIDDEN, "forbidden", "Caller lacks a required role")
+            .with_details(json!({ "role": role })),
+        _ => ErrorResponse::new(StatusCode::UNAUTHORIZED, "unauthenticated", "Authentication is required"),
+    }
+}
+
+// HttpClient 로 호출한 상위 서비스의 실패는 이 서비스의 실패가 아니므로 5xx 게이트웨이 계열로 응답한다.
+fn upstream_error(error: &HttpClientError) -> ErrorResponse {
+    let error = match error {
+        HttpClientError::Discovery(_) => {
+            return ErrorResponse::new(StatusCode::BAD_GATEWAY, "upstream_unavailable", "Upstream service is unavailable");
+        }
+        HttpClientError::Request(e) => e,
+    };
+
+    if error.is_timeout() {
+        ErrorResponse::new(StatusCode::GATEWAY_TIMEOUT, "upstream_timeout", "Upstream service timed out")
+    } else if error.is_connect() {
+        ErrorResponse::new(StatusCode::BAD_GATEWAY, "upstream_unavailable", "Upstream service is unavailable")
+    } else if let Some(status) = error.status() {
+        ErrorResponse::new(StatusCode::BAD_GATEWAY, "upstream_error", "Upstream service returned an error")
+            .with_details(json!({ "status": status.as_u16() }))
+    } else {
+        ErrorResponse::new(StatusCode::BAD_GATEWAY, "upstream_error", "Upstream service request failed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_database_errors() {
+        let filters = global();
+
+        assert_eq!(filters.resolve(&sqlx::Error::RowNotFound).status(), StatusCode::NOT_FOUND);
+        assert_eq!(filters.resolve(&sqlx::Error::PoolTimedOut).code(), "database_unavailable");
+        assert_eq!(filters.resolve(&sqlx::Error::WorkerCrashed).code(), "internal_error");
+    }
+
+    #[test]
+    fn test_security_errors() {
+        let filters = global();
+
+        assert_eq!(filters.resolve(&SecurityError::Unauthenticated).status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(filters.resolve(&SecurityError::Forbidden("admin".to_string())).status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_command_in_progress() {
+        let error = kit_event::BusError::handler(CommandInProgress("UserRegisterCommand"));
+
+        assert_eq!(global().resolve(&error).status(), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn test_discovery_errors() {
+        let error = HttpClientError::Discovery(kit_msa::RegistryError::NoInstance("user-service".to_string()));
+
+        assert_eq!(global().resolve(&error).status(), StatusCode::BAD_GATEWAY);
+    }
+}
@@ -0,0 +1,137 @@
+use std::time::Duration;
+use futures::future::BoxFuture;
+use kit_http::{Claim, ResponseStore, StoreError, StoredResponse};
+use ntex::util::Bytes;
+use sqlx::{FromRow, PgPool};
+
+#[derive(FromRow)]
+struct IdempotencyRecord {
+    fingerprint: String,
+    status: Option<i16>,
+    headers: Option<String>,
+    body: Option<Vec<u8>>,
+}
+
+/// `idempotency_keys` 테이블에 요청 지문과 응답을 저장합니다.
+///
+/// 여러 인스턴스가 같은 키를 동시에 받아도 기본 키 충돌로 한 요청만 선점합니다.
+/// 만료된 키는 같은 키로 다시 선점할 때 덮어쓰고, 나머지는 [`PgResponseStore::purge_expired`] 가 주기적으로 지웁니다.
+/// 테이블은 `platform` 모듈의 마이그레이션이 만듭니다.
+#[derive(Debug, Clone)]
+pub struct PgResponseStore {
+    pool: PgPool,
+}
+
+impl PgResponseStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// 만료된 키를 `interval` 마다 지웁니다. 끝나지 않습니다.
+    pub async fn purge_expired(&self, interval: Duration) {
+        loop {
+            ntex::time::sleep(interval).await;
+
+            if let Err(e) = sqlx::query("DELETE FROM idempotency_keys WHERE expires_at <= CURRENT_TIMESTAMP")
+                .execute(&self.pool)
+                .await
+            {
+                eprintln!("[idempotency] failed to purge expired keys: {}", e);
+            }
+        }
+    }
+
+    async fn try_claim(&self, key: &str, fingerprint: &str, lock_timeout: Duration) -> Result<Claim, sqlx::Error> {
+        // 같은 키가 만료됐을 때만 새 요청으로 덮어쓴다.
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (key, fingerprint, expires_at)
+            VALUES ($1, $2, CURRENT_TIMESTAMP + make_interval(secs => $3))
+            ON CONFLICT (key) DO UPDATE
+            SET fingerprint = EXCLUDED.fingerprint, status = NULL, headers = NULL, body = NULL,
+                expires_at = EXCLUDED.expires_at, created_at = CURRENT_TIMESTAMP
+            WHERE idempotency_keys.expires_at <= CURRENT_TIMESTAMP
+            "#
+        )
+            .bind(key)
+            .bind(fingerprint)
+            .bind(lock_timeout.as_secs_f64())
+            .execute(&self.pool)
+            .await?;
+
+        if inserted.rows_affected() == 1 {
+            return Ok(Claim::Acquired);
+        }
+
+        let record = sqlx::query_as::<_, IdempotencyRecord>(
+            "SELECT fingerprint, status, headers::text AS headers, body FROM idempotency_keys WHERE key = $1"
+        )
+            .bind(key)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(match (record.status, record.body) {
+            (Some(status), body) => Claim::Completed {
+                fingerprint: record.fingerprint,
+                response: StoredResponse {
+                    status: status as u16,
+                    headers: match record.headers {
+                        Some(headers) => serde_json::from_str(&headers).map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+                        None => Vec::new(),
+                    },
+                    body: Bytes::from(body.unwrap_or_default()),
+                },
+            },
+            (None, _) => Claim::InProgress {
+                fingerprint: record.fingerprint,
+            },
+        })
+    }
+}
+
+impl ResponseStore for PgResponseStore {
+    fn claim(&self, key: &str, fingerprint: &str, lock_timeout: Duration) -> BoxFuture<'_, Result<Claim, StoreError>> {
+        let key = key.to_string();
+        let fingerprint = fingerprint.to_string();
+
+        Box::pin(async move { Ok(self.try_claim(&key, &fingerprint, lock_timeout).await?) })
+    }
+
+    fn complete(&self, key: &str, response: StoredResponse, ttl: Duration) -> BoxFuture<'_, Result<(), StoreError>> {
+        let key = key.to_string();
+
+        Box::pin(async move {
+            let headers = serde_json::to_string(&response.headers)?;
+
+            sqlx::query(
+                r#"
+                UPDATE idempotency_keys
+                SET status = $2, headers = $3::jsonb, body = $4, expires_at = CURRENT_TIMESTAMP + make_interval(secs => $5)
+                WHERE key = $1
+                "#
+            )
+                .bind(key)
+                .bind(response.status as i16)
+                .bind(headers)
+                .bind(response.body.to_vec())
+                .bind(ttl.as_secs_f64())
+                .execute(&self.pool)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn release(&self, key: &str) -> BoxFuture<'_, Result<(), StoreError>> {
+        let key = key.to_string();
+
+        Box::pin(async move {
+            sqlx::query("DELETE FROM idempotency_keys WHERE key = $1 AND status IS NULL")
+                .bind(key)
+                .execute(&self.pool)
+                .await?;
+
+            Ok(())
+        })
+    }
+}
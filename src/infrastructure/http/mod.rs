@@ -1 +1,5 @@
-pub mod client;
\ No newline at end of file
+pub mod client;
+pub mod route;
+pub mod exception_filters;
+pub mod openapi;
+pub mod idempotency_store;
\ No newline at end of file
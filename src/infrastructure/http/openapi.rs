@@ -0,0 +1,40 @@
+use core_filter::ErrorResponse;
+use kit_router::{OpenApi, RouteTable, SecurityScheme};
+use serde_json::Value;
+
+/// 저장소에 커밋된 문서. 라우트나 타입이 바뀌면 `UPDATE_OPENAPI=1 cargo test` 로 갱신합니다.
+#[cfg(test)]
+const COMMITTED_SPEC: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+
+/// 모듈이 등록한 라우트로 이 서버의 OpenAPI 문서를 만듭니다.
+pub fn spec(table: &RouteTable) -> Value {
+    OpenApi::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+        .description("사용자 서비스 API")
+        .content_types(&["application/json", "application/msgpack"])
+        .error_schema::<ErrorResponse>()
+        .security_scheme("bearer", SecurityScheme::Bearer { format: Some("JWT") })
+        .generate(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_module::Bootstrap;
+    use crate::modules;
+
+    #[test]
+    fn test_committed_spec_is_up_to_date() {
+        let table = Bootstrap::new().modules(modules::modules()).route_table();
+        let generated = serde_json::to_string_pretty(&spec(&table)).unwrap() + "\n";
+
+        if std::env::var_os("UPDATE_OPENAPI").is_some() {
+            std::fs::write(COMMITTED_SPEC, &generated).unwrap();
+        }
+
+        let committed = std::fs::read_to_string(COMMITTED_SPEC).unwrap_or_default();
+        assert!(
+            committed == generated,
+            "openapi.json is out of date. Run `UPDATE_OPENAPI=1 cargo test` and commit the result."
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use kit_router::RoutePattern;
+use ntex::web::HttpRequest;
+
+/// 요청이 매칭된 라우트 패턴 (`/v1/user/{id}`)
+///
+/// 라우터로 등록한 라우트에서만 값이 있습니다. 라우팅 전에 미들웨어가 거절한 요청이나 매칭되지 않은 요청은 `None` 입니다.
+/// 요청 경로는 클라이언트가 마음대로 바꿀 수 있어 라벨 카디널리티가 무한히 늘어나므로 대신 쓰지 않습니다.
+pub fn route_pattern(request: &HttpRequest) -> Option<String> {
+    RoutePattern::of(request).map(|pattern| pattern.as_str().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::web::test::TestRequest;
+
+    #[test]
+    fn test_route_pattern_is_never_the_request_path() {
+        let request = TestRequest::with_uri("/metrics").to_http_request();
+
+        assert_eq!(route_pattern(&request), None);
+    }
+}
@@ -0,0 +1,22 @@
+use ntex::web::*;
+use ntex::web::types::State;
+use crate::infrastructure::metrics::registry;
+use crate::states::AppState;
+
+#[get("/metrics")]
+async fn metrics(state: State<AppState>) -> HttpResponse {
+    let metrics = registry::global();
+
+    let idle = state.pool.num_idle() as i64;
+    let size = state.pool.size() as i64;
+    metrics.database.pool_connections.with_label_values(&["idle"]).set(idle);
+    metrics.database.pool_connections.with_label_values(&["active"]).set(size - idle);
+    metrics.database.pool_max_connections.set(state.pool.options().get_max_connections() as i64);
+
+    match metrics.encode() {
+        Ok(body) => HttpResponse::Ok()
+            .content_type("text/plain; version=0.0.4")
+            .body(body),
+        Err(e) => HttpResponse::InternalServerError().body(format!("Error: {}", e)),
+    }
+}
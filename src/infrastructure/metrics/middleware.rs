@@ -0,0 +1,82 @@
+use std::time::Instant;
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web;
+use prometheus::IntGauge;
+use crate::infrastructure::http::route::route_pattern;
+use crate::infrastructure::metrics::registry;
+
+/// 라우트별 요청 수, 지연 시간, 처리 중인 요청 수를 기록하는 미들웨어
+pub struct RequestMetrics;
+
+impl<S> Middleware<S> for RequestMetrics {
+    type Service = RequestMetricsMiddleware<S>;
+
+    fn create(&self, service: S) -> Self::Service {
+        RequestMetricsMiddleware { service }
+    }
+}
+
+pub struct RequestMetricsMiddleware<S> {
+    service: S,
+}
+
+impl<S, Err> Service<web::WebRequest<Err>> for RequestMetricsMiddleware<S>
+where
+    S: Service<web::WebRequest<Err>, Response = web::WebResponse, Error = web::Error>,
+    Err: web::ErrorRenderer,
+{
+    type Response = web::WebResponse;
+    type Error = web::Error;
+
+    ntex::forward_ready!(service);
+
+    async fn call(
+        &self,
+        req: web::WebRequest<Err>,
+        ctx: ServiceCtx<'_, Self>,
+    ) -> Result<Self::Response, Self::Error> {
+        let http = &registry::global().http;
+        let method = req.method().to_string();
+
+        let in_flight = InFlight::start(http.requests_in_flight.with_label_values(&[&method]));
+
+        let started_at = Instant::now();
+        let res = ctx.call(&self.service, req).await;
+        let elapsed = started_at.elapsed().as_secs_f64();
+
+        drop(in_flight);
+
+        let (route, status) = match &res {
+            Ok(res) => {
+                let route = route_pattern(res.request()).unwrap_or_else(|| "unmatched".to_string());
+                (route, res.status().as_u16().to_string())
+            }
+            Err(_) => ("unmatched".to_string(), "500".to_string()),
+        };
+
+        http.requests_total
+            .with_label_values(&[&method, &route, &status])
+            .inc();
+        http.request_duration_seconds
+            .with_label_values(&[&method, &route])
+            .observe(elapsed);
+
+        res
+    }
+}
+
+// 패닉이나 취소로 응답 없이 끝나도 처리 중인 요청 수를 되돌린다.
+struct InFlight(IntGauge);
+
+impl InFlight {
+    fn start(gauge: IntGauge) -> Self {
+        gauge.inc();
+        Self(gauge)
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
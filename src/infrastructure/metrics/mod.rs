@@ -0,0 +1,3 @@
+pub mod registry;
+pub mod middleware;
+pub mod metrics_route;
\ No newline at end of file
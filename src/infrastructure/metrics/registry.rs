@@ -0,0 +1,156 @@
+use std::sync::LazyLock;
+use prometheus::core::Collector;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+
+static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+/// 서버 전역 메트릭 레지스트리
+///
+/// HTTP / DB 커넥션 풀 / NATS 메트릭을 기본으로 등록하며,
+/// 각 모듈은 [`register`] 로 자신의 비즈니스 메트릭을 추가할 수 있습니다.
+pub struct Metrics {
+    registry: Registry,
+    pub http: HttpMetrics,
+    pub database: DatabaseMetrics,
+    pub nats: NatsMetrics,
+}
+
+/// 라우트별 RED(Rate, Errors, Duration) 메트릭
+pub struct HttpMetrics {
+    pub requests_total: IntCounterVec,
+    pub request_duration_seconds: HistogramVec,
+    pub requests_in_flight: IntGaugeVec,
+}
+
+/// sqlx 커넥션 풀 상태. `/metrics` 스크레이프 시점에 갱신됩니다.
+pub struct DatabaseMetrics {
+    pub pool_connections: IntGaugeVec,
+    pub pool_max_connections: IntGauge,
+}
+
+pub struct NatsMetrics {
+    pub published_total: IntCounterVec,
+    pub consumed_total: IntCounterVec,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+
+        let http = HttpMetrics {
+            requests_total: IntCounterVec::new(
+                Opts::new("http_requests_total", "Total number of HTTP requests"),
+                &["method", "route", "status"],
+            )
+            .expect("Invalid http_requests_total metric"),
+            request_duration_seconds: HistogramVec::new(
+                HistogramOpts::new("http_request_duration_seconds", "HTTP request latency in seconds"),
+                &["method", "route"],
+            )
+            .expect("Invalid http_request_duration_seconds metric"),
+            requests_in_flight: IntGaugeVec::new(
+                Opts::new("http_requests_in_flight", "Number of HTTP requests currently being served"),
+                &["method"],
+            )
+            .expect("Invalid http_requests_in_flight metric"),
+        };
+
+        let database = DatabaseMetrics {
+            pool_connections: IntGaugeVec::new(
+                Opts::new("db_pool_connections", "Number of database pool connections by state"),
+                &["state"],
+            )
+            .expect("Invalid db_pool_connections metric"),
+            pool_max_connections: IntGauge::new(
+                "db_pool_max_connections",
+                "Maximum number of database pool connections",
+            )
+            .expect("Invalid db_pool_max_connections metric"),
+        };
+
+        let nats = NatsMetrics {
+            published_total: IntCounterVec::new(
+                Opts::new("nats_messages_published_total", "Total number of NATS messages published"),
+                &["subject", "result"],
+            )
+            .expect("Invalid nats_messages_published_total metric"),
+            consumed_total: IntCounterVec::new(
+                Opts::new("nats_messages_consumed_total", "Total number of NATS messages consumed"),
+                &["subject"],
+            )
+            .expect("Invalid nats_messages_consumed_total metric"),
+        };
+
+        let collectors: Vec<Box<dyn Collector>> = vec![
+            Box::new(http.requests_total.clone()),
+            Box::new(http.request_duration_seconds.clone()),
+            Box::new(http.requests_in_flight.clone()),
+            Box::new(database.pool_connections.clone()),
+            Box::new(database.pool_max_connections.clone()),
+            Box::new(nats.published_total.clone()),
+            Box::new(nats.consumed_total.clone()),
+        ];
+
+        for collector in collectors {
+            registry.register(collector).expect("Failed to register default metrics");
+        }
+
+        Self {
+            registry,
+            http,
+            database,
+            nats,
+        }
+    }
+
+    /// Prometheus text exposition format 으로 현재 메트릭을 인코딩합니다.
+    pub fn encode(&self) -> Result<String, prometheus::Error> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+
+        String::from_utf8(buffer).map_err(|e| prometheus::Error::Msg(e.to_string()))
+    }
+}
+
+pub fn global() -> &'static Metrics {
+    &METRICS
+}
+
+/// 모듈의 비즈니스 메트릭을 전역 레지스트리에 등록하고, 등록된 메트릭을 그대로 돌려줍니다.
+///
+/// # 예시
+///
+/// ```
+/// static REGISTRATIONS: LazyLock<IntCounter> = LazyLock::new(|| {
+///     registry::register(IntCounter::new("user_registrations_total", "Total number of registered users").unwrap())
+///         .expect("Failed to register user_registrations_total")
+/// });
+/// ```
+pub fn register<C>(collector: C) -> Result<C, prometheus::Error>
+where
+    C: Collector + Clone + 'static,
+{
+    global().registry.register(Box::new(collector.clone()))?;
+    Ok(collector)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::IntCounter;
+
+    #[test]
+    fn test_register_business_metric() {
+        let counter = register(IntCounter::new("test_business_total", "test counter").unwrap()).unwrap();
+        counter.inc();
+
+        let encoded = global().encode().unwrap();
+        assert!(encoded.contains("test_business_total 1"));
+
+        let duplicated = register(IntCounter::new("test_business_total", "test counter").unwrap());
+        assert!(duplicated.is_err());
+    }
+}
@@ -3,4 +3,6 @@ pub mod database;
 pub mod mq;
 pub mod trace;
 
-pub mod http;
\ No newline at end of file
+pub mod http;
+pub mod metrics;
+pub mod health;
\ No newline at end of file
@@ -1,4 +1,5 @@
 use std::env;
+use async_nats::{Client, ConnectError, ConnectOptions};
 use dotenv::{from_filename};
 
 pub struct NatsConfig {
@@ -90,4 +91,31 @@ impl NatsConfig {
         self.domain = Some(domain.into());
         self
     }
+
+    /// 설정값으로 NATS 서버에 연결합니다.
+    pub async fn connect(&self) -> Result<Client, ConnectError> {
+        let mut options = ConnectOptions::new().max_reconnects(self.max_reconnects);
+
+        if let Some(name) = &self.connection_name {
+            options = options.name(name);
+        }
+
+        if let Some(token) = &self.auth_token {
+            options = options.token(token.clone());
+        }
+
+        if let (Some(username), Some(password)) = (&self.username, &self.password) {
+            options = options.user_and_password(username.clone(), password.clone());
+        }
+
+        if let Some(timeout) = self.connect_timeout {
+            options = options.connection_timeout(timeout);
+        }
+
+        if let Some(wait) = self.reconnect_wait {
+            options = options.reconnect_delay_callback(move |_| wait);
+        }
+
+        options.connect(self.url.as_str()).await
+    }
 }
\ No newline at end of file
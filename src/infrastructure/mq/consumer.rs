@@ -0,0 +1,66 @@
+use std::future::Future;
+use async_nats::{Client, Message, SubscribeError, Subscriber};
+use core_module::Consumer;
+use fastrace::prelude::*;
+use futures::StreamExt;
+use crate::infrastructure::metrics::registry;
+use crate::infrastructure::trace::propagation::TraceContext;
+
+/// 구독한 메시지를 하나씩 `handler` 로 전달합니다.
+///
+/// 메시지 헤더에 `traceparent` 가 있으면 발행 측 트레이스를 이어받는 스팬 안에서
+/// `handler` 가 실행되므로, gateway → service → consumer 가 하나의 트레이스로 묶입니다.
+pub async fn consume<F, Fut>(mut subscriber: Subscriber, handler: F)
+where
+    F: Fn(Message) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    while let Some(message) = subscriber.next().await {
+        let trace_context = message
+            .headers
+            .as_ref()
+            .map(TraceContext::extract)
+            .unwrap_or_default();
+        let span = trace_context.root_span(format!("consume {}", message.subject));
+
+        registry::global()
+            .nats
+            .consumed_total
+            .with_label_values(&[message.subject.as_str()])
+            .inc();
+
+        trace_context
+            .scope(handler(message))
+            .in_span(span)
+            .await;
+    }
+}
+
+
+/// 모듈이 선언한 구독을 모두 시작합니다.
+///
+/// 각 구독은 별도 태스크에서 [`consume`] 으로 처리되며, NATS 연결이 drain 되면 함께 종료됩니다.
+pub async fn spawn_module_consumers(client: &Client, consumers: &[Consumer]) -> Result<(), SubscribeError> {
+    for consumer in consumers {
+        let subject = consumer.subject().to_string();
+        let subscriber = match consumer.queue_group() {
+            Some(queue_group) => client.queue_subscribe(subject, queue_group.to_string()).await?,
+            None => client.subscribe(subject).await?,
+        };
+
+        let consumer = consumer.clone();
+        ntex::rt::spawn(consume(subscriber, move |message| {
+            let module = consumer.module();
+            let subject = message.subject.to_string();
+            let handled = consumer.handle(message);
+
+            async move {
+                if let Err(e) = handled.await {
+                    eprintln!("[consumer] {} failed to handle {}: {}", module, subject, e);
+                }
+            }
+        }));
+    }
+
+    Ok(())
+}
\ No newline at end of file
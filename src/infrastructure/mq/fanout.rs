@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use async_nats::{Client, SubscribeError, Subscriber};
+use futures::{StreamExt, stream};
+use ntex::http::header::CACHE_CONTROL;
+use ntex::service::{fn_factory_with_config, fn_service};
+use ntex::util::{Bytes, Either};
+use ntex::web::{self, HttpRequest, HttpResponse};
+use ntex::ws::error::HandshakeError;
+use ntex::ws::{Frame, Message, WsSink};
+use serde_json::{Value, json};
+use thiserror::Error;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+// 프록시의 유휴 타임아웃(kit-gateway 기본 60초)보다 짧게 보내 연결이 끊기지 않게 한다.
+const HEARTBEAT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Error)]
+pub enum FanoutError {
+    #[error("failed to subscribe: {0}")]
+    Subscribe(#[from] SubscribeError),
+
+    #[error("websocket handshake failed: {0}")]
+    Handshake(#[from] HandshakeError),
+}
+
+/// NATS 에서 받은 메시지 하나
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub subject: String,
+    pub payload: bytes::Bytes,
+}
+
+/// NATS subject 로 들어온 메시지를 이 인스턴스에 연결된 SSE/WebSocket 클라이언트들에게 나눠 보냅니다.
+///
+/// subject 마다 NATS 구독은 하나만 열고, 마지막 클라이언트가 떠난 뒤 다음 메시지가 오면 구독을 해제합니다.
+/// 클라이언트마다 `capacity` 개까지 밀린 메시지를 담아 두며, 그보다 느린 클라이언트는 밀린 메시지를 건너뜁니다.
+///
+/// # 예시
+///
+/// ```ignore
+/// async fn events(fanout: Inject<Fanout>) -> Result<HttpResponse, Exception> {
+///     Ok(fanout.sse("user.registered").await?)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Fanout {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    client: Client,
+    capacity: usize,
+    topics: Mutex<HashMap<String, broadcast::Sender<Event>>>,
+}
+
+impl Fanout {
+    pub fn new(client: Client) -> Self {
+        Self::with_capacity(client, 256)
+    }
+
+    pub fn with_capacity(client: Client, capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                client,
+                capacity: capacity.max(1),
+                topics: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// `subject` 의 메시지를 받습니다. 와일드카드(`user.*`, `user.>`)도 쓸 수 있습니다.
+    pub async fn subscribe(&self, subject: &str) -> Result<broadcast::Receiver<Event>, SubscribeError> {
+        if let Some(sender) = self.inner.topics.lock().unwrap().get(subject) {
+            return Ok(sender.subscribe());
+        }
+
+        let subscriber = self.inner.client.subscribe(subject.to_string()).await?;
+
+        // 구독을 여는 동안 다른 요청이 먼저 열었으면 그쪽을 쓰고 방금 연 구독은 버린다.
+        let mut topics = self.inner.topics.lock().unwrap();
+        if let Some(sender) = topics.get(subject) {
+            return Ok(sender.subscribe());
+        }
+
+        let (sender, receiver) = broadcast::channel(self.inner.capacity);
+        topics.insert(subject.to_string(), sender.clone());
+        ntex::rt::spawn(relay(self.inner.clone(), subject.to_string(), subscriber, sender));
+
+        Ok(receiver)
+    }
+
+    /// `subject` 의 메시지를 `text/event-stream` 으로 보냅니다. 이벤트 이름은 메시지의 subject 입니다.
+    pub async fn sse(&self, subject: &str) -> Result<HttpResponse, FanoutError> {
+        let receiver = self.subscribe(subject).await?;
+
+        let events = stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match ntex::time::timeout(HEARTBEAT, receiver.recv()).await {
+                    Ok(Ok(event)) => return Some((Ok::<_, Infallible>(sse_frame(&event)), receiver)),
+                    Ok(Err(RecvError::Lagged(_))) => continue,
+                    Ok(Err(RecvError::Closed)) => return None,
+                    Err(()) => return Some((Ok(Bytes::from_static(b": keep-alive\n\n")), receiver)),
+                }
+            }
+        });
+
+        Ok(HttpResponse::Ok()
+            .content_type("text/event-stream")
+            .header(CACHE_CONTROL, "no-cache")
+            .streaming(Box::pin(events)))
+    }
+
+    /// `subject` 의 메시지를 `{"subject": .., "data": ..}` 텍스트 프레임으로 보냅니다.
+    ///
+    /// 클라이언트가 보내는 메시지는 무시하고, 연결 확인을 위해 주기적으로 ping 을 보냅니다.
+    pub async fn websocket(&self, req: HttpRequest, subject: &str) -> Result<HttpResponse, FanoutError> {
+        // 핸드셰이크가 잘못된 요청으로 구독을 열지 않도록 먼저 확인한다.
+        ntex::ws::handshake(req.head())?;
+        let receiver = self.subscribe(subject).await?;
+
+        web::ws::start::<_, _, FanoutError>(
+            req,
+            fn_factory_with_config(move |sink: WsSink| {
+                ntex::rt::spawn(push(receiver.resubscribe(), sink));
+
+                async {
+                    Ok::<_, FanoutError>(fn_service(|frame: Frame| async move {
+                        Ok::<_, FanoutError>(match frame {
+                            Frame::Ping(data) => Some(Message::Pong(data)),
+                            Frame::Close(reason) => Some(Message::Close(reason)),
+                            _ => None,
+                        })
+                    }))
+                }
+            }),
+        )
+        .await
+    }
+}
+
+// subject 하나의 NATS 구독을 받아 채널로 나눈다. 받는 쪽이 모두 떠났으면 구독을 정리한다.
+async fn relay(inner: Arc<Inner>, subject: String, mut subscriber: Subscriber, sender: broadcast::Sender<Event>) {
+    while let Some(message) = subscriber.next().await {
+        let event = Event {
+            subject: message.subject.to_string(),
+            payload: message.payload,
+        };
+
+        // 새 클라이언트는 같은 잠금 안에서 채널에 붙으므로, 여기서 0 이면 더 붙을 클라이언트가 없다.
+        if sender.send(event).is_err() && sender.receiver_count() == 0 {
+            let mut topics = inner.topics.lock().unwrap();
+            if sender.receiver_count() == 0 {
+                topics.remove(&subject);
+                return;
+            }
+        }
+    }
+
+    // 연결이 닫혀 구독이 끝났다. 다음 요청이 새로 구독하도록 자리를 비운다.
+    let mut topics = inner.topics.lock().unwrap();
+    if topics.get(&subject).is_some_and(|current| current.same_channel(&sender)) {
+        topics.remove(&subject);
+    }
+}
+
+async fn push(mut receiver: broadcast::Receiver<Event>, sink: WsSink) {
+    let mut disconnected = sink.on_disconnect();
+
+    loop {
+        let message = match ntex::util::select(&mut disconnected, ntex::time::timeout(HEARTBEAT, receiver.recv())).await {
+            Either::Left(()) => return,
+            Either::Right(Ok(Ok(event))) => Message::Text(ws_frame(&event).into()),
+            Either::Right(Ok(Err(RecvError::Lagged(_)))) => continue,
+            Either::Right(Ok(Err(RecvError::Closed))) => Message::Close(None),
+            Either::Right(Err(())) => Message::Ping(Bytes::new()),
+        };
+
+        let closing = matches!(message, Message::Close(_));
+        if sink.send(message).await.is_err() || closing {
+            return sink.io().close();
+        }
+    }
+}
+
+// 여러 줄짜리 본문도 한 이벤트로 전달되도록 줄마다 `data:` 를 붙인다.
+fn sse_frame(event: &Event) -> Bytes {
+    let mut frame = format!("event: {}\n", event.subject);
+    for line in String::from_utf8_lossy(&event.payload).lines() {
+        frame.push_str("data: ");
+        frame.push_str(line);
+        frame.push('\n');
+    }
+    frame.push('\n');
+
+    Bytes::from(frame)
+}
+
+// JSON 본문은 그대로 넣고, 아니면 문자열로 넣는다.
+fn ws_frame(event: &Event) -> String {
+    let data = serde_json::from_slice::<Value>(&event.payload)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&event.payload).into_owned()));
+
+    json!({ "subject": event.subject, "data": data }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames() {
+        let event = Event {
+            subject: "user.registered".to_string(),
+            payload: bytes::Bytes::from_static(b"{\"id\":1}"),
+        };
+        let multiline = Event {
+            subject: "notice".to_string(),
+            payload: bytes::Bytes::from_static(b"first\nsecond"),
+        };
+
+        assert_eq!(sse_frame(&event), "event: user.registered\ndata: {\"id\":1}\n\n");
+        assert_eq!(sse_frame(&multiline), "event: notice\ndata: first\ndata: second\n\n");
+        assert_eq!(ws_frame(&event), r#"{"data":{"id":1},"subject":"user.registered"}"#);
+        assert_eq!(ws_frame(&multiline), r#"{"data":"first\nsecond","subject":"notice"}"#);
+    }
+}
@@ -1,2 +1,5 @@
 
-pub mod config;
\ No newline at end of file
+pub mod config;
+pub mod fanout;
+pub mod publisher;
+pub mod consumer;
\ No newline at end of file
@@ -0,0 +1,28 @@
+use async_nats::{Client, HeaderMap, PublishError, subject::ToSubject};
+use bytes::Bytes;
+use crate::infrastructure::metrics::registry;
+use crate::infrastructure::trace::propagation;
+
+/// 현재 트레이스 컨텍스트를 NATS 헤더에 담아 메시지를 발행합니다.
+///
+/// 소비자 측에서는 [`crate::infrastructure::mq::consumer::consume`] 로 같은 트레이스를 이어받을 수 있습니다.
+pub async fn publish(
+    client: &Client,
+    subject: impl ToSubject,
+    payload: Bytes,
+) -> Result<(), PublishError> {
+    let subject = subject.to_subject();
+
+    let mut headers = HeaderMap::new();
+    propagation::inject(&mut headers);
+
+    let result = client.publish_with_headers(subject.clone(), headers, payload).await;
+
+    registry::global()
+        .nats
+        .published_total
+        .with_label_values(&[subject.as_str(), if result.is_ok() { "ok" } else { "error" }])
+        .inc();
+
+    result
+}
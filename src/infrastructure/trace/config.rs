@@ -0,0 +1,96 @@
+use std::env;
+use std::str::FromStr;
+use std::time::Duration;
+use dotenv::from_filename;
+
+/// 스팬을 내보낼 대상
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReporterKind {
+    Console,
+    OtlpGrpc,
+    OtlpHttp,
+    Jaeger,
+}
+
+impl ReporterKind {
+    /// 엔드포인트가 지정되지 않았을 때 사용하는 로컬 수집기 주소
+    pub fn default_endpoint(&self) -> Option<&'static str> {
+        match self {
+            ReporterKind::Console => None,
+            ReporterKind::OtlpGrpc => Some("http://localhost:4317"),
+            ReporterKind::OtlpHttp => Some("http://localhost:4318/v1/traces"),
+            ReporterKind::Jaeger => Some("http://localhost:14268/api/traces"),
+        }
+    }
+}
+
+impl FromStr for ReporterKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "console" => Ok(ReporterKind::Console),
+            "otlp" | "otlp-grpc" | "grpc" => Ok(ReporterKind::OtlpGrpc),
+            "otlp-http" | "http" => Ok(ReporterKind::OtlpHttp),
+            "jaeger" => Ok(ReporterKind::Jaeger),
+            other => Err(format!("Unknown trace reporter: {}", other)),
+        }
+    }
+}
+
+pub struct TraceConfig {
+    pub enabled: bool,
+    pub reporter: ReporterKind,
+    pub endpoint: Option<String>,
+    pub service_name: String,
+    pub sample_ratio: f64,
+    pub report_interval: Duration,
+    pub max_spans_per_trace: Option<usize>,
+}
+
+impl TraceConfig {
+    /// 환경 변수로 설정을 읽습니다. `TRACE_REPORTER` 가 알 수 없는 값이면 콘솔로 바꾸지 않고 에러를 돌려줍니다.
+    pub fn from_env() -> Result<Self, String> {
+        from_filename(".development.env").ok();
+
+        let reporter = match env::var("TRACE_REPORTER") {
+            Ok(value) => value.parse()?,
+            Err(_) => ReporterKind::Console,
+        };
+
+        Ok(Self {
+            enabled: env::var("TRACE_ENABLED").map(|value| value != "false").unwrap_or(true),
+            reporter,
+            endpoint: env::var("TRACE_ENDPOINT").ok(),
+            service_name: env::var("TRACE_SERVICE_NAME").unwrap_or_else(|_| "kit-server".to_string()),
+            sample_ratio: env::var("TRACE_SAMPLE_RATIO")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(1.0),
+            report_interval: env::var("TRACE_REPORT_INTERVAL_MS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(Duration::from_secs(1)),
+            max_spans_per_trace: Some(1024),
+        })
+    }
+
+    /// 설정된 엔드포인트, 없으면 리포터별 기본 엔드포인트
+    pub fn endpoint(&self) -> Option<String> {
+        self.endpoint
+            .clone()
+            .or_else(|| self.reporter.default_endpoint().map(str::to_string))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reporter_kind() {
+        assert_eq!("OTLP-HTTP".parse::<ReporterKind>(), Ok(ReporterKind::OtlpHttp));
+        assert_eq!("otpl".parse::<ReporterKind>(), Err("Unknown trace reporter: otpl".to_string()));
+    }
+}
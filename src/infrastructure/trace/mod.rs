@@ -1 +1,5 @@
-pub mod tracer;
\ No newline at end of file
+pub mod tracer;
+pub mod propagation;
+pub mod config;
+pub mod sampler;
+pub mod reporter;
\ No newline at end of file
@@ -0,0 +1,169 @@
+use std::borrow::Cow;
+use std::future::Future;
+use fastrace::prelude::*;
+use crate::infrastructure::trace::sampler;
+
+pub const TRACEPARENT: &str = "traceparent";
+pub const TRACESTATE: &str = "tracestate";
+
+tokio::task_local! {
+    static CURRENT_TRACESTATE: Option<String>;
+}
+
+/// W3C Trace Context 헤더를 읽고 쓸 수 있는 전송 매체(HTTP 헤더, NATS 헤더 등)
+pub trait Carrier {
+    fn get(&self, key: &str) -> Option<&str>;
+    fn set(&mut self, key: &'static str, value: String);
+}
+
+impl Carrier for ntex::http::HeaderMap {
+    fn get(&self, key: &str) -> Option<&str> {
+        ntex::http::HeaderMap::get(self, key).and_then(|value| value.to_str().ok())
+    }
+
+    fn set(&mut self, key: &'static str, value: String) {
+        if let Ok(value) = ntex::http::header::HeaderValue::from_str(&value) {
+            self.insert(ntex::http::header::HeaderName::from_static(key), value);
+        }
+    }
+}
+
+impl Carrier for reqwest::header::HeaderMap {
+    fn get(&self, key: &str) -> Option<&str> {
+        reqwest::header::HeaderMap::get(self, key).and_then(|value| value.to_str().ok())
+    }
+
+    fn set(&mut self, key: &'static str, value: String) {
+        if let Ok(value) = reqwest::header::HeaderValue::from_str(&value) {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl Carrier for async_nats::HeaderMap {
+    fn get(&self, key: &str) -> Option<&str> {
+        async_nats::HeaderMap::get(self, key).map(|value| value.as_str())
+    }
+
+    fn set(&mut self, key: &'static str, value: String) {
+        self.insert(key, value);
+    }
+}
+
+/// 수신한 요청/메시지에서 추출한 W3C Trace Context
+#[derive(Debug, Clone, Default)]
+pub struct TraceContext {
+    pub parent: Option<SpanContext>,
+    pub tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// `traceparent`/`tracestate` 헤더를 추출합니다.
+    ///
+    /// `traceparent` 가 없거나 형식이 잘못된 경우 `parent` 는 `None` 이 되며,
+    /// 이 때 `tracestate` 도 W3C 명세에 따라 무시됩니다.
+    pub fn extract<C: Carrier + ?Sized>(carrier: &C) -> Self {
+        let parent = carrier
+            .get(TRACEPARENT)
+            .and_then(|value| SpanContext::decode_w3c_traceparent(value.trim()));
+
+        let tracestate = parent
+            .and(carrier.get(TRACESTATE))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string);
+
+        Self { parent, tracestate }
+    }
+
+    /// 추출한 부모 컨텍스트를 이어받는 루트 스팬을 생성합니다.
+    /// 부모가 없으면 전역 샘플러의 결정에 따라 새로운 트레이스를 시작합니다.
+    pub fn root_span(&self, name: impl Into<Cow<'static, str>>) -> Span {
+        Span::root(name, sampler::global().root_context(self.parent))
+    }
+
+    /// `tracestate` 를 현재 태스크에 묶어 `future` 를 실행합니다.
+    /// 이 범위 안에서 호출된 [`inject`] 는 같은 `tracestate` 를 전달합니다.
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT_TRACESTATE.scope(self.tracestate, future).await
+    }
+}
+
+/// 현재 스팬 컨텍스트를 `traceparent`/`tracestate` 헤더로 주입합니다.
+///
+/// 활성화된 로컬 스팬이 없으면 아무 것도 하지 않습니다.
+pub fn inject<C: Carrier + ?Sized>(carrier: &mut C) {
+    let Some(span_context) = SpanContext::current_local_parent() else {
+        return;
+    };
+
+    carrier.set(TRACEPARENT, span_context.encode_w3c_traceparent());
+
+    let tracestate = CURRENT_TRACESTATE
+        .try_with(|tracestate| tracestate.clone())
+        .ok()
+        .flatten();
+
+    if let Some(tracestate) = tracestate {
+        carrier.set(TRACESTATE, tracestate);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fastrace::collector::{Config, ConsoleReporter};
+
+    const PARENT: &str = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+
+    #[test]
+    fn test_extract_traceparent_and_tracestate() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.set(TRACEPARENT, PARENT.to_string());
+        headers.set(TRACESTATE, "congo=t61rcWkgMzE".to_string());
+
+        let context = TraceContext::extract(&headers);
+        let parent = context.parent.unwrap();
+
+        assert_eq!(parent.trace_id, TraceId(0x0af7651916cd43dd8448eb211c80319c));
+        assert_eq!(parent.span_id, SpanId(0xb7ad6b7169203331));
+        assert_eq!(context.tracestate.as_deref(), Some("congo=t61rcWkgMzE"));
+    }
+
+    #[test]
+    fn test_extract_ignores_tracestate_without_traceparent() {
+        let mut headers = async_nats::HeaderMap::new();
+        headers.set(TRACEPARENT, "not-a-traceparent".to_string());
+        headers.set(TRACESTATE, "congo=t61rcWkgMzE".to_string());
+
+        let context = TraceContext::extract(&headers);
+
+        assert!(context.parent.is_none());
+        assert!(context.tracestate.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_inject_within_scope() {
+        fastrace::set_reporter(ConsoleReporter, Config::default());
+
+        let mut headers = ntex::http::HeaderMap::new();
+        headers.set(TRACEPARENT, PARENT.to_string());
+        headers.set(TRACESTATE, "congo=t61rcWkgMzE".to_string());
+
+        let context = TraceContext::extract(&headers);
+        let root = context.root_span("test");
+
+        let injected = context
+            .scope(async {
+                let mut outbound = async_nats::HeaderMap::new();
+                inject(&mut outbound);
+                outbound
+            })
+            .in_span(root)
+            .await;
+
+        let traceparent = Carrier::get(&injected, TRACEPARENT).unwrap();
+        assert!(traceparent.starts_with("00-0af7651916cd43dd8448eb211c80319c-"));
+        assert_eq!(Carrier::get(&injected, TRACESTATE), Some("congo=t61rcWkgMzE"));
+    }
+}
@@ -0,0 +1,194 @@
+use std::time::Duration;
+use fastrace::collector::{Reporter, SpanRecord};
+use reqwest::blocking::Client;
+use reqwest::header::CONTENT_TYPE;
+use crate::infrastructure::trace::reporter::otlp::is_error;
+
+// Thrift binary protocol 타입 코드
+const T_STOP: u8 = 0;
+const T_I32: u8 = 8;
+const T_I64: u8 = 10;
+const T_STRING: u8 = 11;
+const T_STRUCT: u8 = 12;
+const T_LIST: u8 = 15;
+
+// jaeger.thrift TagType.STRING
+const TAG_TYPE_STRING: i32 = 0;
+const FLAG_SAMPLED: i32 = 1;
+
+/// Jaeger collector 의 HTTP thrift 엔드포인트(`/api/traces`)로 스팬을 내보내는 리포터
+pub struct JaegerReporter {
+    endpoint: String,
+    service_name: String,
+    client: Option<Client>,
+}
+
+impl JaegerReporter {
+    pub fn new(endpoint: impl Into<String>, service_name: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            service_name: service_name.into(),
+            client: None,
+        }
+    }
+
+    fn export(&mut self, body: Vec<u8>) -> Result<(), String> {
+        if self.client.is_none() {
+            let client = Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .map_err(|e| e.to_string())?;
+            self.client = Some(client);
+        }
+
+        let client = self.client.as_ref().ok_or_else(|| "Jaeger client is not initialized".to_string())?;
+
+        let response = client
+            .post(&self.endpoint)
+            .header(CONTENT_TYPE, "application/x-thrift")
+            .body(body)
+            .send()
+            .map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("collector responded with {}", response.status()));
+        }
+
+        Ok(())
+    }
+}
+
+impl Reporter for JaegerReporter {
+    fn report(&mut self, spans: Vec<SpanRecord>) {
+        if spans.is_empty() {
+            return;
+        }
+
+        let body = encode_batch(&self.service_name, &spans);
+
+        if let Err(e) = self.export(body) {
+            eprintln!("[trace] failed to export {} spans to Jaeger: {}", spans.len(), e);
+        }
+    }
+}
+
+/// jaeger.thrift 의 `Batch` 를 thrift binary protocol 로 인코딩합니다.
+pub fn encode_batch(service_name: &str, spans: &[SpanRecord]) -> Vec<u8> {
+    let mut writer = ThriftWriter::default();
+
+    // Batch.process
+    writer.field(T_STRUCT, 1);
+    writer.field(T_STRING, 1);
+    writer.string(service_name);
+    writer.stop();
+
+    // Batch.spans
+    writer.field(T_LIST, 2);
+    writer.list(T_STRUCT, spans.len());
+    for span in spans {
+        encode_span(&mut writer, span);
+    }
+
+    writer.stop();
+    writer.into_inner()
+}
+
+fn encode_span(writer: &mut ThriftWriter, span: &SpanRecord) {
+    writer.field(T_I64, 1);
+    writer.i64(span.trace_id.0 as u64 as i64);
+    writer.field(T_I64, 2);
+    writer.i64((span.trace_id.0 >> 64) as u64 as i64);
+    writer.field(T_I64, 3);
+    writer.i64(span.span_id.0 as i64);
+    writer.field(T_I64, 4);
+    writer.i64(span.parent_id.0 as i64);
+    writer.field(T_STRING, 5);
+    writer.string(&span.name);
+    writer.field(T_I32, 7);
+    writer.i32(FLAG_SAMPLED);
+    writer.field(T_I64, 8);
+    writer.i64((span.begin_time_unix_ns / 1_000) as i64);
+    writer.field(T_I64, 9);
+    writer.i64((span.duration_ns / 1_000) as i64);
+
+    let mut tags: Vec<(&str, &str)> = span
+        .properties
+        .iter()
+        .map(|(key, value)| (key.as_ref(), value.as_ref()))
+        .collect();
+    if is_error(span) {
+        tags.push(("otel.status_code", "ERROR"));
+    }
+
+    writer.field(T_LIST, 10);
+    writer.list(T_STRUCT, tags.len());
+    for (key, value) in tags {
+        tag(writer, key, value);
+    }
+
+    if !span.events.is_empty() {
+        writer.field(T_LIST, 11);
+        writer.list(T_STRUCT, span.events.len());
+        for event in &span.events {
+            writer.field(T_I64, 1);
+            writer.i64((event.timestamp_unix_ns / 1_000) as i64);
+            writer.field(T_LIST, 2);
+            writer.list(T_STRUCT, event.properties.len() + 1);
+            tag(writer, "event", &event.name);
+            for (key, value) in &event.properties {
+                tag(writer, key, value);
+            }
+            writer.stop();
+        }
+    }
+
+    writer.stop();
+}
+
+fn tag(writer: &mut ThriftWriter, key: &str, value: &str) {
+    writer.field(T_STRING, 1);
+    writer.string(key);
+    writer.field(T_I32, 2);
+    writer.i32(TAG_TYPE_STRING);
+    writer.field(T_STRING, 3);
+    writer.string(value);
+    writer.stop();
+}
+
+#[derive(Default)]
+struct ThriftWriter {
+    buf: Vec<u8>,
+}
+
+impl ThriftWriter {
+    fn into_inner(self) -> Vec<u8> {
+        self.buf
+    }
+
+    fn field(&mut self, field_type: u8, id: i16) {
+        self.buf.push(field_type);
+        self.buf.extend_from_slice(&id.to_be_bytes());
+    }
+
+    fn stop(&mut self) {
+        self.buf.push(T_STOP);
+    }
+
+    fn list(&mut self, element_type: u8, size: usize) {
+        self.buf.push(element_type);
+        self.i32(size as i32);
+    }
+
+    fn i32(&mut self, value: i32) {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn i64(&mut self, value: i64) {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn string(&mut self, value: &str) {
+        self.i32(value.len() as i32);
+        self.buf.extend_from_slice(value.as_bytes());
+    }
+}
@@ -0,0 +1,42 @@
+pub mod otlp;
+pub mod jaeger;
+
+use fastrace::collector::{Config, ConsoleReporter};
+use crate::infrastructure::trace::config::{ReporterKind, TraceConfig};
+use crate::infrastructure::trace::reporter::jaeger::JaegerReporter;
+use crate::infrastructure::trace::reporter::otlp::{OtlpProtocol, OtlpReporter};
+use crate::infrastructure::trace::sampler::{self, Sampler};
+
+/// 설정에 맞는 리포터와 샘플러를 전역으로 등록합니다.
+///
+/// 스팬은 요청마다 바로 보내지 않고 `report_interval` 단위로 모아서 전송합니다.
+/// 비활성화된 경우 리포터를 등록하지 않으므로 스팬이 수집되지 않습니다.
+pub fn install(config: &TraceConfig) {
+    if !config.enabled {
+        return;
+    }
+
+    sampler::init(Sampler::new(config.sample_ratio));
+
+    let collector_config = Config::default()
+        .report_interval(config.report_interval)
+        .max_spans_per_trace(config.max_spans_per_trace);
+
+    let endpoint = config.endpoint().unwrap_or_default();
+
+    match config.reporter {
+        ReporterKind::Console => fastrace::set_reporter(ConsoleReporter, collector_config),
+        ReporterKind::OtlpGrpc => fastrace::set_reporter(
+            OtlpReporter::new(endpoint, OtlpProtocol::Grpc, &config.service_name),
+            collector_config,
+        ),
+        ReporterKind::OtlpHttp => fastrace::set_reporter(
+            OtlpReporter::new(endpoint, OtlpProtocol::Http, &config.service_name),
+            collector_config,
+        ),
+        ReporterKind::Jaeger => fastrace::set_reporter(
+            JaegerReporter::new(endpoint, &config.service_name),
+            collector_config,
+        ),
+    }
+}
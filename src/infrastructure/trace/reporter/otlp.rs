@@ -0,0 +1,304 @@
+use std::time::Duration;
+use fastrace::collector::{Reporter, SpanRecord};
+use reqwest::blocking::Client;
+use reqwest::header::CONTENT_TYPE;
+
+const GRPC_EXPORT_PATH: &str = "/opentelemetry.proto.collector.trace.v1.TraceService/Export";
+
+// opentelemetry.proto.trace.v1.Span.SpanKind
+const SPAN_KIND_INTERNAL: u64 = 1;
+const SPAN_KIND_SERVER: u64 = 2;
+const SPAN_KIND_CLIENT: u64 = 3;
+const SPAN_KIND_PRODUCER: u64 = 4;
+const SPAN_KIND_CONSUMER: u64 = 5;
+
+// opentelemetry.proto.trace.v1.Status.StatusCode
+const STATUS_CODE_ERROR: u64 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OtlpProtocol {
+    Grpc,
+    Http,
+}
+
+/// OTLP(protobuf) 로 수집기(otel-collector, Jaeger 등)에 스팬을 내보내는 리포터
+///
+/// fastrace 의 수집 스레드에서 `report_interval` 마다 모인 스팬을 한 번에 전송합니다.
+pub struct OtlpReporter {
+    endpoint: String,
+    protocol: OtlpProtocol,
+    service_name: String,
+    client: Option<Client>,
+}
+
+impl OtlpReporter {
+    pub fn new(endpoint: impl Into<String>, protocol: OtlpProtocol, service_name: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            protocol,
+            service_name: service_name.into(),
+            client: None,
+        }
+    }
+
+    // blocking 클라이언트는 async 런타임 안에서 생성/해제할 수 없으므로 수집 스레드에서 처음 사용할 때 만든다.
+    fn client(&mut self) -> Result<&Client, String> {
+        if self.client.is_none() {
+            let mut builder = Client::builder().timeout(Duration::from_secs(10));
+
+            if self.protocol == OtlpProtocol::Grpc {
+                builder = builder.http2_prior_knowledge();
+            }
+
+            self.client = Some(builder.build().map_err(|e| e.to_string())?);
+        }
+
+        self.client.as_ref().ok_or_else(|| "OTLP client is not initialized".to_string())
+    }
+
+    fn export(&mut self, body: Vec<u8>) -> Result<(), String> {
+        let protocol = self.protocol;
+        let endpoint = self.endpoint.clone();
+        let client = self.client()?;
+
+        let response = match protocol {
+            OtlpProtocol::Http => client
+                .post(&endpoint)
+                .header(CONTENT_TYPE, "application/x-protobuf")
+                .body(body)
+                .send(),
+            OtlpProtocol::Grpc => {
+                // gRPC 메시지 프레임: 압축 플래그(1 byte) + 길이(4 bytes, big endian) + 본문
+                let mut framed = Vec::with_capacity(body.len() + 5);
+                framed.push(0);
+                framed.extend_from_slice(&(body.len() as u32).to_be_bytes());
+                framed.extend_from_slice(&body);
+
+                client
+                    .post(format!("{}{}", endpoint.trim_end_matches('/'), GRPC_EXPORT_PATH))
+                    .header(CONTENT_TYPE, "application/grpc")
+                    .header("te", "trailers")
+                    .body(framed)
+                    .send()
+            }
+        }
+        .map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("collector responded with {}", response.status()));
+        }
+
+        // 즉시 실패한 gRPC 호출은 trailers 대신 헤더에 grpc-status 를 담아 보낸다.
+        if let Some(status) = response.headers().get("grpc-status")
+            && status != "0"
+        {
+            let message = response
+                .headers()
+                .get("grpc-message")
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default();
+            return Err(format!("grpc-status {:?}: {}", status, message));
+        }
+
+        Ok(())
+    }
+}
+
+impl Reporter for OtlpReporter {
+    fn report(&mut self, spans: Vec<SpanRecord>) {
+        if spans.is_empty() {
+            return;
+        }
+
+        let body = encode_export_request(&self.service_name, &spans);
+
+        if let Err(e) = self.export(body) {
+            eprintln!("[trace] failed to export {} spans via OTLP: {}", spans.len(), e);
+        }
+    }
+}
+
+/// `ExportTraceServiceRequest` 를 protobuf 로 인코딩합니다.
+pub fn encode_export_request(service_name: &str, spans: &[SpanRecord]) -> Vec<u8> {
+    let mut request = ProtoWriter::default();
+
+    // ExportTraceServiceRequest.resource_spans
+    request.message(1, |resource_spans| {
+        // ResourceSpans.resource
+        resource_spans.message(1, |resource| {
+            key_value(resource, 1, "service.name", service_name);
+        });
+
+        // ResourceSpans.scope_spans
+        resource_spans.message(2, |scope_spans| {
+            scope_spans.message(1, |scope| {
+                scope.string(1, env!("CARGO_PKG_NAME"));
+                scope.string(2, env!("CARGO_PKG_VERSION"));
+            });
+
+            for span in spans {
+                scope_spans.message(2, |writer| encode_span(writer, span));
+            }
+        });
+    });
+
+    request.into_inner()
+}
+
+fn encode_span(writer: &mut ProtoWriter, span: &SpanRecord) {
+    writer.bytes(1, &span.trace_id.0.to_be_bytes());
+    writer.bytes(2, &span.span_id.0.to_be_bytes());
+
+    if span.parent_id.0 != 0 {
+        writer.bytes(4, &span.parent_id.0.to_be_bytes());
+    }
+
+    writer.string(5, &span.name);
+    writer.uint(6, span_kind(span));
+    writer.fixed64(7, span.begin_time_unix_ns);
+    writer.fixed64(8, span.begin_time_unix_ns + span.duration_ns);
+
+    for (key, value) in &span.properties {
+        key_value(writer, 9, key, value);
+    }
+
+    for event in &span.events {
+        writer.message(11, |writer| {
+            writer.fixed64(1, event.timestamp_unix_ns);
+            writer.string(2, &event.name);
+
+            for (key, value) in &event.properties {
+                key_value(writer, 3, key, value);
+            }
+        });
+    }
+
+    if is_error(span) {
+        writer.message(15, |status| status.uint(3, STATUS_CODE_ERROR));
+    }
+}
+
+fn span_kind(span: &SpanRecord) -> u64 {
+    let kind = span
+        .properties
+        .iter()
+        .find(|(key, _)| key == "span.kind")
+        .map(|(_, value)| value.as_ref());
+
+    match kind {
+        Some("server") => SPAN_KIND_SERVER,
+        Some("client") => SPAN_KIND_CLIENT,
+        Some("producer") => SPAN_KIND_PRODUCER,
+        Some("consumer") => SPAN_KIND_CONSUMER,
+        _ => SPAN_KIND_INTERNAL,
+    }
+}
+
+pub(super) fn is_error(span: &SpanRecord) -> bool {
+    span.properties
+        .iter()
+        .any(|(key, value)| key == "error" && value == "true")
+}
+
+fn key_value(writer: &mut ProtoWriter, field: u32, key: &str, value: &str) {
+    writer.message(field, |key_value| {
+        key_value.string(1, key);
+        // AnyValue.string_value 는 oneof 이므로 빈 문자열도 명시적으로 기록한다.
+        key_value.message(2, |any_value| any_value.bytes(1, value.as_bytes()));
+    });
+}
+
+/// OTLP 메시지에 필요한 만큼만 구현한 protobuf 인코더
+#[derive(Default)]
+struct ProtoWriter {
+    buf: Vec<u8>,
+}
+
+impl ProtoWriter {
+    fn into_inner(self) -> Vec<u8> {
+        self.buf
+    }
+
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buf.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.buf.push(value as u8);
+    }
+
+    fn key(&mut self, field: u32, wire_type: u8) {
+        self.varint(((field as u64) << 3) | wire_type as u64);
+    }
+
+    fn uint(&mut self, field: u32, value: u64) {
+        self.key(field, 0);
+        self.varint(value);
+    }
+
+    fn fixed64(&mut self, field: u32, value: u64) {
+        self.key(field, 1);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn bytes(&mut self, field: u32, value: &[u8]) {
+        self.key(field, 2);
+        self.varint(value.len() as u64);
+        self.buf.extend_from_slice(value);
+    }
+
+    fn string(&mut self, field: u32, value: &str) {
+        if !value.is_empty() {
+            self.bytes(field, value.as_bytes());
+        }
+    }
+
+    fn message(&mut self, field: u32, encode: impl FnOnce(&mut ProtoWriter)) {
+        let mut inner = ProtoWriter::default();
+        encode(&mut inner);
+        self.bytes(field, &inner.buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fastrace::prelude::*;
+
+    #[test]
+    fn test_varint_encoding() {
+        let mut writer = ProtoWriter::default();
+        writer.varint(300);
+        assert_eq!(writer.into_inner(), vec![0xac, 0x02]);
+    }
+
+    #[test]
+    fn test_encode_span_fields() {
+        let span = SpanRecord {
+            trace_id: TraceId(1),
+            span_id: SpanId(2),
+            parent_id: SpanId(0),
+            begin_time_unix_ns: 10,
+            duration_ns: 5,
+            name: "GET /user".into(),
+            properties: vec![("span.kind".into(), "server".into()), ("error".into(), "true".into())],
+            events: vec![],
+        };
+
+        let mut writer = ProtoWriter::default();
+        encode_span(&mut writer, &span);
+        let encoded = writer.into_inner();
+
+        // trace_id(field 1, 16 bytes)
+        assert_eq!(&encoded[..2], &[0x0a, 16]);
+        assert_eq!(encoded[17], 1);
+        // span_id(field 2, 8 bytes) 바로 뒤에 parent 없이 name(field 5)이 온다.
+        assert_eq!(&encoded[18..20], &[0x12, 8]);
+        assert_eq!(&encoded[28..30], &[0x2a, 9]);
+        assert_eq!(&encoded[30..39], b"GET /user");
+        // kind = SERVER
+        assert_eq!(&encoded[39..41], &[0x30, SPAN_KIND_SERVER as u8]);
+        // 마지막은 status { code = ERROR }
+        assert_eq!(&encoded[encoded.len() - 4..], &[0x7a, 2, 0x18, STATUS_CODE_ERROR as u8]);
+    }
+}
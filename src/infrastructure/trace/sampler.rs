@@ -0,0 +1,78 @@
+use std::sync::OnceLock;
+use fastrace::prelude::*;
+
+static GLOBAL_SAMPLER: OnceLock<Sampler> = OnceLock::new();
+
+/// 트레이스 ID 비율 기반 샘플러
+///
+/// 같은 트레이스 ID 는 항상 같은 결정을 내리므로, 여러 인스턴스가 독립적으로 판단해도
+/// 하나의 트레이스가 중간에 끊기지 않습니다.
+#[derive(Debug, Clone, Copy)]
+pub struct Sampler {
+    ratio: f64,
+}
+
+impl Sampler {
+    pub fn new(ratio: f64) -> Self {
+        Self {
+            ratio: if ratio.is_nan() { 1.0 } else { ratio.clamp(0.0, 1.0) },
+        }
+    }
+
+    pub fn should_sample(&self, trace_id: TraceId) -> bool {
+        if self.ratio >= 1.0 {
+            return true;
+        }
+
+        let threshold = (self.ratio * u64::MAX as f64) as u64;
+        (trace_id.0 as u64) < threshold
+    }
+
+    /// 새 트레이스를 시작할 컨텍스트를 만듭니다.
+    ///
+    /// 상위에서 전달된 부모가 있으면 부모의 샘플링 결정을 그대로 따릅니다.
+    pub fn root_context(&self, parent: Option<SpanContext>) -> SpanContext {
+        parent.unwrap_or_else(|| {
+            let context = SpanContext::random();
+            context.sampled(self.should_sample(context.trace_id))
+        })
+    }
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+/// 프로세스 전역 샘플러를 설정합니다. 최초 한 번만 적용됩니다.
+pub fn init(sampler: Sampler) {
+    let _ = GLOBAL_SAMPLER.set(sampler);
+}
+
+pub fn global() -> Sampler {
+    GLOBAL_SAMPLER.get().copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_ratio_bounds() {
+        let always = Sampler::new(1.0);
+        let never = Sampler::new(0.0);
+
+        assert!(always.should_sample(TraceId(u128::MAX)));
+        assert!(!never.should_sample(TraceId(0)));
+    }
+
+    #[test]
+    fn test_parent_decision_is_kept() {
+        let sampler = Sampler::new(0.0);
+        let parent = SpanContext::new(TraceId(1), SpanId(2)).sampled(true);
+
+        assert!(sampler.root_context(Some(parent)).sampled);
+        assert!(!sampler.root_context(None).sampled);
+    }
+}
@@ -1,6 +1,10 @@
+use std::sync::Arc;
 use ntex::service::{Middleware, Service, ServiceCtx};
 use ntex::web;
 use fastrace::prelude::*;
+use kit_context::RequestContext;
+use crate::infrastructure::http::route::route_pattern;
+use crate::infrastructure::trace::propagation::TraceContext;
 
 pub struct Tracer;
 
@@ -36,15 +40,72 @@ where
         
         let span_name = format!("{} {}", method, path);
 
-        let root = Span::root(span_name, SpanContext::random());
-        let guard = root.set_local_parent();
-        
-        let res = ctx.call(&self.service, req).await?;
-        
-        drop(guard);
-        
-        fastrace::flush();
+        // 상위 서비스(gateway 등)에서 전달된 traceparent 가 있으면 같은 트레이스로 이어 붙인다.
+        let trace_context = TraceContext::extract(req.headers());
+        let root = trace_context
+            .root_span(span_name)
+            .with_properties(|| request_properties(&req));
+
+        // 루트 스팬은 응답 정보를 기록해야 하므로, 핸들러는 자식 스팬 안에서 실행한다.
+        let handler = Span::enter_with_parent("handler", &root);
+
+        let res = trace_context
+            .scope(ctx.call(&self.service, req))
+            .in_span(handler)
+            .await;
+
+        match &res {
+            Ok(res) => root.add_properties(|| response_properties(res)),
+            Err(e) => root.add_properties(|| [
+                ("error", "true".to_string()),
+                ("error.message", e.to_string()),
+            ]),
+        }
+
+        res
+    }
+}
+
+fn request_properties<Err>(req: &web::WebRequest<Err>) -> Vec<(&'static str, String)> {
+    let mut properties = vec![
+        ("span.kind", "server".to_string()),
+        ("http.method", req.method().to_string()),
+        ("http.target", req.path().to_string()),
+    ];
+
+    // `X-Forwarded-For` 는 클라이언트가 꾸밀 수 있으므로 소켓 상대 주소를 남긴다.
+    if let Some(peer) = req.peer_addr() {
+        properties.push(("network.peer.address", peer.ip().to_string()));
+    }
 
-        Ok(res)
+    if let Some(user_agent) = req.headers().get("user-agent").and_then(|value| value.to_str().ok()) {
+        properties.push(("user_agent.original", user_agent.to_string()));
     }
-}
\ No newline at end of file
+
+    properties
+}
+
+fn response_properties(res: &web::WebResponse) -> Vec<(&'static str, String)> {
+    let status = res.status();
+    let mut properties = vec![("http.status_code", status.as_u16().to_string())];
+
+    if let Some(route) = route_pattern(res.request()) {
+        properties.push(("http.route", route));
+    }
+
+    // 요청 헤더의 신원은 서명을 확인하기 전이므로, `TrustedIdentity` 를 거쳐 컨텍스트에 남은 사용자만 기록한다.
+    let user_id = res
+        .request()
+        .extensions()
+        .get::<Arc<RequestContext>>()
+        .and_then(|context| context.principal.as_ref().map(|principal| principal.id.clone()));
+    if let Some(user_id) = user_id {
+        properties.push(("user.id", user_id));
+    }
+
+    if status.is_server_error() {
+        properties.push(("error", "true".to_string()));
+    }
+
+    properties
+}
@@ -1,65 +1,274 @@
+#![recursion_limit = "256"]
+
 mod modules;
 mod states;
 mod infrastructure;
 
 use std::env;
-use async_nats::ConnectOptions;
-use fastrace::collector::{Config, ConsoleReporter};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use core_filter::ExceptionBoundary;
+use core_module::Bootstrap;
+use kit_cache::{Cache, MemoryCache, RedisCache};
+use kit_context::Context;
+use kit_event::{CommandPipeline, Idempotency, Tracing, Validation};
+use kit_http::{CacheRateLimitStore, IdempotencyKey, Quota, RateLimit, RateLimitRule};
+use kit_lock::{DistributedLock, PgAdvisoryLock};
+use kit_msa::{Discovery, PgRegistry, RegistryBackend, ServiceInstance, ServiceRegistry};
+use kit_router::{ApiDocs, RouteTable};
+use kit_security::{IdentitySigner, TrustedIdentity};
+use ntex::http::Method;
+use ntex::time::Seconds;
+use ntex::util::Either;
 use ntex::web::*;
 use sqlx::postgres::PgPoolOptions;
+use crate::infrastructure::application::bootstrap::retry::{Backoff, retry};
+use crate::infrastructure::application::shutdown::{Shutdown, wait_for_signal};
+use crate::infrastructure::database::{migrate, projection};
+use crate::infrastructure::database::idempotency_store::PgIdempotencyStore;
+use crate::infrastructure::database::transaction::Transactional;
+use crate::infrastructure::health::check::{DatabaseCheck, NatsCheck};
+use crate::infrastructure::health::health_route::{liveness, readiness};
+use crate::infrastructure::health::registry::HealthRegistry;
+use crate::infrastructure::http::{exception_filters, openapi};
+use crate::infrastructure::http::idempotency_store::PgResponseStore;
+use crate::infrastructure::metrics::metrics_route::metrics;
+use crate::infrastructure::mq::config::NatsConfig;
+use crate::infrastructure::mq::consumer::spawn_module_consumers;
+use crate::infrastructure::mq::fanout::Fanout;
+use crate::infrastructure::metrics::middleware::RequestMetrics;
+use crate::infrastructure::trace::config::TraceConfig;
+use crate::infrastructure::trace::reporter;
 use crate::infrastructure::trace::tracer::Tracer;
-use crate::modules::user::core::command::handler::UserRegisterCommandHandler;
-use crate::modules::user::infrastructure::user_repository::UserRepository;
-use crate::modules::user::infrastructure::user_security_repository::UserSecurityRepository;
-use crate::modules::user::interface::user_route::createUser;
-use crate::states::{AppState, UserDeps};
+use crate::states::AppState;
+
+// 디버그 빌드에서만 등록된 라우트 목록을 `GET /_routes` 로 제공한다.
+fn debug_routes(cfg: &mut ServiceConfig, table: &RouteTable) {
+    if cfg!(debug_assertions) {
+        let table = table.clone();
+        cfg.route("/_routes", get().to(move || {
+            let table = table.clone();
+            async move { table }
+        }));
+    }
+}
 
 #[ntex::main]
 async fn main() -> std::io::Result<()> {
-    fastrace::set_reporter(ConsoleReporter, Config::default());
-
     dotenv::from_filename(".development.env").ok();
 
+    reporter::install(&TraceConfig::from_env().map_err(std::io::Error::other)?);
+
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
     let server_addr = env::var("SERVER_ADDR").unwrap_or_else(|_| "127.0.0.1:8080".to_string());
+    // 다른 인스턴스가 이 인스턴스를 호출할 주소. 모든 인터페이스에 바인딩하면(`0.0.0.0`) 따로 지정해야 한다.
+    let service_address = env::var("SERVICE_ADDRESS").unwrap_or_else(|_| server_addr.clone());
+    if service_address.parse::<SocketAddr>().is_ok_and(|addr| addr.ip().is_unspecified()) {
+        return Err(std::io::Error::other(format!(
+            "SERVICE_ADDRESS `{}` is not reachable from other hosts, set it to an advertised address",
+            service_address
+        )));
+    }
+    // kit-gateway 가 인증한 뒤 서명해 보내는 신원 헤더를 확인하는 키. 게이트웨이와 같은 값이어야 한다.
+    let identity_secret = env::var("IDENTITY_SECRET").expect("IDENTITY_SECRET must be set");
+    // 서비스 레지스트리에 이 인스턴스를 등록할 이름. 다른 서비스는 `service://<이름>` 으로 호출한다.
+    let service_name = env::var("SERVICE_NAME").unwrap_or_else(|_| "user-service".to_string());
+    // `X-Forwarded-For` 를 믿을 프록시 주소 목록(쉼표 구분). 비어 있으면 소켓 상대 주소만 클라이언트 IP 로 쓴다.
+    let trusted_proxies = env::var("TRUSTED_PROXIES")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|proxy| !proxy.is_empty())
+        .map(|proxy| proxy.parse::<IpAddr>().map_err(|e| std::io::Error::other(format!("invalid TRUSTED_PROXIES entry `{}`: {}", proxy, e))))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    let pool = PgPoolOptions::new()
-        .max_connections(5)
-        .connect(&database_url)
+    let backoff = Backoff::default();
+
+    let pool = retry("database", &backoff, || {
+        PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+    })
         .await
-        .expect("Failed to create database connection pool");
+        .map_err(std::io::Error::other)?;
 
     let pool_clone = pool.clone();
 
-    let user_repository = UserRepository::new(pool.clone());
-    let user_security_repository = UserSecurityRepository::new(pool.clone());
-    let user_register_command_handler = UserRegisterCommandHandler::new(user_repository.clone(), user_security_repository.clone());
+    println!("\nSERVER ADDRESS IS: {}", &server_addr);
+
+    let nats_config = NatsConfig::default().with_connection_name("rust-client");
+    let nats_client = retry("nats", &backoff, || nats_config.connect())
+        .await
+        .map_err(std::io::Error::other)?;
 
-    let user_deps = UserDeps {
-        user_register_command_handler,
-        user_repository,
-        user_security_repository,
+    // REDIS_URL 이 없으면 인스턴스마다 따로 쓰는 메모리 캐시로 대신한다.
+    let cache: Arc<dyn Cache> = match env::var("REDIS_URL") {
+        Ok(redis_url) => Arc::new(
+            retry("redis", &backoff, || RedisCache::connect(&redis_url))
+                .await
+                .map_err(std::io::Error::other)?,
+        ),
+        Err(_) => Arc::new(MemoryCache::default()),
     };
 
-    println!("\nSERVER ADDRESS IS: {}", &server_addr);
+    let lock = DistributedLock::new(PgAdvisoryLock::new(pool.clone()));
+
+    let registry_shared: Arc<dyn RegistryBackend> = Arc::new(PgRegistry::new(pool.clone()));
+    // 커맨드 결과는 인스턴스끼리 함께 보도록 Postgres 에 저장한다.
+    let command_store = PgIdempotencyStore::new(pool.clone());
 
-    let nats_client = ConnectOptions::new()
-        .name("rust-client")
-        .connect("localhost:4222")
+    let application = Bootstrap::new()
+        .instance(pool.clone())
+        .instance(nats_client.clone())
+        .instance(Fanout::new(nats_client.clone()))
+        .instance(cache.clone())
+        .instance(lock.clone())
+        .instance(Discovery::shared(registry_shared.clone()))
+        .instance(
+            CommandPipeline::new()
+                .with(Tracing)
+                .with(Validation)
+                .with(Idempotency::new(command_store.clone()))
+                .with(Transactional::new(pool.clone())),
+        )
+        .modules(modules::modules())
+        .build()
         .await
-        .expect("Failed to connect to the server");
+        .map_err(std::io::Error::other)?;
+
+    let route_table = application.route_table();
+    println!("\n{}\n", route_table);
+    let api_docs = ApiDocs::new(&openapi::spec(&route_table));
+
+    migrate::run(&pool, application.migrations())
+        .await
+        .map_err(std::io::Error::other)?;
+
+    let response_store = PgResponseStore::new(pool.clone());
+
+    // `rebuild-projections [name...]`: 읽기 모델만 다시 만들고 서버는 띄우지 않는다.
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    if let Some((command, names)) = args.split_first()
+        && command == "rebuild-projections"
+    {
+        let rebuilt = lock
+            .with_lock("rebuild-projections", |_| projection::rebuild(application.projections(), names))
+            .await
+            .map_err(std::io::Error::other)?;
+
+        return match rebuilt {
+            Some(result) => result.map_err(std::io::Error::other),
+            None => Err(std::io::Error::other("another instance is already rebuilding projections")),
+        };
+    }
 
-    HttpServer::new(move || {
+    spawn_module_consumers(&nats_client, application.consumers())
+        .await
+        .map_err(std::io::Error::other)?;
+
+    let purged_store = response_store.clone();
+    ntex::rt::spawn(async move { purged_store.purge_expired(Duration::from_secs(60)).await });
+    ntex::rt::spawn(async move { command_store.purge_expired(Duration::from_secs(60)).await });
+
+    let health_registry = HealthRegistry::new()
+        .with_check(DatabaseCheck::new(pool.clone()))
+        .with_check(NatsCheck::new(nats_client.clone()));
+    let health_registry = application
+        .health_checks()
+        .iter()
+        .fold(health_registry, |registry, check| registry.with_shared_check(check.clone()));
+
+    // 가입은 IP 당 시간당 10번까지. 인스턴스가 여러 개면 REDIS_URL 로 공유 캐시를 써야 함께 계산된다.
+    let rate_limit = RateLimit::new(CacheRateLimitStore::new(cache.clone())).rule(
+        RateLimitRule::new("user-register", Quota::sliding_window(10, Duration::from_secs(60 * 60)).map_err(std::io::Error::other)?)
+            .route(Method::POST, "/v1/user"),
+    );
+
+    let trusted_identity = TrustedIdentity::new(IdentitySigner::new(identity_secret));
+
+    let shutdown = Shutdown::new(Duration::from_secs(10));
+
+    // 등록 순서대로 실행된다. 남은 publish 를 흘려보낸 뒤 DB 를 닫고, 마지막으로 스팬을 내보낸다.
+    let nats_drain_client = nats_client.clone();
+    shutdown.on_shutdown("nats", move || async move {
+        if let Err(e) = nats_drain_client.drain().await {
+            println!("[shutdown] failed to drain NATS: {}", e);
+        }
+    });
+
+    let database_pool = pool.clone();
+    shutdown.on_shutdown("database", move || async move { database_pool.close().await });
+    shutdown.on_shutdown("trace", || async { fastrace::flush() });
+
+    let reported_health = health_registry.clone();
+    let server = HttpServer::new(move || {
         App::new()
             .state(AppState {
                 pool: pool_clone.clone(),
-                nats_client: nats_client.clone(),
             })
-            .state(user_deps.clone())
+            .state(health_registry.clone())
+            .state(exception_filters::global())
+            .wrap(IdempotencyKey::new(response_store.clone()))
+            .wrap(rate_limit.clone())
+            .wrap(Context::new().with_timeout(Duration::from_secs(30)).with_trusted_proxies(trusted_proxies.clone()))
+            .wrap(trusted_identity.clone())
+            // 패닉으로 만든 500 도 메트릭과 스팬에 남도록 경계는 그 둘의 안쪽에 둔다.
+            .wrap(ExceptionBoundary)
+            .wrap(RequestMetrics)
             .wrap(Tracer)
-            .service(createUser)
+            .service(metrics)
+            .service(liveness)
+            .service(readiness)
+            .configure(|cfg| application.configure(cfg))
+            .configure(|cfg| debug_routes(cfg, &route_table))
+            .configure(|cfg| api_docs.configure(cfg))
     })
-        .bind(("127.0.0.1", 8080))?
-        .run()
+        .disable_signals()
+        .shutdown_timeout(Seconds(30))
+        .bind(server_addr.as_str())?
+        .run();
+
+    let registration = ServiceRegistry::shared(registry_shared)
+        .register(
+            ServiceInstance::new(service_name, service_address)
+                .with_metadata("version", env!("CARGO_PKG_VERSION")),
+        )
+        .await
+        .map_err(std::io::Error::other)?;
+
+    let server_handle = server.clone();
+    ntex::rt::spawn(async move {
+        // 신호를 기다리는 동안 readiness 결과를 레지스트리의 인스턴스 상태로 알린다.
+        let signal = match ntex::util::select(
+            wait_for_signal(),
+            reported_health.report_to(&registration, Duration::from_secs(5)),
+        )
         .await
+        {
+            Either::Left(signal) => signal,
+            Either::Right(never) => match never {},
+        };
+
+        match signal {
+            Ok(signal) => println!("\n{} received, stopping server gracefully", signal),
+            Err(e) => {
+                // 신호를 못 받는다고 멀쩡한 서버를 내리지 않는다. 등록을 쥔 채 상태를 계속 알린다.
+                println!("\nFailed to listen for shutdown signals, serving without graceful shutdown: {}", e);
+                match reported_health.report_to(&registration, Duration::from_secs(5)).await {}
+            }
+        }
+
+        // 새 요청이 이 인스턴스로 오지 않도록 서버를 멈추기 전에 레지스트리에서 뺀다.
+        if let Err(e) = registration.deregister().await {
+            println!("[shutdown] failed to deregister service instance: {}", e);
+        }
+
+        server_handle.stop(true).await;
+    });
+
+    server.await?;
+    shutdown.run().await;
+
+    Ok(())
 }
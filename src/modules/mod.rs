@@ -1 +1,16 @@
-pub mod user;
\ No newline at end of file
+pub mod platform;
+pub mod user;
+
+use core_module::Module;
+use crate::modules::platform::platform_module::PlatformModule;
+use crate::modules::user::user_module::UserModule;
+
+/// 애플리케이션을 구성하는 기능 모듈 목록
+///
+/// 새 기능을 추가할 때는 모듈을 만들어 이 목록에만 등록하면 됩니다.
+pub fn modules() -> Vec<Box<dyn Module>> {
+    vec![
+        Box::new(PlatformModule),
+        Box::new(UserModule),
+    ]
+}
\ No newline at end of file
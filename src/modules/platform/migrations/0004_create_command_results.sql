@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS command_results (
+    key VARCHAR(512) PRIMARY KEY,
+    result JSONB,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_command_results_expires_at ON command_results (expires_at);
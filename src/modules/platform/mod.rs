@@ -0,0 +1 @@
+pub mod platform_module;
@@ -0,0 +1,29 @@
+use core_module::{Module, ModuleDefinition};
+use kit_lock::PgAdvisoryLock;
+use kit_msa::PgRegistry;
+
+/// 기능 모듈이 함께 쓰는 인프라 테이블(멱등성 키, 커맨드 결과, 분산 락 펜싱 토큰, 서비스 레지스트리 등)의 마이그레이션을 담당하는 모듈
+///
+/// 다른 모듈의 마이그레이션보다 먼저 적용되도록 모듈 목록의 맨 앞에 둡니다.
+pub struct PlatformModule;
+
+impl Module for PlatformModule {
+    fn name(&self) -> &'static str {
+        "platform"
+    }
+
+    fn configure(&self, module: &mut ModuleDefinition) {
+        module.migration(
+            1,
+            "create_idempotency_keys",
+            include_str!("migrations/0001_create_idempotency_keys.sql"),
+        );
+        module.migration(2, "create_lock_tokens", PgAdvisoryLock::MIGRATION);
+        module.migration(3, "create_service_instances", PgRegistry::MIGRATION);
+        module.migration(
+            4,
+            "create_command_results",
+            include_str!("migrations/0004_create_command_results.sql"),
+        );
+    }
+}
@@ -1,13 +1,47 @@
+use core_filter::ValidationError;
+use kit_event::Command;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use crate::modules::user::core::command::handler::UserRegisterCommandHandler;
 
-#[derive(Serialize, Deserialize, Debug)]
+/// 회원 가입 요청
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct UserRegisterCommand {
+    #[schemars(length(min = 1, max = 30))]
     pub name: String,
+    #[schemars(email, length(max = 100))]
     pub email: String,
+    #[schemars(length(min = 1))]
     pub password: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+impl Command for UserRegisterCommand {
+    type Result = UserRegisterCommandResult;
+    type Handler = UserRegisterCommandHandler;
+
+    /// 테이블 제약(`users.name`, `users.email`)을 넘기 전에 입력을 검증합니다.
+    fn validate(&self) -> Result<(), ValidationError> {
+        let mut error = ValidationError::new();
+
+        if self.name.trim().is_empty() || self.name.chars().count() > 30 {
+            error = error.with_field("name", "must be between 1 and 30 characters");
+        }
+
+        if !self.email.contains('@') || self.email.chars().count() > 100 {
+            error = error.with_field("email", "must be a valid email address up to 100 characters");
+        }
+
+        if self.password.is_empty() {
+            error = error.with_field("password", "must not be empty");
+        }
+
+        error.into_result()
+    }
+}
+
+/// 회원 가입 결과
+#[derive(Deserialize, Serialize, Debug, JsonSchema)]
 pub struct UserRegisterCommandResult {
+    /// 생성된 사용자 ID
     pub id: i32,
 }
\ No newline at end of file
@@ -1,44 +1,59 @@
+use async_nats::Client;
+use core_filter::Exception;
 use futures::try_join;
-use ntex::web::types::State;
-use serde_json::json;
+use kit_event::CommandHandler;
+use crate::infrastructure::database::transaction;
+use crate::infrastructure::mq::publisher;
 use crate::modules::user::core::command::command::{UserRegisterCommand, UserRegisterCommandResult};
 use crate::modules::user::core::entity::password::PasswordEncrypter;
+use crate::modules::user::core::event::user_event::{RegisteredUser, UserEvent};
+use crate::modules::user::infrastructure::user_event_repository::UserEventRepository;
+use crate::modules::user::infrastructure::user_metrics;
 use crate::modules::user::infrastructure::user_repository::UserRepository;
 use crate::modules::user::infrastructure::user_security_repository::UserSecurityRepository;
-use crate::states::{AppState, UserDeps};
 
 #[derive(Debug, Clone)]
 pub struct UserRegisterCommandHandler {
     pub user_repository: UserRepository,
     pub user_security_repository: UserSecurityRepository,
+    pub user_event_repository: UserEventRepository,
+    pub nats_client: Client,
 }
 
 impl UserRegisterCommandHandler {
-    pub fn new(user_repository: UserRepository, user_security_repository: UserSecurityRepository) -> Self {
+    pub fn new(
+        user_repository: UserRepository,
+        user_security_repository: UserSecurityRepository,
+        user_event_repository: UserEventRepository,
+        nats_client: Client,
+    ) -> Self {
         Self {
             user_repository,
             user_security_repository,
+            user_event_repository,
+            nats_client,
         }
     }
+}
+
+/// 입력 검증과 트랜잭션은 커맨드 버스 미들웨어가 담당합니다.
+impl CommandHandler<UserRegisterCommand> for UserRegisterCommandHandler {
+    type Error = Exception;
 
-    pub async fn handle(
+    async fn handle(
         &self,
         command: UserRegisterCommand,
-        deps: &State<UserDeps>,
-        state: &State<AppState>,
-    ) -> Result<UserRegisterCommandResult, String> {
-        let user = deps
+    ) -> Result<UserRegisterCommandResult, Exception> {
+        let user = self
             .user_repository
-            .insert(&command, state)
-            .await
-            .map_err(|e| format!("Error inserting user: {:?}", e))?;
+            .insert(&command)
+            .await?;
         
         let password = command.password.clone();
         let encrypter = PasswordEncrypter::new();
-        let encrypted_password = encrypter.hash(&password)
-            .map_err(|e| format!("Error encrypting password: {:?}", e))?;
+        let encrypted_password = encrypter.hash(&password)?;
         
-        let security_repository = &deps.user_security_repository;
+        let security_repository = &self.user_security_repository;
         
         try_join!(
             security_repository.insert_password(
@@ -46,30 +61,34 @@ impl UserRegisterCommandHandler {
                 encrypted_password.hash.clone(), 
                 encrypted_password.salt.clone()
             ),
-            security_repository.insert_security_history(
-                user.id,
-                "REGISTRATION".to_string(),
-                None,
-                None
-            ),
+            security_repository.insert_security_history(user.id, "REGISTRATION".to_string()),
             security_repository.insert_security_counter("USER_REGISTRATION".to_string())
-        )
-            .map_err(|e| format!("Failed to execute security operations: {:?}", e))?;
+        )?;
         
-        let event = json!({
-            "event": "user.registered",
-            "user": {
-                "id": user.id,
-                "username": user.name,
-                "email": user.email,
-            }
-        });
+        let event = UserEvent::Registered {
+            user: RegisteredUser {
+                id: user.id,
+                username: user.name,
+                email: user.email,
+            },
+            occurred_at: user.created_at,
+        };
 
-        state
-            .nats_client
-            .publish("user.registered", serde_json::to_vec(&event).unwrap().into())
-            .await
-            .map_err(|e| format!("Failed to publish NATS event: {}", e))?;
+        self.user_event_repository.append(&event).await?;
+
+        // 커밋이 실패했을 때 없는 가입이 알려지거나 세어지지 않도록 발행과 집계는 커밋 뒤로 미룬다.
+        // 발행에 실패해도 가입은 유지되며, 읽기 모델은 `rebuild-projections` 로 `user_events` 에서 다시 만들 수 있다.
+        let client = self.nats_client.clone();
+        let subject = event.subject();
+        let payload = serde_json::to_vec(&event)?;
+        transaction::after_commit(async move {
+            user_metrics::REGISTRATIONS.inc();
+
+            if let Err(e) = publisher::publish(&client, subject, payload.into()).await {
+                eprintln!("[user] failed to publish {}: {}", subject, e);
+            }
+        })
+        .await;
 
         Ok(UserRegisterCommandResult {
             id: user.id,
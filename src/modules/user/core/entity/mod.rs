@@ -2,4 +2,5 @@ pub mod user;
 pub mod password;
 pub mod user_security_password;
 pub mod user_security_history;
-pub mod system_security_counter;
\ No newline at end of file
+pub mod system_security_counter;
+pub mod user_view;
\ No newline at end of file
@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// 조회 전용 사용자 정보 (`user_view` 프로젝션)
+#[derive(Serialize, Deserialize, Debug, Clone, FromRow, JsonSchema)]
+pub struct UserView {
+    /// 사용자 ID
+    pub id: i32,
+    pub name: String,
+    pub email: String,
+    /// 가입 시각
+    pub registered_at: DateTime<Utc>,
+}
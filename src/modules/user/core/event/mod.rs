@@ -0,0 +1 @@
+pub mod user_event;
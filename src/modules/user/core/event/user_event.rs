@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 사용자 도메인 이벤트
+///
+/// `user_events` 에 쌓이고 같은 형식으로 NATS 에 발행됩니다. 읽기 모델은 이 이벤트만으로 다시 만들 수 있어야 합니다.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "event")]
+pub enum UserEvent {
+    #[serde(rename = "user.registered")]
+    Registered {
+        user: RegisteredUser,
+        occurred_at: DateTime<Utc>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RegisteredUser {
+    pub id: i32,
+    pub username: String,
+    pub email: String,
+}
+
+impl UserEvent {
+    /// 이벤트 이름이자 발행 subject
+    pub fn subject(&self) -> &'static str {
+        match self {
+            UserEvent::Registered { .. } => "user.registered",
+        }
+    }
+
+    pub fn user_id(&self) -> i32 {
+        match self {
+            UserEvent::Registered { user, .. } => user.id,
+        }
+    }
+
+    pub fn occurred_at(&self) -> DateTime<Utc> {
+        match self {
+            UserEvent::Registered { occurred_at, .. } => *occurred_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_registered_keeps_published_format() {
+        let occurred_at = Utc::now();
+        let event = UserEvent::Registered {
+            user: RegisteredUser {
+                id: 1,
+                username: "jeff".to_string(),
+                email: "jeff@example.com".to_string(),
+            },
+            occurred_at,
+        };
+        let value = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(value["event"], "user.registered");
+        assert_eq!(value["user"], json!({ "id": 1, "username": "jeff", "email": "jeff@example.com" }));
+        assert_eq!(serde_json::from_value::<UserEvent>(value).unwrap(), event);
+    }
+}
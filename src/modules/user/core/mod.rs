@@ -1,2 +1,4 @@
 pub mod entity;
-pub mod command;
\ No newline at end of file
+pub mod command;
+pub mod query;
+pub mod event;
\ No newline at end of file
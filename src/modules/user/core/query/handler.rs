@@ -0,0 +1,49 @@
+use futures::try_join;
+use kit_event::QueryHandler;
+use kit_http::Page;
+use crate::modules::user::core::entity::user_view::UserView;
+use crate::modules::user::core::query::queries::{FindUser, SearchUsers};
+use crate::modules::user::infrastructure::user_cache::UserCache;
+use crate::modules::user::infrastructure::user_view_repository::UserViewRepository;
+
+/// 사용자 조회 핸들러. 쓰기 테이블 대신 `user_view` 프로젝션만 읽으며, 단건 조회는 [`UserCache`] 를 거칩니다.
+#[derive(Clone)]
+pub struct UserQueryHandler {
+    pub user_view_repository: UserViewRepository,
+    pub user_cache: UserCache,
+}
+
+impl UserQueryHandler {
+    pub fn new(user_view_repository: UserViewRepository, user_cache: UserCache) -> Self {
+        Self {
+            user_view_repository,
+            user_cache,
+        }
+    }
+}
+
+impl QueryHandler<FindUser> for UserQueryHandler {
+    type Error = sqlx::Error;
+
+    async fn handle(&self, query: FindUser) -> Result<UserView, sqlx::Error> {
+        let repository = self.user_view_repository.clone();
+
+        self.user_cache
+            .find(query.id, move || async move { repository.find(query.id).await })
+            .await
+    }
+}
+
+impl QueryHandler<SearchUsers> for UserQueryHandler {
+    type Error = sqlx::Error;
+
+    async fn handle(&self, query: SearchUsers) -> Result<Page<UserView>, sqlx::Error> {
+        let keyword = query.keyword.as_deref();
+        let (users, total) = try_join!(
+            self.user_view_repository.search(keyword, query.page.offset(), query.page.limit()),
+            self.user_view_repository.count(keyword),
+        )?;
+
+        Ok(Page::new(users, &query.page, total as u64))
+    }
+}
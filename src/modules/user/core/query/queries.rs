@@ -0,0 +1,57 @@
+use kit_event::Query;
+use kit_http::{Page, PageRequest};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use crate::modules::user::core::entity::user_view::UserView;
+use crate::modules::user::core::query::handler::UserQueryHandler;
+
+/// ID 로 사용자 한 명을 조회합니다. 없으면 `RowNotFound` (404) 입니다.
+#[derive(Debug, Clone)]
+pub struct FindUser {
+    pub id: i32,
+}
+
+impl Query for FindUser {
+    type Output = UserView;
+    type Handler = UserQueryHandler;
+}
+
+/// 이름 또는 이메일에 키워드가 포함된 사용자를 최근 가입 순으로 조회합니다.
+#[derive(Debug, Clone)]
+pub struct SearchUsers {
+    pub keyword: Option<String>,
+    pub page: PageRequest,
+}
+
+impl Query for SearchUsers {
+    type Output = Page<UserView>;
+    type Handler = UserQueryHandler;
+}
+
+/// 사용자 검색 쿼리 파라미터
+///
+/// `page`, `size` 는 [`PageRequest`] 가 검증해서 꺼내므로 여기서는 API 문서에만 쓰입니다.
+#[derive(Deserialize, Debug, JsonSchema)]
+pub struct UserSearchParams {
+    /// 이름 또는 이메일에 포함된 문자열 (대소문자 무시)
+    pub keyword: Option<String>,
+    /// 페이지 번호 (1부터)
+    #[allow(dead_code)]
+    #[schemars(range(min = 1))]
+    pub page: Option<u32>,
+    /// 페이지 크기 (최대 100)
+    #[allow(dead_code)]
+    #[schemars(range(min = 1, max = 100))]
+    pub size: Option<u32>,
+}
+
+impl UserSearchParams {
+    /// 빈 키워드는 전체 조회로 취급합니다.
+    pub fn keyword(&self) -> Option<String> {
+        self.keyword
+            .as_deref()
+            .map(str::trim)
+            .filter(|keyword| !keyword.is_empty())
+            .map(str::to_string)
+    }
+}
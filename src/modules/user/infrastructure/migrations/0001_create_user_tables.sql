@@ -0,0 +1,41 @@
+CREATE TABLE IF NOT EXISTS users (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(30) NOT NULL,
+    email VARCHAR(100) UNIQUE NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    deleted_at TIMESTAMPTZ NULL DEFAULT NULL
+);
+
+CREATE TABLE IF NOT EXISTS user_security_password (
+    id SERIAL PRIMARY KEY,
+    password_hash VARCHAR(255) NOT NULL,
+    salt VARCHAR(64) NOT NULL,
+    last_password_change TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    failed_attempts INT DEFAULT 0,
+    account_locked BOOLEAN DEFAULT FALSE,
+    lock_time TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    deleted_at TIMESTAMPTZ NULL DEFAULT NULL,
+    user_id INT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS user_security_history (
+    id      BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    action_type VARCHAR(30) NOT NULL CHECK (action_type IN ('LOGIN', 'PASSWORD_CHANGE', 'SUSPICIOUS_ACTIVITY')),
+    ip_address VARCHAR(45),
+    device_info VARCHAR(255),
+    timestamp TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id)
+);
+
+CREATE TABLE IF NOT EXISTS system_security_counter (
+    id BIGSERIAL PRIMARY KEY,
+    counter_type VARCHAR(50) NOT NULL,
+    counter_value BIGINT DEFAULT 0,
+    last_updated TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (counter_type)
+);
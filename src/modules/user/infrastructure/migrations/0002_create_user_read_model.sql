@@ -0,0 +1,21 @@
+CREATE TABLE IF NOT EXISTS user_events (
+    id BIGSERIAL PRIMARY KEY,
+    user_id INT NOT NULL,
+    event_type VARCHAR(50) NOT NULL,
+    payload JSONB NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_events_user_id ON user_events (user_id);
+
+CREATE TABLE IF NOT EXISTS user_view (
+    id INT PRIMARY KEY,
+    name VARCHAR(30) NOT NULL,
+    email VARCHAR(100) NOT NULL,
+    registered_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_view_name ON user_view (lower(name));
+CREATE INDEX IF NOT EXISTS idx_user_view_email ON user_view (lower(email));
+CREATE INDEX IF NOT EXISTS idx_user_view_registered_at ON user_view (registered_at DESC, id DESC);
@@ -0,0 +1,6 @@
+-- 이벤트 로그가 생기기 전에 가입한 사용자는 가입 이벤트가 없으므로 users 에서 바로 채운다.
+INSERT INTO user_view (id, name, email, registered_at, updated_at)
+SELECT id, name, email, COALESCE(created_at, CURRENT_TIMESTAMP), COALESCE(updated_at, CURRENT_TIMESTAMP)
+FROM users
+WHERE deleted_at IS NULL
+ON CONFLICT (id) DO NOTHING;
@@ -1,2 +1,7 @@
 pub mod user_repository;
-pub mod user_security_repository;
\ No newline at end of file
+pub mod user_security_repository;
+pub mod user_metrics;
+pub mod user_event_repository;
+pub mod user_view_repository;
+pub mod user_projection;
+pub mod user_cache;
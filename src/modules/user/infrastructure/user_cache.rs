@@ -0,0 +1,51 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use kit_cache::{Cache, Namespace, ReadThrough};
+use crate::modules::user::core::entity::user_view::UserView;
+use crate::modules::user::core::event::user_event::UserEvent;
+
+/// 사용자 조회 캐시 (`user:` 네임스페이스)
+///
+/// 값은 `user_view` 를 기준으로 하므로, 프로젝션이 이벤트를 적용한 뒤에 [`UserCache::invalidate`] 로 무효화합니다.
+#[derive(Clone)]
+pub struct UserCache {
+    read_through: ReadThrough,
+}
+
+impl UserCache {
+    pub fn new(cache: Arc<dyn Cache>) -> Self {
+        Self {
+            read_through: ReadThrough::new(Namespace::new(cache, "user"))
+                .with_ttl(Duration::from_secs(5 * 60))
+                .with_stale_while_revalidate(Duration::from_secs(60)),
+        }
+    }
+
+    pub async fn find<F, Fut>(&self, id: i32, load: F) -> Result<UserView, sqlx::Error>
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<UserView, sqlx::Error>> + Send + 'static,
+    {
+        self.read_through
+            .get_or_load(&id.to_string(), &[&tag(id)], load)
+            .await
+            .map_err(|e| Arc::try_unwrap(e).unwrap_or_else(|e| shared_error(&e)))
+    }
+
+    pub async fn invalidate(&self, event: &UserEvent) {
+        self.read_through.invalidate_tag(&tag(event.user_id())).await;
+    }
+}
+
+// 동시에 기다리던 요청과 나눠 가진 에러. sqlx::Error 는 복제할 수 없으므로 없음 여부만 살린다.
+fn shared_error(error: &sqlx::Error) -> sqlx::Error {
+    match error {
+        sqlx::Error::RowNotFound => sqlx::Error::RowNotFound,
+        e => sqlx::Error::Protocol(e.to_string()),
+    }
+}
+
+fn tag(id: i32) -> String {
+    format!("user:{}", id)
+}
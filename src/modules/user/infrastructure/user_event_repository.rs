@@ -0,0 +1,36 @@
+use sqlx::{PgPool, Error, query_scalar};
+use crate::infrastructure::database::transaction;
+use crate::modules::user::core::event::user_event::UserEvent;
+
+/// 사용자 도메인 이벤트 로그. 읽기 모델을 다시 만들 때 이 순서대로 재생합니다.
+#[derive(Debug, Clone)]
+pub struct UserEventRepository {
+    pool: PgPool,
+}
+
+impl UserEventRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool
+        }
+    }
+
+    pub async fn append(&self, event: &UserEvent) -> Result<i64, Error> {
+        let payload = serde_json::to_string(event).map_err(|e| Error::Encode(Box::new(e)))?;
+        let mut connection = transaction::acquire(&self.pool).await?;
+
+        query_scalar::<_, i64>(
+            r#"
+            INSERT INTO user_events (user_id, event_type, payload, occurred_at)
+            VALUES ($1, $2, $3::jsonb, $4)
+            RETURNING id
+            "#
+        )
+            .bind(event.user_id())
+            .bind(event.subject())
+            .bind(payload)
+            .bind(event.occurred_at())
+            .fetch_one(&mut *connection)
+            .await
+    }
+}
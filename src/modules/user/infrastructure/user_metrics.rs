@@ -0,0 +1,11 @@
+use std::sync::LazyLock;
+use prometheus::IntCounter;
+use crate::infrastructure::metrics::registry;
+
+// system_security_counter 의 USER_REGISTRATION 과 같은 값을 Prometheus 로도 노출한다.
+pub static REGISTRATIONS: LazyLock<IntCounter> = LazyLock::new(|| {
+    let counter = IntCounter::new("user_registrations_total", "Total number of registered users")
+        .expect("Invalid user_registrations_total metric");
+
+    registry::register(counter).expect("Failed to register user_registrations_total")
+});
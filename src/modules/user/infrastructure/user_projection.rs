@@ -0,0 +1,81 @@
+use core_module::Projection;
+use futures::future::BoxFuture;
+use sqlx::{PgExecutor, PgPool, Error, query, query_scalar};
+use crate::modules::user::core::event::user_event::UserEvent;
+
+/// 사용자 이벤트로 `user_view` 를 갱신합니다.
+///
+/// 같은 이벤트를 여러 번 적용해도 결과가 같으므로, 재전송된 메시지나 재생성 중 도착한 메시지가 있어도 안전합니다.
+#[derive(Debug, Clone)]
+pub struct UserProjection {
+    pool: PgPool,
+}
+
+impl UserProjection {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool
+        }
+    }
+
+    pub async fn apply(&self, event: &UserEvent) -> Result<(), Error> {
+        apply(&self.pool, event).await
+    }
+
+    /// `user_view` 를 비우고 `user_events` 를 처음부터 다시 적용합니다. 하나의 트랜잭션으로 실행됩니다.
+    ///
+    /// 이벤트 로그가 생기기 전에 가입해 이벤트가 없는 사용자는 마지막에 `users` 에서 채웁니다.
+    pub async fn rebuild(&self) -> Result<u64, Error> {
+        let mut transaction = self.pool.begin().await?;
+
+        query("TRUNCATE user_view").execute(&mut *transaction).await?;
+
+        let payloads = query_scalar::<_, String>("SELECT payload::text FROM user_events ORDER BY id")
+            .fetch_all(&mut *transaction)
+            .await?;
+
+        for payload in &payloads {
+            let event = serde_json::from_str::<UserEvent>(payload).map_err(|e| Error::Decode(Box::new(e)))?;
+            apply(&mut *transaction, &event).await?;
+        }
+
+        query(include_str!("migrations/0003_backfill_user_view.sql")).execute(&mut *transaction).await?;
+
+        transaction.commit().await?;
+
+        Ok(payloads.len() as u64)
+    }
+}
+
+impl Projection for UserProjection {
+    fn name(&self) -> &str {
+        "user_view"
+    }
+
+    fn rebuild(&self) -> BoxFuture<'_, Result<u64, String>> {
+        Box::pin(async move { UserProjection::rebuild(self).await.map_err(|e| e.to_string()) })
+    }
+}
+
+async fn apply<'e>(executor: impl PgExecutor<'e>, event: &UserEvent) -> Result<(), Error> {
+    match event {
+        UserEvent::Registered { user, occurred_at } => {
+            query(
+                r#"
+                INSERT INTO user_view (id, name, email, registered_at, updated_at)
+                VALUES ($1, $2, $3, $4, $4)
+                ON CONFLICT (id) DO UPDATE
+                SET name = EXCLUDED.name, email = EXCLUDED.email, updated_at = EXCLUDED.updated_at
+                "#
+            )
+                .bind(user.id)
+                .bind(&user.username)
+                .bind(&user.email)
+                .bind(occurred_at)
+                .execute(executor)
+                .await?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,9 +1,8 @@
 use sqlx::{PgPool, Error, query_as};
-use ntex::web::types::State;
 use crate::modules::user::core::command::command::UserRegisterCommand;
 use crate::modules::user::core::entity::user::User;
-use crate::states::AppState;
 use chrono::Utc;
+use crate::infrastructure::database::transaction;
 
 #[derive(Debug, Clone)]
 pub struct UserRepository {
@@ -17,8 +16,9 @@ impl UserRepository {
         }
     }
 
-    pub async fn insert(&self, command: &UserRegisterCommand, _state: &State<AppState>) -> Result<User, Error> {
+    pub async fn insert(&self, command: &UserRegisterCommand) -> Result<User, Error> {
         let now = Utc::now();
+        let mut connection = transaction::acquire(&self.pool).await?;
 
         let user = query_as::<_, User>(
             r#"
@@ -31,7 +31,7 @@ impl UserRepository {
             .bind(&command.email)
             .bind(now)
             .bind(now)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *connection)
             .await?;
 
         Ok(user)
@@ -1,4 +1,6 @@
+use kit_context::RequestContext;
 use sqlx::{PgPool, Error, query_as};
+use crate::infrastructure::database::transaction;
 use crate::modules::user::core::entity::system_security_counter::SystemSecurityCounter;
 use crate::modules::user::core::entity::user_security_history::UserSecurityHistory;
 use crate::modules::user::core::entity::user_security_password::UserSecurityPassword;
@@ -17,6 +19,8 @@ impl UserSecurityRepository {
 
     // 비밀번호 보안 정보 저장
     pub async fn insert_password(&self, user_id: i32, password_hash: String, salt: String) -> Result<i32, Error> {
+        let mut connection = transaction::acquire(&self.pool).await?;
+
         let record = query_as::<_, UserSecurityPassword>(
             r#"
             INSERT INTO user_security_password (password_hash, salt, user_id)
@@ -28,20 +32,21 @@ impl UserSecurityRepository {
             .bind(password_hash)
             .bind(salt)
             .bind(user_id)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *connection)
             .await?;
 
         Ok(record.id)
     }
 
-    // 보안 이력 저장
-    pub async fn insert_security_history(
-        &self,
-        user_id: i32,
-        action_type: String,
-        ip_address: Option<String>,
-        device_info: Option<String>
-    ) -> Result<i64, Error> {
+    // 보안 이력 저장 (접속 IP, 기기 정보는 현재 요청 컨텍스트에서 가져온다)
+    pub async fn insert_security_history(&self, user_id: i32, action_type: String) -> Result<i64, Error> {
+        let context = RequestContext::current();
+        // 컬럼 길이(VARCHAR(45), VARCHAR(255))를 넘으면 가입 도중 INSERT 가 실패하므로 잘라서 저장한다.
+        let ip_address = context.as_ref().and_then(|context| context.client_ip.as_deref()).map(|ip| truncate(ip, 45));
+        let device_info = context.as_ref().and_then(|context| context.user_agent.as_deref()).map(|agent| truncate(agent, 255));
+
+        let mut connection = transaction::acquire(&self.pool).await?;
+
         let record = sqlx::query_as::<_, UserSecurityHistory>(
             r#"
         INSERT INTO user_security_history (user_id, action_type, ip_address, device_info)
@@ -53,7 +58,7 @@ impl UserSecurityRepository {
             .bind(action_type)
             .bind(ip_address)
             .bind(device_info)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *connection)
             .await?;
 
         Ok(record.id)
@@ -61,6 +66,8 @@ impl UserSecurityRepository {
 
     // 시스템 보안 카운터 추가/증가
     pub async fn insert_security_counter(&self, counter_type: String) -> Result<i64, sqlx::Error> {
+        let mut connection = transaction::acquire(&self.pool).await?;
+
         let record = sqlx::query_as::<_, SystemSecurityCounter>(
             r#"
             INSERT INTO system_security_counter (counter_type, counter_value)
@@ -73,9 +80,25 @@ impl UserSecurityRepository {
             "#
         )
             .bind(counter_type)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *connection)
             .await?;
 
         Ok(record.counter_value as i64)
     }
-}
\ No newline at end of file
+}
+
+// 글자 경계를 지켜 `max` 글자까지만 남긴다.
+fn truncate(value: &str, max: usize) -> String {
+    value.chars().take(max).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_keeps_char_boundaries() {
+        assert_eq!(truncate("가나다라", 2), "가나");
+        assert_eq!(truncate("127.0.0.1", 45), "127.0.0.1");
+    }
+}
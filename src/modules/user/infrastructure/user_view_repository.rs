@@ -0,0 +1,82 @@
+use sqlx::{PgPool, Error, query_as, query_scalar};
+use crate::modules::user::core::entity::user_view::UserView;
+
+/// `user_view` 프로젝션 조회
+#[derive(Debug, Clone)]
+pub struct UserViewRepository {
+    pool: PgPool,
+}
+
+impl UserViewRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool
+        }
+    }
+
+    pub async fn find(&self, id: i32) -> Result<UserView, Error> {
+        query_as::<_, UserView>(
+            r#"
+            SELECT id, name, email, registered_at
+            FROM user_view
+            WHERE id = $1
+            "#
+        )
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    pub async fn search(&self, keyword: Option<&str>, offset: i64, limit: i64) -> Result<Vec<UserView>, Error> {
+        query_as::<_, UserView>(
+            r#"
+            SELECT id, name, email, registered_at
+            FROM user_view
+            WHERE $1::text IS NULL OR lower(name) LIKE $1 OR lower(email) LIKE $1
+            ORDER BY registered_at DESC, id DESC
+            OFFSET $2
+            LIMIT $3
+            "#
+        )
+            .bind(keyword.map(like_pattern))
+            .bind(offset)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    pub async fn count(&self, keyword: Option<&str>) -> Result<i64, Error> {
+        query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM user_view
+            WHERE $1::text IS NULL OR lower(name) LIKE $1 OR lower(email) LIKE $1
+            "#
+        )
+            .bind(keyword.map(like_pattern))
+            .fetch_one(&self.pool)
+            .await
+    }
+}
+
+// 키워드 안의 LIKE 와일드카드는 글자 그대로 비교한다.
+fn like_pattern(keyword: &str) -> String {
+    let escaped = keyword
+        .to_lowercase()
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+
+    format!("%{}%", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_like_pattern_escapes_wildcards() {
+        assert_eq!(like_pattern("Jeff"), "%jeff%");
+        assert_eq!(like_pattern("100%_a\\b"), "%100\\%\\_a\\\\b%");
+    }
+}
@@ -1,28 +1,81 @@
-use ntex::web::*;
-use ntex::web::types::{Json, State};
-use crate::modules::user::core::command::command::UserRegisterCommand;
-use crate::states::{AppState, UserDeps};
+use core_container::Inject;
+use core_filter::Exception;
+use kit_context::RequestContext;
+use kit_event::{CommandBus, QueryBus};
+use kit_http::{Content, Page, PageRequest, Reply};
+use kit_security::{SecurityError, require_role};
+use ntex::web::types::{Path, Query};
+use ntex::web::{HttpRequest, HttpResponse};
+use crate::modules::user::core::command::command::{UserRegisterCommand, UserRegisterCommandResult};
+use crate::modules::user::core::entity::user_view::UserView;
+use crate::infrastructure::mq::fanout::Fanout;
+use crate::modules::user::core::query::queries::{FindUser, SearchUsers, UserSearchParams};
 
-#[post("/user")]
+// 사용자 정보와 가입 이벤트에는 이메일 등 개인 정보가 들어 있어 본인이 아니면 관리자만 볼 수 있다.
+const ADMIN_ROLE: &str = "admin";
+const EVENTS_SUBJECT: &str = "user.>";
+
+/// `POST /v1/user`
+#[fastrace::trace]
+#[allow(non_snake_case)]
+pub async fn createUser(
+    command: Content<UserRegisterCommand>,
+    commands: CommandBus,
+) -> Result<Reply<UserRegisterCommandResult>, Exception> {
+    let result = commands.send(command.into_inner()).await?;
+
+    Ok(Reply::created(result))
+}
+
+/// `GET /v1/user/{id}`
+#[fastrace::trace]
+#[allow(non_snake_case)]
+pub async fn getUser(id: Path<i32>, context: RequestContext, queries: QueryBus) -> Result<Reply<UserView>, Exception> {
+    let id = id.into_inner();
+    let principal = context.principal.as_ref().ok_or(SecurityError::Unauthenticated)?;
+    if principal.id != id.to_string() {
+        require_role(&context, ADMIN_ROLE)?;
+    }
+
+    let user = queries.ask(FindUser { id }).await?;
+
+    Ok(Reply::ok(user))
+}
+
+/// `GET /v1/user?keyword=&page=&size=`
 #[fastrace::trace]
 #[allow(non_snake_case)]
-async fn createUser(
-    command: Json<UserRegisterCommand>,
-    state: State<AppState>,
-    deps: State<UserDeps>,
-) -> Result<impl Responder, Error> {
-    let result = match deps.user_register_command_handler
-        .handle(
-            command.into_inner(),
-            &deps,
-            &state,
-        )
-        .await {
-        Ok(result) => result,
-        Err(e) => {
-            return Ok(HttpResponse::InternalServerError().body(format!("Error: {}", e)));
-        }
-    };
-    
-    Ok(HttpResponse::Ok().json(&result))
-}
\ No newline at end of file
+pub async fn searchUsers(
+    params: Query<UserSearchParams>,
+    page: PageRequest,
+    context: RequestContext,
+    queries: QueryBus,
+) -> Result<Page<UserView>, Exception> {
+    require_role(&context, ADMIN_ROLE)?;
+
+    let users = queries.ask(SearchUsers { keyword: params.keyword(), page }).await?;
+
+    Ok(users)
+}
+
+/// `GET /v1/user/events` (SSE)
+#[fastrace::trace]
+#[allow(non_snake_case)]
+pub async fn streamUserEvents(context: RequestContext, fanout: Inject<Fanout>) -> Result<HttpResponse, Exception> {
+    require_role(&context, ADMIN_ROLE)?;
+
+    Ok(fanout.sse(EVENTS_SUBJECT).await?)
+}
+
+/// `GET /v1/user/events/ws` (WebSocket)
+#[fastrace::trace]
+#[allow(non_snake_case)]
+pub async fn watchUserEvents(
+    req: HttpRequest,
+    context: RequestContext,
+    fanout: Inject<Fanout>,
+) -> Result<HttpResponse, Exception> {
+    require_role(&context, ADMIN_ROLE)?;
+
+    Ok(fanout.websocket(req, EVENTS_SUBJECT).await?)
+}
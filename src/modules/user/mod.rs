@@ -1,3 +1,4 @@
 pub mod interface;
 pub mod infrastructure;
-pub mod core;
\ No newline at end of file
+pub mod core;
+pub mod user_module;
\ No newline at end of file
@@ -0,0 +1,168 @@
+use std::sync::Arc;
+use async_nats::Client;
+use core_container::ContainerError;
+use core_module::{Module, ModuleDefinition, ModuleResolver};
+use kit_cache::Cache;
+use kit_http::Envelope;
+use kit_router::{Operation, RouteGroup};
+use ntex::http::StatusCode;
+use sqlx::PgPool;
+use crate::modules::user::core::command::command::{UserRegisterCommand, UserRegisterCommandResult};
+use crate::modules::user::core::command::handler::UserRegisterCommandHandler;
+use crate::modules::user::core::entity::user_view::UserView;
+use crate::modules::user::core::event::user_event::UserEvent;
+use crate::modules::user::core::query::handler::UserQueryHandler;
+use crate::modules::user::core::query::queries::UserSearchParams;
+use crate::modules::user::infrastructure::user_cache::UserCache;
+use crate::modules::user::infrastructure::user_event_repository::UserEventRepository;
+use crate::modules::user::infrastructure::user_projection::UserProjection;
+use crate::modules::user::infrastructure::user_repository::UserRepository;
+use crate::modules::user::infrastructure::user_security_repository::UserSecurityRepository;
+use crate::modules::user::infrastructure::user_view_repository::UserViewRepository;
+use crate::modules::user::interface::user_route::{createUser, getUser, searchUsers, streamUserEvents, watchUserEvents};
+
+/// 회원 가입과 사용자 보안 정보를 담당하는 모듈
+///
+/// 조회는 가입 이벤트로 갱신되는 `user_view` 프로젝션에서만 읽으므로, 가입 직후에는 잠시 보이지 않을 수 있습니다.
+pub struct UserModule;
+
+impl Module for UserModule {
+    fn name(&self) -> &'static str {
+        "user"
+    }
+
+    fn configure(&self, module: &mut ModuleDefinition) {
+        module
+            .migration(
+                1,
+                "create_user_tables",
+                include_str!("infrastructure/migrations/0001_create_user_tables.sql"),
+            )
+            .migration(
+                2,
+                "create_user_read_model",
+                include_str!("infrastructure/migrations/0002_create_user_read_model.sql"),
+            )
+            .migration(
+                3,
+                "backfill_user_view",
+                include_str!("infrastructure/migrations/0003_backfill_user_view.sql"),
+            )
+            .singleton(|resolver: ModuleResolver| async move {
+                let pool = resolver.get::<PgPool>().await?;
+                Ok(UserRepository::new(pool.as_ref().clone()))
+            })
+            .singleton(|resolver: ModuleResolver| async move {
+                let pool = resolver.get::<PgPool>().await?;
+                Ok(UserSecurityRepository::new(pool.as_ref().clone()))
+            })
+            .singleton(|resolver: ModuleResolver| async move {
+                let pool = resolver.get::<PgPool>().await?;
+                Ok(UserEventRepository::new(pool.as_ref().clone()))
+            })
+            .singleton(|resolver: ModuleResolver| async move {
+                let pool = resolver.get::<PgPool>().await?;
+                Ok(UserViewRepository::new(pool.as_ref().clone()))
+            })
+            .singleton(|resolver: ModuleResolver| async move {
+                let pool = resolver.get::<PgPool>().await?;
+                Ok(UserProjection::new(pool.as_ref().clone()))
+            })
+            .singleton(|resolver: ModuleResolver| async move {
+                let cache = resolver.get::<Arc<dyn Cache>>().await?;
+                Ok(UserCache::new(cache.as_ref().clone()))
+            })
+            .singleton(|resolver: ModuleResolver| async move {
+                Ok::<_, ContainerError>(UserRegisterCommandHandler::new(
+                    resolver.get::<UserRepository>().await?.as_ref().clone(),
+                    resolver.get::<UserSecurityRepository>().await?.as_ref().clone(),
+                    resolver.get::<UserEventRepository>().await?.as_ref().clone(),
+                    resolver.get::<Client>().await?.as_ref().clone(),
+                ))
+            })
+            .singleton(|resolver: ModuleResolver| async move {
+                Ok::<_, ContainerError>(UserQueryHandler::new(
+                    resolver.get::<UserViewRepository>().await?.as_ref().clone(),
+                    resolver.get::<UserCache>().await?.as_ref().clone(),
+                ))
+            })
+            .queue_consumer("user.registered", "user-projection", |resolver: ModuleResolver, message| async move {
+                let event = serde_json::from_slice::<UserEvent>(&message.payload).map_err(|e| e.to_string())?;
+                let projection = resolver.get::<UserProjection>().await.map_err(|e| e.to_string())?;
+                let cache = resolver.get::<UserCache>().await.map_err(|e| e.to_string())?;
+
+                projection.apply(&event).await.map_err(|e| e.to_string())?;
+                cache.invalidate(&event).await;
+
+                Ok(())
+            })
+            .projection(|resolver: ModuleResolver| async move {
+                let projection = resolver.get::<UserProjection>().await?;
+                Ok(projection.as_ref().clone())
+            })
+            .export::<UserRepository>()
+            .group(
+                RouteGroup::new("/user")
+                    .version(1)
+                    .tag("user")
+                    .post("", createUser)
+                    .doc(
+                        Operation::new()
+                            .summary("회원 가입")
+                            .description("`Idempotency-Key` 헤더를 보내면 같은 키로 재시도해도 한 번만 가입되고 첫 응답이 그대로 돌아옵니다.")
+                            .body::<UserRegisterCommand>()
+                            .response::<Envelope<UserRegisterCommandResult>>(StatusCode::CREATED, "가입 완료")
+                            .error(StatusCode::CONFLICT, "이미 가입된 이메일 또는 같은 `Idempotency-Key` 요청이 처리 중")
+                            .error(StatusCode::UNPROCESSABLE_ENTITY, "입력값 검증 실패 또는 다른 요청에 사용된 `Idempotency-Key`")
+                            .error(StatusCode::TOO_MANY_REQUESTS, "IP 당 시간당 가입 요청 한도 초과 (`Retry-After` 참고)")
+                            .error(StatusCode::SERVICE_UNAVAILABLE, "데이터베이스 또는 메시지 브로커 연결 실패"),
+                    )
+                    .get("", searchUsers)
+                    .doc(
+                        Operation::new()
+                            .summary("사용자 검색")
+                            .description("이름 또는 이메일로 검색하며 최근 가입 순으로 정렬합니다.")
+                            .query::<UserSearchParams>()
+                            .response::<Envelope<Vec<UserView>>>(StatusCode::OK, "검색 결과 (`meta.pagination` 포함)")
+                            .error(StatusCode::BAD_REQUEST, "잘못된 페이지 파라미터")
+                            .error(StatusCode::UNAUTHORIZED, "인증되지 않은 호출자")
+                            .error(StatusCode::FORBIDDEN, "`admin` 역할 없음")
+                            .security("bearer"),
+                    )
+                    .get("/events", streamUserEvents)
+                    .doc(
+                        Operation::new()
+                            .summary("사용자 이벤트 구독 (SSE)")
+                            .description("`user.*` 이벤트를 `text/event-stream` 으로 보냅니다. 이벤트 이름은 subject 이고, 15초마다 주석 줄로 연결을 유지합니다.")
+                            .empty_response(StatusCode::OK, "이벤트 스트림")
+                            .error(StatusCode::UNAUTHORIZED, "인증되지 않은 호출자")
+                            .error(StatusCode::FORBIDDEN, "`admin` 역할 없음")
+                            .error(StatusCode::SERVICE_UNAVAILABLE, "메시지 브로커 연결 실패")
+                            .security("bearer"),
+                    )
+                    .get("/events/ws", watchUserEvents)
+                    .doc(
+                        Operation::new()
+                            .summary("사용자 이벤트 구독 (WebSocket)")
+                            .description("이벤트마다 `{\"subject\": .., \"data\": ..}` 텍스트 프레임을 보냅니다. 클라이언트가 보내는 메시지는 무시합니다.")
+                            .empty_response(StatusCode::SWITCHING_PROTOCOLS, "WebSocket 연결")
+                            .error(StatusCode::BAD_REQUEST, "잘못된 WebSocket 업그레이드 요청")
+                            .error(StatusCode::UNAUTHORIZED, "인증되지 않은 호출자")
+                            .error(StatusCode::FORBIDDEN, "`admin` 역할 없음")
+                            .error(StatusCode::SERVICE_UNAVAILABLE, "메시지 브로커 연결 실패")
+                            .security("bearer"),
+                    )
+                    .get("/{id}", getUser)
+                    .doc(
+                        Operation::new()
+                            .summary("사용자 조회")
+                            .description("본인 또는 `admin` 역할만 조회할 수 있습니다.")
+                            .response::<Envelope<UserView>>(StatusCode::OK, "사용자 정보")
+                            .error(StatusCode::UNAUTHORIZED, "인증되지 않은 호출자")
+                            .error(StatusCode::FORBIDDEN, "본인이 아니고 `admin` 역할 없음")
+                            .error(StatusCode::NOT_FOUND, "존재하지 않는 사용자")
+                            .security("bearer"),
+                    ),
+            );
+    }
+}